@@ -0,0 +1,144 @@
+//! GraphQL queries and mutations on announcements.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::{DateTime, Utc};
+
+/// Type representing GraphQL query to get active announcements.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/announcements/active.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ActiveAnnouncements;
+
+/// Type representing GraphQL query to get recent announcements.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/announcements/list.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct Announcements;
+
+/// Type representing GraphQL query to get an announcement by ID.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/announcements/get.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct Announcement;
+
+/// Type representing GraphQL mutation to create an announcement.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/announcements/create.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct CreateAnnouncement;
+
+/// Type representing GraphQL mutation to edit an announcement.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/announcements/edit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct EditAnnouncement;
+
+/// Type representing GraphQL mutation to expire an announcement.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/announcements/expire.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ExpireAnnouncement;
+
+impl ActiveAnnouncements {
+    /// Get the announcements that have not expired, newest first.
+    pub async fn get(
+    ) -> Result<Vec<active_announcements::ActiveAnnouncementsAnnouncements>, TelescopeError> {
+        send_query::<Self>(active_announcements::Variables { now: Utc::now() })
+            .await
+            .map(|response| response.announcements)
+    }
+}
+
+impl Announcements {
+    /// Get the most recent announcements, newest first.
+    pub async fn get(
+        limit: i64,
+    ) -> Result<Vec<announcements::AnnouncementsAnnouncements>, TelescopeError> {
+        send_query::<Self>(announcements::Variables { limit })
+            .await
+            .map(|response| response.announcements)
+    }
+}
+
+impl Announcement {
+    /// Get an announcement by ID.
+    pub async fn get(
+        announcement_id: i64,
+    ) -> Result<Option<announcement::AnnouncementAnnouncement>, TelescopeError> {
+        send_query::<Self>(announcement::Variables { announcement_id })
+            .await
+            .map(|response| response.announcement)
+    }
+}
+
+impl CreateAnnouncement {
+    /// Create an announcement. Return the created announcement's ID.
+    pub async fn execute(
+        title: String,
+        body_markdown: String,
+        semester_id: String,
+        close_date_time: Option<DateTime<Utc>>,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(create_announcement::Variables {
+            title,
+            body_markdown,
+            semester_id,
+            close_date_time,
+        })
+        .await
+        .map(|response| {
+            response
+                .insert_announcements_one
+                .map(|obj| obj.announcement_id)
+        })
+    }
+}
+
+impl EditAnnouncement {
+    /// Edit an announcement. Return the ID of the announcement if it exists.
+    pub async fn execute(
+        vars: edit_announcement::Variables,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(vars).await.map(|response| {
+            response
+                .update_announcements_by_pk
+                .map(|obj| obj.announcement_id)
+        })
+    }
+}
+
+impl ExpireAnnouncement {
+    /// Expire an announcement now. Return the ID of the announcement if it exists.
+    pub async fn execute(announcement_id: i64) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(expire_announcement::Variables {
+            announcement_id,
+            now: Utc::now(),
+        })
+        .await
+        .map(|response| {
+            response
+                .update_announcements_by_pk
+                .map(|obj| obj.announcement_id)
+        })
+    }
+}
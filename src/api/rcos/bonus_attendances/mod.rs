@@ -0,0 +1,117 @@
+//! GraphQL queries and mutations on bonus attendance.
+//!
+//! Coordinators award bonus attendance for events outside of regular meetings, like
+//! hackathons. Each record counts as one attended meeting for one user in one semester.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::search_strings::resolve_search_string;
+use crate::api::rcos::{send_json_query, send_query};
+use crate::error::TelescopeError;
+use serde_json::Value;
+
+/// The GraphQL mutation to award bonus attendance to several users.
+const AWARD_MUTATION: &'static str =
+    include_str!("../../../../graphql/rcos/bonus_attendances/award.graphql");
+
+/// Type representing GraphQL query to get the bonus attendance in a semester.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/bonus_attendances/list.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct BonusAttendances;
+
+/// Type representing GraphQL query to search the users who can be awarded bonus attendance.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/bonus_attendances/list.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct BonusAttendanceCandidates;
+
+/// Type representing GraphQL mutation to remove a bonus attendance record.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/bonus_attendances/list.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct DeleteBonusAttendance;
+
+impl BonusAttendances {
+    /// Get the bonus attendance awarded in a semester, most recent first.
+    pub async fn get(
+        semester_id: String,
+    ) -> Result<Vec<bonus_attendances::BonusAttendancesBonusAttendances>, TelescopeError> {
+        send_query::<Self>(bonus_attendances::Variables { semester_id })
+            .await
+            .map(|response| response.bonus_attendances)
+    }
+}
+
+impl BonusAttendanceCandidates {
+    /// Search the users enrolled in a semester by name or username.
+    pub async fn get(
+        semester_id: String,
+        search: Option<String>,
+    ) -> Result<Vec<bonus_attendance_candidates::BonusAttendanceCandidatesUsers>, TelescopeError>
+    {
+        send_query::<Self>(bonus_attendance_candidates::Variables {
+            semester_id,
+            search: resolve_search_string(search),
+        })
+        .await
+        .map(|response| response.users)
+    }
+}
+
+impl DeleteBonusAttendance {
+    /// Remove a bonus attendance record. Return the removed record if it existed.
+    pub async fn execute(
+        bonus_attendance_id: i64,
+    ) -> Result<
+        Option<delete_bonus_attendance::DeleteBonusAttendanceDeleteBonusAttendancesByPk>,
+        TelescopeError,
+    > {
+        send_query::<Self>(delete_bonus_attendance::Variables {
+            bonus_attendance_id,
+        })
+        .await
+        .map(|response| response.delete_bonus_attendances_by_pk)
+    }
+}
+
+/// Award bonus attendance to several users in a semester for the same reason. This is done in
+/// JSON format to avoid the bulky generated type for the insert input. Return the number of
+/// records created.
+pub async fn award(
+    semester_id: &str,
+    usernames: &[String],
+    reason: &str,
+) -> Result<u64, TelescopeError> {
+    let objects: Vec<Value> = usernames
+        .iter()
+        .map(|username| {
+            json!({
+                "semester_id": semester_id,
+                "username": username,
+                "reason": reason,
+            })
+        })
+        .collect();
+
+    let response: Value = send_json_query(
+        "AwardBonusAttendance",
+        AWARD_MUTATION,
+        json!({ "objects": objects }),
+    )
+    .await?;
+
+    response["insert_bonus_attendances"]["affected_rows"]
+        .as_u64()
+        .ok_or(TelescopeError::ise(
+            "Bonus attendance insert did not return affected rows.",
+        ))
+}
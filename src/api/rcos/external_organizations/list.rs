@@ -0,0 +1,25 @@
+//! GraphQL query to list all external organizations.
+
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to list external organizations.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/external_organizations/list.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ExternalOrganizations;
+
+impl ExternalOrganizations {
+    /// Get all external organizations ordered by title.
+    pub async fn get() -> Result<
+        Vec<external_organizations::ExternalOrganizationsExternalOrganizations>,
+        TelescopeError,
+    > {
+        send_query::<Self>(external_organizations::Variables {})
+            .await
+            .map(|response| response.external_organizations)
+    }
+}
@@ -0,0 +1,5 @@
+//! GraphQL queries and mutations on external organizations (project sponsors).
+
+pub mod list;
+pub mod mutations;
+pub mod organization;
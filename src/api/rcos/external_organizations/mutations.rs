@@ -0,0 +1,92 @@
+//! GraphQL mutations to create, edit, and delete external organizations.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use url::Url;
+
+/// Type representing GraphQL mutation to create an external organization.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/external_organizations/create.graphql",
+    response_derives = "Debug,Copy,Clone,Serialize"
+)]
+pub struct CreateExternalOrganization;
+
+/// Type representing GraphQL mutation to edit an external organization.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/external_organizations/edit.graphql",
+    response_derives = "Debug,Copy,Clone,Serialize"
+)]
+pub struct EditExternalOrganization;
+
+/// Type representing GraphQL mutation to delete an external organization.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/external_organizations/delete.graphql",
+    response_derives = "Debug,Copy,Clone,Serialize"
+)]
+pub struct DeleteExternalOrganization;
+
+impl CreateExternalOrganization {
+    /// Create an external organization. Return the created organization's ID.
+    pub async fn execute(
+        title: String,
+        homepage: String,
+        contact_emails: Vec<Url>,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(create_external_organization::Variables {
+            title,
+            homepage,
+            contact_emails,
+        })
+        .await
+        .map(|response| {
+            response
+                .insert_external_organizations_one
+                .map(|obj| obj.external_organization_id)
+        })
+    }
+}
+
+impl EditExternalOrganization {
+    /// Edit an external organization. Return the organization's ID if it exists.
+    pub async fn execute(
+        external_organization_id: i64,
+        title: String,
+        homepage: String,
+        contact_emails: Vec<Url>,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(edit_external_organization::Variables {
+            external_organization_id,
+            title,
+            homepage,
+            contact_emails,
+        })
+        .await
+        .map(|response| {
+            response
+                .update_external_organizations_by_pk
+                .map(|obj| obj.external_organization_id)
+        })
+    }
+}
+
+impl DeleteExternalOrganization {
+    /// Delete an external organization. Return the organization's ID if it existed.
+    pub async fn execute(external_organization_id: i64) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(delete_external_organization::Variables {
+            external_organization_id,
+        })
+        .await
+        .map(|response| {
+            response
+                .delete_external_organizations_by_pk
+                .map(|obj| obj.external_organization_id)
+        })
+    }
+}
@@ -0,0 +1,52 @@
+//! GraphQL queries to get a single external organization.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to get an organization and its projects.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/external_organizations/organization.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct Organization;
+
+/// Type representing GraphQL query to get an organization's details to edit.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/external_organizations/get.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct GetExternalOrganization;
+
+impl Organization {
+    /// Get an organization and the projects it has sponsored by its ID.
+    pub async fn get(
+        external_organization_id: i64,
+    ) -> Result<Option<organization::OrganizationOrganization>, TelescopeError> {
+        send_query::<Self>(organization::Variables {
+            external_organization_id,
+        })
+        .await
+        .map(|response| response.organization)
+    }
+}
+
+impl GetExternalOrganization {
+    /// Get an organization's details, including contact emails, by its ID.
+    pub async fn get(
+        external_organization_id: i64,
+    ) -> Result<
+        Option<get_external_organization::GetExternalOrganizationOrganization>,
+        TelescopeError,
+    > {
+        send_query::<Self>(get_external_organization::Variables {
+            external_organization_id,
+        })
+        .await
+        .map(|response| response.organization)
+    }
+}
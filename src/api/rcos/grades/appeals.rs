@@ -0,0 +1,48 @@
+//! GraphQL query and mutation for the faculty advisor grade appeals inbox.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to get grade appeals.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/grades/appeals.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct GradeAppeals;
+
+/// Type representing GraphQL mutation to mark a grade appeal handled.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/grades/appeals.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SetAppealHandled;
+
+impl GradeAppeals {
+    /// Get all open grade appeals (oldest first) and the most recently handled ones.
+    pub async fn get(handled_limit: i64) -> Result<grade_appeals::ResponseData, TelescopeError> {
+        send_query::<Self>(grade_appeals::Variables { handled_limit }).await
+    }
+}
+
+impl SetAppealHandled {
+    /// Mark a grade appeal handled, or reopen it. Return `Ok(false)` if the appeal does not
+    /// exist.
+    pub async fn execute(
+        semester_id: String,
+        username: String,
+        is_handled: bool,
+    ) -> Result<bool, TelescopeError> {
+        send_query::<Self>(set_appeal_handled::Variables {
+            semester_id,
+            username,
+            is_handled,
+        })
+        .await
+        .map(|response| response.update_final_grade_appeal_by_pk.is_some())
+    }
+}
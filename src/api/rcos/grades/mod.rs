@@ -0,0 +1,8 @@
+//! GraphQL queries and mutations on grades and grade appeals.
+//!
+//! Grades are stored on enrollments. Students can appeal their grade for a semester once, and
+//! can update their appeal until a faculty advisor marks it handled.
+
+pub mod appeals;
+pub mod sheet;
+pub mod student;
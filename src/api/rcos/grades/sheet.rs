@@ -0,0 +1,58 @@
+//! GraphQL query and mutation for the faculty advisor grading sheet.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the grading sheet for a semester.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/grades/sheet.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct GradingSheet;
+
+/// Type representing GraphQL mutation to set a student's grades.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/grades/sheet.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SetGrades;
+
+impl GradingSheet {
+    /// Get the grading sheet for a semester. Return `None` if the semester does not exist.
+    pub async fn get(
+        semester_id: String,
+    ) -> Result<Option<grading_sheet::GradingSheetSemester>, TelescopeError> {
+        send_query::<Self>(grading_sheet::Variables {
+            semester_id,
+            now: Utc::now(),
+        })
+        .await
+        .map(|response| response.semester)
+    }
+}
+
+impl SetGrades {
+    /// Set a student's mid-year and final grades. Return `Ok(false)` if the student is not
+    /// enrolled in the semester.
+    pub async fn execute(
+        semester_id: String,
+        username: String,
+        mid_year_grade: Option<f64>,
+        final_grade: Option<f64>,
+    ) -> Result<bool, TelescopeError> {
+        send_query::<Self>(set_grades::Variables {
+            semester_id,
+            username,
+            mid_year_grade,
+            final_grade,
+        })
+        .await
+        .map(|response| response.update_enrollments_by_pk.is_some())
+    }
+}
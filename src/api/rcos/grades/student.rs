@@ -0,0 +1,57 @@
+//! GraphQL query and mutation for students to see their grades and appeal them.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to get a student's grades.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/grades/student.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct StudentGrades;
+
+/// Type representing GraphQL mutation to file a grade appeal.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/grades/student.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct UpsertGradeAppeal;
+
+impl StudentGrades {
+    /// Get a student's enrollments and grades, most recent semester first.
+    pub async fn get(
+        username: String,
+    ) -> Result<Vec<student_grades::StudentGradesEnrollments>, TelescopeError> {
+        send_query::<Self>(student_grades::Variables { username })
+            .await
+            .map(|response| response.enrollments)
+    }
+}
+
+impl UpsertGradeAppeal {
+    /// File or update a student's grade appeal for a semester.
+    pub async fn execute(
+        username: String,
+        semester_id: String,
+        expected_grade: String,
+        reason: String,
+    ) -> Result<(), TelescopeError> {
+        send_query::<Self>(upsert_grade_appeal::Variables {
+            username,
+            semester_id,
+            expected_grade,
+            reason,
+        })
+        .await?
+        .insert_final_grade_appeal_one
+        .ok_or(TelescopeError::ise(
+            "Grade appeal upsert did not return a record.",
+        ))
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,69 @@
+//! GraphQL queries and mutations for meeting check-in codes and attendance records.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to get a meeting's attendance code and attendees.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/meetings/attendance/attendance.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct MeetingAttendance;
+
+/// Type representing GraphQL mutation to set or clear a meeting's attendance code.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/meetings/attendance/set_code.graphql",
+    response_derives = "Debug,Copy,Clone,Serialize"
+)]
+pub struct SetAttendanceCode;
+
+/// Type representing GraphQL mutation to record a user's attendance at a meeting.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/meetings/attendance/record.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct RecordAttendance;
+
+impl MeetingAttendance {
+    /// Get the attendance data for a meeting by its ID.
+    pub async fn get(
+        meeting_id: i64,
+    ) -> Result<Option<meeting_attendance::MeetingAttendanceMeeting>, TelescopeError> {
+        send_query::<Self>(meeting_attendance::Variables { meeting_id })
+            .await
+            .map(|response| response.meeting)
+    }
+}
+
+impl SetAttendanceCode {
+    /// Set the attendance code of a meeting. Use `None` to close check-in.
+    /// Return the ID of the meeting if it exists.
+    pub async fn execute(
+        meeting_id: i64,
+        code: Option<String>,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(set_attendance_code::Variables { meeting_id, code })
+            .await
+            .map(|response| response.update_meetings_by_pk.map(|obj| obj.meeting_id))
+    }
+}
+
+impl RecordAttendance {
+    /// Record a user's attendance at a meeting. This returns `Ok(false)` if the user
+    /// had already checked in.
+    pub async fn execute(meeting_id: i64, username: String) -> Result<bool, TelescopeError> {
+        send_query::<Self>(record_attendance::Variables {
+            meeting_id,
+            username,
+        })
+        .await
+        .map(|response| response.insert_meeting_attendances_one.is_some())
+    }
+}
@@ -0,0 +1,30 @@
+//! GraphQL query for the per-semester attendance report.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the attendance of every student in a semester.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/meetings/attendance/report.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct AttendanceReport;
+
+impl AttendanceReport {
+    /// Get the attendance report data for a semester, counting meetings that have started so
+    /// far. Return `None` if the semester does not exist.
+    pub async fn get(
+        semester_id: String,
+    ) -> Result<Option<attendance_report::AttendanceReportSemester>, TelescopeError> {
+        send_query::<Self>(attendance_report::Variables {
+            semester_id,
+            now: Utc::now(),
+        })
+        .await
+        .map(|response| response.semester)
+    }
+}
@@ -1,10 +1,9 @@
 //! GraphQL mutation to create a meeting.
 
-use crate::api::rcos::meetings::MeetingType;
+use crate::api::rcos::meetings::delete::DeleteMeeting;
 use crate::api::rcos::prelude::*;
 use crate::api::rcos::send_query;
 use crate::error::TelescopeError;
-use chrono::{DateTime, Utc};
 
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -22,37 +21,48 @@ pub fn normalize_url(url: Option<String>) -> Option<String> {
 impl CreateMeeting {
     /// Execute a meeting creation mutation. Return the created meeting's ID.
     pub async fn execute(
-        host_username: Option<String>,
-        title: Option<String>,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-        description: String,
-        is_draft: bool,
-        is_remote: bool,
-        location: Option<String>,
-        meeting_url: Option<String>,
-        recording_url: Option<String>,
-        external_slides_url: Option<String>,
-        semester_id: String,
-        kind: MeetingType,
+        mut vars: create_meeting::Variables,
     ) -> Result<Option<i64>, TelescopeError> {
-        send_query::<Self>(create_meeting::Variables {
-            host_username,
-            title,
-            start,
-            end,
-            description,
-            is_draft,
-            is_remote,
-            location,
-            // Coerce an empty or whitespace string to none.
-            meeting_url: normalize_url(meeting_url),
-            recording_url: normalize_url(recording_url),
-            external_slides_url: normalize_url(external_slides_url),
-            semester_id,
-            kind,
-        })
-        .await
-        .map(|response| response.insert_meetings_one.map(|obj| obj.meeting_id))
+        // Coerce an empty or whitespace string to none.
+        vars.meeting_url = normalize_url(vars.meeting_url);
+        vars.recording_url = normalize_url(vars.recording_url);
+        vars.external_slides_url = normalize_url(vars.external_slides_url);
+
+        send_query::<Self>(vars)
+            .await
+            .map(|response| response.insert_meetings_one.map(|obj| obj.meeting_id))
+    }
+}
+
+/// Create several meetings, such as the occurrences of a recurring meeting. If any of them
+/// cannot be created, the ones that were are deleted again so that a series is never left
+/// half created. Return the IDs of the created meetings.
+pub async fn create_meetings(
+    meetings: Vec<create_meeting::Variables>,
+) -> Result<Vec<i64>, TelescopeError> {
+    let mut created: Vec<i64> = Vec::with_capacity(meetings.len());
+    for meeting in meetings {
+        let result: Result<i64, TelescopeError> =
+            CreateMeeting::execute(meeting).await.and_then(|id| {
+                id.ok_or(TelescopeError::ise(
+                    "Meeting creation call did not return ID.",
+                ))
+            });
+
+        match result {
+            Ok(meeting_id) => created.push(meeting_id),
+            Err(err) => {
+                for meeting_id in created {
+                    if let Err(delete_err) = DeleteMeeting::execute(meeting_id).await {
+                        error!(
+                            "Could not delete meeting {} from a partly created series: {}",
+                            meeting_id, delete_err
+                        );
+                    }
+                }
+                return Err(err);
+            }
+        }
     }
+    return Ok(created);
 }
@@ -1,8 +1,10 @@
-//! Meeting edit mutation and host selection query.
+//! Meeting edit mutation, host selection query, and following occurrences query.
 
+use crate::api::rcos::meetings::MeetingType;
 use crate::api::rcos::prelude::*;
 use crate::api::rcos::send_query;
 use crate::error::TelescopeError;
+use chrono::{DateTime, Utc};
 
 /// Type representing GraphQL meeting edit mutation.
 #[derive(GraphQLQuery)]
@@ -38,3 +40,62 @@ impl EditHostSelection {
         send_query::<Self>(edit_host_selection::Variables { meeting_id }).await
     }
 }
+
+/// Type representing query for meetings that may be later occurrences of a recurring meeting.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/meetings/edit/following.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct FollowingMeetings;
+
+impl FollowingMeetings {
+    /// Get the meetings of a type in a semester that start after a given time.
+    pub async fn get(
+        semester_id: String,
+        kind: MeetingType,
+        after: DateTime<Utc>,
+    ) -> Result<Vec<following_meetings::FollowingMeetingsMeetings>, TelescopeError> {
+        send_query::<Self>(following_meetings::Variables {
+            semester_id,
+            kind,
+            after,
+        })
+        .await
+        .map(|response| response.meetings)
+    }
+}
+
+/// Apply several meeting edits one at a time. Each edit is paired with the edit that undoes it.
+/// If any edit fails, the ones already made are undone, so that a series of meetings is never
+/// left half edited.
+pub async fn edit_meetings(
+    edits: Vec<(edit_meeting::Variables, edit_meeting::Variables)>,
+) -> Result<(), TelescopeError> {
+    let mut made: Vec<edit_meeting::Variables> = Vec::with_capacity(edits.len());
+    for (edit, undo) in edits {
+        let meeting_id: i64 = edit.meeting_id;
+        let result: Result<i64, TelescopeError> = EditMeeting::execute(edit).await.and_then(|id| {
+            id.ok_or(TelescopeError::ise(format!(
+                "Meeting {} could not be edited.",
+                meeting_id
+            )))
+        });
+
+        match result {
+            Ok(_) => made.push(undo),
+            Err(err) => {
+                // Undo the edits already made, latest first.
+                for undo in made.into_iter().rev() {
+                    let undo_id: i64 = undo.meeting_id;
+                    if let Err(undo_err) = EditMeeting::execute(undo).await {
+                        error!("Could not undo edit to meeting {}: {}", undo_id, undo_err);
+                    }
+                }
+                return Err(err);
+            }
+        }
+    }
+    return Ok(());
+}
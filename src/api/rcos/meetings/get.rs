@@ -1,5 +1,6 @@
 //! List meetings query.
 
+use crate::api::rcos::meetings::get_by_id::meeting_title;
 use crate::api::rcos::meetings::MeetingType;
 use crate::api::rcos::prelude::*;
 use crate::api::rcos::send_query;
@@ -35,3 +36,10 @@ impl Meetings {
         .meetings)
     }
 }
+
+impl MeetingsMeetings {
+    /// Get the title of this meeting. See [`meeting_title`].
+    pub fn title(&self) -> String {
+        meeting_title(self.title.as_deref(), self.type_, &self.start_date_time)
+    }
+}
@@ -1,8 +1,10 @@
 //! GraphQL query to get a meeting by its ID.
 
+use crate::api::rcos::meetings::MeetingType;
 use crate::api::rcos::prelude::*;
 use crate::api::rcos::send_query;
 use crate::error::TelescopeError;
+use chrono::{DateTime, Utc};
 
 /// Type representing public RCOS meetings.
 #[derive(GraphQLQuery)]
@@ -30,16 +32,26 @@ impl MeetingMeeting {
     /// Get the title of this meeting. This is the user-defined title if there is one, otherwise
     /// a title is constructed from the start date and meeting type.
     pub fn title(&self) -> String {
-        // Check for a user-defined title.
-        if self.title.is_some() {
-            return self.title.clone().unwrap();
-        }
+        meeting_title(self.title.as_deref(), self.type_, &self.start_date_time)
+    }
+}
 
-        // Otherwise create a title.
-        format!(
-            "RCOS {} - {}",
-            self.type_,
-            self.start_date_time.format("%B %_d, %Y")
-        )
+/// Get the title of a meeting. This is the user-defined title if there is one, otherwise
+/// a title is constructed from the start date and meeting type.
+pub fn meeting_title(
+    title: Option<&str>,
+    meeting_type: MeetingType,
+    start_date_time: &DateTime<Utc>,
+) -> String {
+    // Check for a user-defined title.
+    if let Some(title) = title {
+        return title.to_string();
     }
+
+    // Otherwise create a title.
+    format!(
+        "RCOS {} - {}",
+        meeting_type,
+        start_date_time.format("%B %_d, %Y")
+    )
 }
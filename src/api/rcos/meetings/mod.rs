@@ -1,5 +1,7 @@
 //! Queries and mutations to the RCOS API for meeting data.
 
+pub mod attendance;
+pub mod attendance_report;
 pub mod authorization_for;
 pub mod creation;
 pub mod delete;
@@ -0,0 +1,28 @@
+//! GraphQL query to get the context for the mentor application form.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the mentor application form context.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/mentor_proposals/context.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct MentorProposalContext;
+
+impl MentorProposalContext {
+    /// Get the mentor application form context for a user.
+    pub async fn get(
+        username: String,
+    ) -> Result<mentor_proposal_context::ResponseData, TelescopeError> {
+        send_query::<Self>(mentor_proposal_context::Variables {
+            username,
+            now: Utc::today().naive_utc(),
+        })
+        .await
+    }
+}
@@ -0,0 +1,9 @@
+//! GraphQL queries and mutations on mentor proposals.
+//!
+//! Each user can apply to mentor once per semester. Proposals are pending until they are
+//! approved or rejected. Only users with an approved proposal can be assigned to mentor a
+//! small group that semester.
+
+pub mod context;
+pub mod review;
+pub mod submit;
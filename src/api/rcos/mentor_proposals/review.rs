@@ -0,0 +1,83 @@
+//! GraphQL queries and mutations to review mentor proposals.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the mentor proposals in ongoing semesters.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/mentor_proposals/queue.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct MentorProposalQueue;
+
+/// Type representing GraphQL query to get a mentor proposal for review.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/mentor_proposals/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct MentorProposal;
+
+/// Type representing GraphQL mutation to approve or reject a mentor proposal.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/mentor_proposals/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ReviewMentorProposal;
+
+impl MentorProposalQueue {
+    /// Get the mentor proposals in ongoing semesters, oldest first.
+    pub async fn get(
+    ) -> Result<Vec<mentor_proposal_queue::MentorProposalQueueMentorProposals>, TelescopeError>
+    {
+        send_query::<Self>(mentor_proposal_queue::Variables {
+            now: Utc::today().naive_utc(),
+        })
+        .await
+        .map(|response| response.mentor_proposals)
+    }
+}
+
+impl MentorProposal {
+    /// Get a mentor proposal and whether the reviewer is enrolled in its semester.
+    pub async fn get(
+        semester_id: String,
+        username: String,
+        reviewer: String,
+    ) -> Result<mentor_proposal::ResponseData, TelescopeError> {
+        send_query::<Self>(mentor_proposal::Variables {
+            semester_id,
+            username,
+            reviewer,
+        })
+        .await
+    }
+}
+
+impl ReviewMentorProposal {
+    /// Approve or reject a mentor proposal. Return `Ok(false)` if the proposal does not exist.
+    pub async fn execute(
+        semester_id: String,
+        username: String,
+        is_approved: bool,
+        reviewer_username: String,
+        reviewer_comments: Option<String>,
+    ) -> Result<bool, TelescopeError> {
+        send_query::<Self>(review_mentor_proposal::Variables {
+            semester_id,
+            username,
+            is_approved,
+            reviewer_username,
+            reviewer_comments,
+        })
+        .await
+        .map(|response| response.update_mentor_proposals_by_pk.is_some())
+    }
+}
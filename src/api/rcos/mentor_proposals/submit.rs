@@ -0,0 +1,32 @@
+//! GraphQL mutation to submit a mentor proposal.
+
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL mutation to submit a mentor proposal.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/mentor_proposals/submit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SubmitMentorProposal;
+
+impl SubmitMentorProposal {
+    /// Submit a mentor proposal, replacing the user's existing proposal for the semester.
+    pub async fn execute(
+        username: String,
+        semester_id: String,
+        reason: String,
+        skillset: String,
+    ) -> Result<(), TelescopeError> {
+        send_query::<Self>(submit_mentor_proposal::Variables {
+            username,
+            semester_id,
+            reason,
+            skillset,
+        })
+        .await
+        .map(|_| ())
+    }
+}
@@ -8,15 +8,25 @@ use graphql_client::{GraphQLQuery, QueryBody, Response as GraphQlResponse};
 use reqwest::{header::HeaderValue, header::ACCEPT, Client};
 use serde_json::Value;
 
+pub mod announcements;
 mod auth;
+pub mod bonus_attendances;
 pub mod discord_assoications;
+pub mod external_organizations;
+pub mod grades;
 pub mod landing_page_stats;
 pub mod meetings;
+pub mod mentor_proposals;
+pub mod pay_requests;
 pub mod prelude;
+pub mod project_pitches;
 pub mod projects;
 pub mod search_strings;
 pub mod semesters;
+pub mod small_groups;
+pub mod status_updates;
 pub mod users;
+pub mod workshop_proposals;
 
 /// The name of this API in error messages.
 const API_NAME: &'static str = "RCOS Central Hasura GraphQL API";
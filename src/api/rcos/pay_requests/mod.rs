@@ -0,0 +1,7 @@
+//! GraphQL queries and mutations on pay requests.
+//!
+//! Students who want to take RCOS for pay file a pay request with their enrollment. Their
+//! enrollment is only marked for pay once a faculty advisor approves the request.
+
+pub mod review;
+pub mod submit;
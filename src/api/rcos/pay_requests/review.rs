@@ -0,0 +1,72 @@
+//! GraphQL queries and mutations for faculty advisors to review pay requests.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to get the pay requests in a semester.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/pay_requests/list.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct PayRequests;
+
+/// Type representing GraphQL mutation to approve or deny a pay request.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/pay_requests/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ReviewPayRequest;
+
+/// Type representing GraphQL query to get the approved for-pay students in a semester.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/pay_requests/export.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ForPayStudents;
+
+impl PayRequests {
+    /// Get the pay requests in a semester, oldest first.
+    pub async fn get(
+        semester_id: String,
+    ) -> Result<Vec<pay_requests::PayRequestsPayRequests>, TelescopeError> {
+        send_query::<Self>(pay_requests::Variables { semester_id })
+            .await
+            .map(|response| response.pay_requests)
+    }
+}
+
+impl ReviewPayRequest {
+    /// Approve or deny a pay request, marking the enrollment for pay if it is approved.
+    /// Return `Ok(false)` if the pay request does not exist.
+    pub async fn execute(
+        semester_id: String,
+        username: String,
+        is_approved: bool,
+    ) -> Result<bool, TelescopeError> {
+        send_query::<Self>(review_pay_request::Variables {
+            semester_id,
+            username,
+            is_approved,
+        })
+        .await
+        .map(|response| response.update_pay_requests_by_pk.is_some())
+    }
+}
+
+impl ForPayStudents {
+    /// Get the enrollments in a semester that are approved for pay, sorted by name.
+    pub async fn get(
+        semester_id: String,
+    ) -> Result<Vec<for_pay_students::ForPayStudentsEnrollments>, TelescopeError> {
+        send_query::<Self>(for_pay_students::Variables { semester_id })
+            .await
+            .map(|response| response.enrollments)
+    }
+}
@@ -0,0 +1,51 @@
+//! GraphQL mutations to file and withdraw pay requests.
+
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL mutation to file a pay request.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/pay_requests/submit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct UpsertPayRequest;
+
+/// Type representing GraphQL mutation to withdraw a pay request.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/pay_requests/submit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct DeletePayRequest;
+
+impl UpsertPayRequest {
+    /// File a pay request for a user's enrollment in a semester. The enrollment must exist.
+    pub async fn execute(
+        username: String,
+        semester_id: String,
+        reason: String,
+    ) -> Result<(), TelescopeError> {
+        send_query::<Self>(upsert_pay_request::Variables {
+            username,
+            semester_id,
+            reason,
+        })
+        .await
+        .map(|_| ())
+    }
+}
+
+impl DeletePayRequest {
+    /// Withdraw a user's pay request for a semester, if they have one.
+    pub async fn execute(username: String, semester_id: String) -> Result<(), TelescopeError> {
+        send_query::<Self>(delete_pay_request::Variables {
+            username,
+            semester_id,
+        })
+        .await
+        .map(|_| ())
+    }
+}
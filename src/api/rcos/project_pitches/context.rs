@@ -0,0 +1,26 @@
+//! GraphQL query to get the context for the project pitch form.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the project pitch form context.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/project_pitches/context.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct PitchContext;
+
+impl PitchContext {
+    /// Get the project pitch form context for a user.
+    pub async fn get(username: String) -> Result<pitch_context::ResponseData, TelescopeError> {
+        send_query::<Self>(pitch_context::Variables {
+            username,
+            now: Utc::today().naive_utc(),
+        })
+        .await
+    }
+}
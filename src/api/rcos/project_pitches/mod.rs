@@ -0,0 +1,9 @@
+//! GraphQL queries and mutations on project pitches.
+//!
+//! Each user can pitch one project per semester -- either a new project or an existing one.
+//! Pitches are pending until a reviewer is set. Rejected pitches can be revised and
+//! resubmitted, which clears the review.
+
+pub mod context;
+pub mod review;
+pub mod submit;
@@ -0,0 +1,150 @@
+//! GraphQL queries and mutations to review project pitches.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the pitches in ongoing semesters.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/project_pitches/queue.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct PitchReviewQueue;
+
+/// Type representing GraphQL query to get a pitch for review.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/project_pitches/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ProjectPitch;
+
+/// Type representing GraphQL mutation to reject a pitch.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/project_pitches/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct RejectPitch;
+
+/// Type representing GraphQL mutation to approve a pitch.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/project_pitches/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ApprovePitch;
+
+/// Type representing GraphQL mutation to approve a pitch for a new project, creating the project.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/project_pitches/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ApprovePitchWithProject;
+
+impl PitchReviewQueue {
+    /// Get the pitches in ongoing semesters, oldest first.
+    pub async fn get(
+    ) -> Result<Vec<pitch_review_queue::PitchReviewQueueProjectPitches>, TelescopeError> {
+        send_query::<Self>(pitch_review_queue::Variables {
+            now: Utc::today().naive_utc(),
+        })
+        .await
+        .map(|response| response.project_pitches)
+    }
+}
+
+impl ProjectPitch {
+    /// Get a pitch and whether the reviewer is enrolled in the pitch's semester.
+    pub async fn get(
+        semester_id: String,
+        username: String,
+        reviewer: String,
+    ) -> Result<project_pitch::ResponseData, TelescopeError> {
+        send_query::<Self>(project_pitch::Variables {
+            semester_id,
+            username,
+            reviewer,
+        })
+        .await
+    }
+}
+
+impl RejectPitch {
+    /// Reject a pitch. Return `Ok(false)` if the pitch does not exist.
+    pub async fn execute(
+        semester_id: String,
+        username: String,
+        reviewer_username: String,
+        reviewer_comments: Option<String>,
+    ) -> Result<bool, TelescopeError> {
+        send_query::<Self>(reject_pitch::Variables {
+            semester_id,
+            username,
+            reviewer_username,
+            reviewer_comments,
+        })
+        .await
+        .map(|response| response.update_project_pitches_by_pk.is_some())
+    }
+}
+
+impl ApprovePitch {
+    /// Approve a pitch for an existing project and add the pitcher to that project.
+    /// Return `Ok(false)` if the pitch or the pitcher's enrollment does not exist.
+    pub async fn execute(
+        semester_id: String,
+        username: String,
+        reviewer_username: String,
+        reviewer_comments: Option<String>,
+        project_id: i64,
+    ) -> Result<bool, TelescopeError> {
+        send_query::<Self>(approve_pitch::Variables {
+            semester_id,
+            username,
+            reviewer_username,
+            reviewer_comments,
+            project_id,
+        })
+        .await
+        .map(|response| {
+            response.update_project_pitches_by_pk.is_some()
+                && response.update_enrollments_by_pk.is_some()
+        })
+    }
+}
+
+impl ApprovePitchWithProject {
+    /// Approve a pitch for a new project, creating the project and making the pitcher its lead.
+    /// Return the created project's ID.
+    pub async fn execute(
+        semester_id: String,
+        username: String,
+        reviewer_username: String,
+        reviewer_comments: Option<String>,
+        title: String,
+        description: String,
+        stack: Vec<String>,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(approve_pitch_with_project::Variables {
+            semester_id,
+            username,
+            reviewer_username,
+            reviewer_comments,
+            title,
+            description,
+            stack,
+            repository_urls: Vec::new(),
+        })
+        .await
+        .map(|response| response.insert_projects_one.map(|obj| obj.project_id))
+    }
+}
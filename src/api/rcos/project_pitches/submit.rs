@@ -0,0 +1,21 @@
+//! GraphQL mutation to submit a project pitch.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL mutation to submit a project pitch.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/project_pitches/submit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SubmitPitch;
+
+impl SubmitPitch {
+    /// Submit a project pitch, replacing the user's existing pitch for the semester.
+    pub async fn execute(vars: submit_pitch::Variables) -> Result<(), TelescopeError> {
+        send_query::<Self>(vars).await.map(|_| ())
+    }
+}
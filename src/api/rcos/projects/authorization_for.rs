@@ -0,0 +1,104 @@
+//! GraphQL query to check what a user can do with projects.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::api::rcos::users::UserRole;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get a user's project authorization info.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/projects/authorization_for.graphql"
+)]
+pub struct ProjectAuthorizationFor;
+
+/// Info on the user that dictates their ability to manage projects.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserProjectAuthorization {
+    /// The user's username.
+    pub username: Option<String>,
+    /// The user's role. Admins can manage any project.
+    role: UserRole,
+    /// Is this user a coordinator during an ongoing semester?
+    is_current_coordinator: bool,
+    /// The IDs of the projects this user leads in an ongoing semester.
+    projects_led: Vec<i64>,
+}
+
+impl Default for UserProjectAuthorization {
+    fn default() -> Self {
+        UserProjectAuthorization {
+            username: None,
+            role: UserRole::External,
+            is_current_coordinator: false,
+            projects_led: Vec::new(),
+        }
+    }
+}
+
+impl UserProjectAuthorization {
+    /// Can the user associated with this authorization manage any project?
+    /// This is coordinators and admins.
+    pub fn can_manage_all(&self) -> bool {
+        self.is_current_coordinator || self.role.is_admin()
+    }
+
+    /// Can the user associated with this authorization create projects?
+    pub fn can_create_projects(&self) -> bool {
+        self.can_manage_all()
+    }
+
+    /// Can the user associated with this authorization edit a given project?
+    /// Project leads can edit the projects they currently lead.
+    pub fn can_edit(&self, project_id: i64) -> bool {
+        self.can_manage_all() || self.projects_led.contains(&project_id)
+    }
+
+    /// Can the user associated with this authorization delete projects?
+    pub fn can_delete_projects(&self) -> bool {
+        self.can_manage_all()
+    }
+}
+
+impl ProjectAuthorizationFor {
+    /// Get the project authorization rules for a given user.
+    pub async fn get(username: Option<String>) -> Result<UserProjectAuthorization, TelescopeError> {
+        // Users that are not signed in have the lowest authorization.
+        if username.is_none() {
+            return Ok(UserProjectAuthorization::default());
+        }
+
+        let username: String = username.unwrap();
+        let response = send_query::<Self>(project_authorization_for::Variables {
+            username: username.clone(),
+            now: Utc::today().naive_utc(),
+        })
+        .await?;
+
+        let role: UserRole = response
+            .users_by_pk
+            .map(|user| user.role)
+            .unwrap_or(UserRole::External);
+
+        let is_current_coordinator: bool = response
+            .current_enrollments
+            .iter()
+            .any(|enrollment| enrollment.is_coordinator);
+
+        let projects_led: Vec<i64> = response
+            .current_enrollments
+            .iter()
+            .filter(|enrollment| enrollment.is_project_lead)
+            .filter_map(|enrollment| enrollment.project_id)
+            .collect();
+
+        return Ok(UserProjectAuthorization {
+            username: Some(username),
+            role,
+            is_current_coordinator,
+            projects_led,
+        });
+    }
+}
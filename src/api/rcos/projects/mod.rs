@@ -1,3 +1,7 @@
 //! RCOS API interactions related to projects.
 
+pub mod authorization_for;
+pub mod mutations;
+pub mod presentations;
+pub mod project;
 pub mod projects_page;
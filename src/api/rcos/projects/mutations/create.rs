@@ -0,0 +1,40 @@
+//! GraphQL mutation to create a project.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use url::Url;
+
+/// Type representing GraphQL mutation to create a project.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/projects/mutations/create.graphql",
+    response_derives = "Debug,Copy,Clone,Serialize"
+)]
+pub struct CreateProject;
+
+impl CreateProject {
+    /// Create a project. Return the created project's ID.
+    pub async fn execute(
+        title: String,
+        description: String,
+        stack: Vec<String>,
+        repository_urls: Vec<Url>,
+        homepage_url: Option<String>,
+        cover_image_url: Option<String>,
+        external_organization_id: Option<i64>,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(create_project::Variables {
+            title,
+            description,
+            stack,
+            repository_urls,
+            homepage_url,
+            cover_image_url,
+            external_organization_id,
+        })
+        .await
+        .map(|response| response.insert_projects_one.map(|obj| obj.project_id))
+    }
+}
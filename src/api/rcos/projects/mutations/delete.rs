@@ -0,0 +1,23 @@
+//! GraphQL mutation to delete a project.
+
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL mutation to delete a project and its associations.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/projects/mutations/delete.graphql",
+    response_derives = "Debug,Clone,Serialize",
+    variables_derives = "Debug,Clone,Copy"
+)]
+pub struct DeleteProject;
+
+impl DeleteProject {
+    /// Delete a project. Return the ID of the deleted project if it existed.
+    pub async fn execute(project_id: i64) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(delete_project::Variables { project_id })
+            .await
+            .map(|response| response.delete_projects_by_pk.map(|obj| obj.project_id))
+    }
+}
@@ -0,0 +1,23 @@
+//! GraphQL mutation to edit a project.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL mutation to edit a project.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/projects/mutations/edit.graphql",
+    response_derives = "Debug,Copy,Clone,Serialize"
+)]
+pub struct EditProject;
+
+impl EditProject {
+    /// Execute a project edit mutation. Return the ID of the edited project if it exists.
+    pub async fn execute(vars: edit_project::Variables) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(vars)
+            .await
+            .map(|response| response.update_projects_by_pk.map(|obj| obj.project_id))
+    }
+}
@@ -0,0 +1,5 @@
+//! Mutations on projects in the RCOS database.
+
+pub mod create;
+pub mod delete;
+pub mod edit;
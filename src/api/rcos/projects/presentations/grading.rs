@@ -0,0 +1,63 @@
+//! GraphQL query and mutation for graders to grade project presentations.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the presentations to grade this semester.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/projects/presentations/grading.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct PresentationGrading;
+
+/// Type representing GraphQL mutation to grade a project presentation.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/projects/presentations/grading.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct UpsertPresentationGrade;
+
+impl PresentationGrading {
+    /// Get the ongoing semester's small groups with their projects' presentations, the
+    /// grades given so far, and the given user's grades.
+    pub async fn get(
+        username: String,
+    ) -> Result<Option<presentation_grading::PresentationGradingCurrentSemester>, TelescopeError>
+    {
+        send_query::<Self>(presentation_grading::Variables {
+            username,
+            now: Utc::today().naive_utc(),
+        })
+        .await
+        .map(|response| response.current_semester.into_iter().next())
+    }
+}
+
+impl UpsertPresentationGrade {
+    /// Set a grader's grade for a project's presentation in a semester.
+    pub async fn execute(
+        project_id: i64,
+        semester_id: String,
+        grader_username: String,
+        grade: f64,
+    ) -> Result<(), TelescopeError> {
+        send_query::<Self>(upsert_presentation_grade::Variables {
+            project_id,
+            semester_id,
+            grader_username,
+            grade,
+        })
+        .await?
+        .insert_project_presentation_grades_one
+        .ok_or(TelescopeError::ise(
+            "Presentation grade upsert did not return a record.",
+        ))
+        .map(|_| ())
+    }
+}
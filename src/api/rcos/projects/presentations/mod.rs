@@ -0,0 +1,8 @@
+//! GraphQL queries and mutations on end-of-semester project presentations.
+//!
+//! Each project has at most one presentation per semester, submitted by its project lead.
+//! Graders each give a presentation one grade, and the project's presentation grade is the
+//! average of those.
+
+pub mod grading;
+pub mod submit;
@@ -0,0 +1,60 @@
+//! GraphQL query and mutation for project leads to submit presentations.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the presentation form context.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/projects/presentations/submit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct PresentationContext;
+
+/// Type representing GraphQL mutation to submit a project presentation.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/projects/presentations/submit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct UpsertPresentation;
+
+impl PresentationContext {
+    /// Get the presentation form context for a project.
+    pub async fn get(
+        project_id: i64,
+    ) -> Result<presentation_context::ResponseData, TelescopeError> {
+        send_query::<Self>(presentation_context::Variables {
+            project_id,
+            now: Utc::today().naive_utc(),
+        })
+        .await
+    }
+}
+
+impl UpsertPresentation {
+    /// Submit or update a project's presentation for a semester.
+    pub async fn execute(
+        project_id: i64,
+        semester_id: String,
+        presentation_url: String,
+        is_draft: bool,
+    ) -> Result<(), TelescopeError> {
+        send_query::<Self>(upsert_presentation::Variables {
+            project_id,
+            semester_id,
+            presentation_url,
+            is_draft,
+        })
+        .await?
+        .insert_project_presentations_one
+        .ok_or(TelescopeError::ise(
+            "Presentation upsert did not return a record.",
+        ))
+        .map(|_| ())
+    }
+}
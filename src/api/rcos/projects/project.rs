@@ -0,0 +1,28 @@
+//! GraphQL query to get a project's details by its ID.
+
+use crate::api::rcos::{prelude::*, send_query};
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get a project's details.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/projects/project.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct Project;
+
+impl Project {
+    /// Get a project by its ID.
+    pub async fn get_by_id(
+        project_id: i64,
+    ) -> Result<Option<project::ProjectProject>, TelescopeError> {
+        send_query::<Self>(project::Variables {
+            project_id,
+            now: Utc::today().naive_utc(),
+        })
+        .await
+        .map(|response| response.project)
+    }
+}
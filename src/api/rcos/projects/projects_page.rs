@@ -5,7 +5,7 @@ use crate::error::TelescopeError;
 use chrono::Utc;
 
 /// Projects per page.
-const PER_PAGE: u32 = 20;
+pub const PER_PAGE: u32 = 20;
 
 /// GraphQL query to get projects with enrollments in an ongoing semester.
 #[derive(GraphQLQuery)]
@@ -0,0 +1,40 @@
+//! GraphQL query to get the coordinators of the current semesters.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query for current coordinators.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/semesters/current/coordinators.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct CurrentCoordinators;
+
+impl CurrentCoordinators {
+    /// Get the usernames of everyone coordinating an ongoing semester.
+    pub async fn get() -> Result<Vec<String>, TelescopeError> {
+        send_query::<Self>(current_coordinators::Variables {
+            now: Utc::today().naive_utc(),
+        })
+        .await
+        .map(|response| {
+            response
+                .current_semesters
+                .into_iter()
+                .flat_map(|semester| semester.coordinators)
+                .map(|coordinator| coordinator.username)
+                .collect()
+        })
+    }
+
+    /// Is the given user coordinating an ongoing semester?
+    pub async fn contains(username: &str) -> Result<bool, TelescopeError> {
+        Self::get()
+            .await
+            .map(|coordinators| coordinators.iter().any(|c| c == username))
+    }
+}
@@ -1,3 +1,4 @@
 //! GraphQL operations on the current semesters.
 
+pub mod coordinators;
 pub mod info;
@@ -0,0 +1,23 @@
+//! GraphQL query to list every semester.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to list all semesters.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/semesters/list.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SemesterList;
+
+impl SemesterList {
+    /// Get all semesters, most recent first.
+    pub async fn get() -> Result<Vec<semester_list::SemesterListSemesters>, TelescopeError> {
+        send_query::<Self>(semester_list::Variables {})
+            .await
+            .map(|response| response.semesters)
+    }
+}
@@ -0,0 +1,85 @@
+//! GraphQL query to check what a user can do with small groups.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::api::rcos::users::UserRole;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get a user's small group authorization info.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/authorization_for.graphql"
+)]
+pub struct SmallGroupAuthorizationFor;
+
+/// Info on the user that dictates their ability to manage and mentor small groups.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserSmallGroupAuthorization {
+    /// The user's username.
+    pub username: Option<String>,
+    /// The user's role. Admins can manage small groups.
+    role: UserRole,
+    /// Is this user a coordinator during an ongoing semester?
+    is_current_coordinator: bool,
+    /// The IDs of the small groups this user mentors.
+    mentoring: Vec<i64>,
+}
+
+impl Default for UserSmallGroupAuthorization {
+    fn default() -> Self {
+        UserSmallGroupAuthorization {
+            username: None,
+            role: UserRole::External,
+            is_current_coordinator: false,
+            mentoring: Vec::new(),
+        }
+    }
+}
+
+impl UserSmallGroupAuthorization {
+    /// Can the user associated with this authorization create and manage small groups?
+    pub fn can_manage(&self) -> bool {
+        self.is_current_coordinator || self.role.is_admin()
+    }
+
+    /// Can the user associated with this authorization view a small group's dashboard?
+    /// This is the group's mentors and anyone who can manage small groups.
+    pub fn can_view_dashboard(&self, small_group_id: i64) -> bool {
+        self.can_manage() || self.mentoring.contains(&small_group_id)
+    }
+}
+
+impl SmallGroupAuthorizationFor {
+    /// Get the small group authorization rules for a given user.
+    pub async fn get(
+        username: Option<String>,
+    ) -> Result<UserSmallGroupAuthorization, TelescopeError> {
+        // Users that are not signed in have the lowest authorization.
+        if username.is_none() {
+            return Ok(UserSmallGroupAuthorization::default());
+        }
+
+        let username: String = username.unwrap();
+        let response = send_query::<Self>(small_group_authorization_for::Variables {
+            username: username.clone(),
+            now: Utc::today().naive_utc(),
+        })
+        .await?;
+
+        return Ok(UserSmallGroupAuthorization {
+            username: Some(username),
+            role: response
+                .users_by_pk
+                .map(|user| user.role)
+                .unwrap_or(UserRole::External),
+            is_current_coordinator: !response.current_coordinator.is_empty(),
+            mentoring: response
+                .small_group_mentors
+                .iter()
+                .map(|mentor| mentor.small_group_id)
+                .collect(),
+        });
+    }
+}
@@ -0,0 +1,29 @@
+//! GraphQL query for the mentor dashboard of a small group.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get a small group's member progress.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/dashboard.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SmallGroupDashboard;
+
+impl SmallGroupDashboard {
+    /// Get the dashboard data for a small group.
+    pub async fn get(
+        small_group_id: i64,
+    ) -> Result<Option<small_group_dashboard::SmallGroupDashboardSmallGroup>, TelescopeError> {
+        send_query::<Self>(small_group_dashboard::Variables {
+            small_group_id,
+            now: Utc::now(),
+        })
+        .await
+        .map(|response| response.small_group)
+    }
+}
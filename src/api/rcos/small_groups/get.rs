@@ -0,0 +1,21 @@
+//! GraphQL query to get a small group by ID.
+
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to get a small group.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/get.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SmallGroup;
+
+impl SmallGroup {
+    /// Get a small group, the list of all projects (for attaching more), and the users
+    /// eligible to mentor it.
+    pub async fn get(small_group_id: i64) -> Result<small_group::ResponseData, TelescopeError> {
+        send_query::<Self>(small_group::Variables { small_group_id }).await
+    }
+}
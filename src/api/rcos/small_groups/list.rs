@@ -0,0 +1,25 @@
+//! GraphQL query to list small groups by semester.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to list the small groups in a semester.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/list.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SmallGroups;
+
+impl SmallGroups {
+    /// Get the small groups in a semester.
+    pub async fn get(
+        semester_id: String,
+    ) -> Result<Vec<small_groups::SmallGroupsSmallGroups>, TelescopeError> {
+        send_query::<Self>(small_groups::Variables { semester_id })
+            .await
+            .map(|response| response.small_groups)
+    }
+}
@@ -0,0 +1,7 @@
+//! Queries and mutations on small groups.
+
+pub mod authorization_for;
+pub mod dashboard;
+pub mod get;
+pub mod list;
+pub mod mutations;
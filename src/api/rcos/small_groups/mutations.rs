@@ -0,0 +1,148 @@
+//! GraphQL mutations to create and manage small groups.
+
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL mutation to create a small group.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/mutations/create.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct CreateSmallGroup;
+
+/// Type representing GraphQL mutation to edit a small group.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/mutations/edit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct EditSmallGroup;
+
+/// Type representing GraphQL mutation to assign a mentor to a small group.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/mutations/mentors.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct AddSmallGroupMentor;
+
+/// Type representing GraphQL mutation to remove a mentor from a small group.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/mutations/mentors.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct RemoveSmallGroupMentor;
+
+/// Type representing GraphQL mutation to attach a project to a small group.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/mutations/projects.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct AddSmallGroupProject;
+
+/// Type representing GraphQL mutation to detach a project from a small group.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/small_groups/mutations/projects.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct RemoveSmallGroupProject;
+
+impl CreateSmallGroup {
+    /// Create a small group. Return the created small group's ID.
+    pub async fn execute(
+        semester_id: String,
+        title: String,
+        location: Option<String>,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(create_small_group::Variables {
+            semester_id,
+            title,
+            location,
+        })
+        .await
+        .map(|response| {
+            response
+                .insert_small_groups_one
+                .map(|obj| obj.small_group_id)
+        })
+    }
+}
+
+impl EditSmallGroup {
+    /// Edit a small group. Return the ID of the small group if it exists.
+    pub async fn execute(
+        small_group_id: i64,
+        title: String,
+        location: Option<String>,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(edit_small_group::Variables {
+            small_group_id,
+            title,
+            location,
+        })
+        .await
+        .map(|response| {
+            response
+                .update_small_groups_by_pk
+                .map(|obj| obj.small_group_id)
+        })
+    }
+}
+
+impl AddSmallGroupMentor {
+    /// Assign a mentor to a small group.
+    pub async fn execute(small_group_id: i64, username: String) -> Result<(), TelescopeError> {
+        send_query::<Self>(add_small_group_mentor::Variables {
+            small_group_id,
+            username,
+        })
+        .await
+        .map(|_| ())
+    }
+}
+
+impl RemoveSmallGroupMentor {
+    /// Remove a mentor from a small group.
+    pub async fn execute(small_group_id: i64, username: String) -> Result<(), TelescopeError> {
+        send_query::<Self>(remove_small_group_mentor::Variables {
+            small_group_id,
+            username,
+        })
+        .await
+        .map(|_| ())
+    }
+}
+
+impl AddSmallGroupProject {
+    /// Attach a project to a small group.
+    pub async fn execute(small_group_id: i64, project_id: i64) -> Result<(), TelescopeError> {
+        send_query::<Self>(add_small_group_project::Variables {
+            small_group_id,
+            project_id,
+        })
+        .await
+        .map(|_| ())
+    }
+}
+
+impl RemoveSmallGroupProject {
+    /// Detach a project from a small group.
+    pub async fn execute(small_group_id: i64, project_id: i64) -> Result<(), TelescopeError> {
+        send_query::<Self>(remove_small_group_project::Variables {
+            small_group_id,
+            project_id,
+        })
+        .await
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,44 @@
+//! GraphQL query to get a status update and a user's submission for it.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get a status update.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/status_updates/get.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct StatusUpdate;
+
+use status_update::StatusUpdateStatusUpdate;
+
+impl StatusUpdate {
+    /// Get a status update by ID, with the given user's submission.
+    pub async fn get(
+        status_update_id: i64,
+        username: String,
+    ) -> Result<Option<StatusUpdateStatusUpdate>, TelescopeError> {
+        send_query::<Self>(status_update::Variables {
+            status_update_id,
+            username,
+        })
+        .await
+        .map(|response| response.status_update)
+    }
+}
+
+impl StatusUpdateStatusUpdate {
+    /// Is this status update accepting submissions right now?
+    pub fn is_open(&self) -> bool {
+        let now = Utc::now();
+        self.open_date_time <= now
+            && self
+                .close_date_time
+                .map(|close| now <= close)
+                .unwrap_or(true)
+    }
+}
@@ -0,0 +1,102 @@
+//! GraphQL queries and mutations for grading status update submissions.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL query to get the grading context of a small group.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/status_updates/grading_context.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct GradingContext;
+
+/// Type representing GraphQL query to get the submissions of a small group's students.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/status_updates/grading_queue.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct GradingQueue;
+
+/// Type representing GraphQL mutation to grade a submission.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/status_updates/grade.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct GradeSubmission;
+
+impl GradingContext {
+    /// Get the grading context for a small group and viewer.
+    pub async fn get(
+        small_group_id: i64,
+        viewer: String,
+    ) -> Result<grading_context::ResponseData, TelescopeError> {
+        send_query::<Self>(grading_context::Variables {
+            small_group_id,
+            viewer,
+        })
+        .await
+    }
+}
+
+impl grading_context::ResponseData {
+    /// Can the viewer grade submissions for this small group? Mentors of the small group,
+    /// coordinators of its semester, and admins can.
+    pub fn viewer_can_grade(&self) -> bool {
+        let is_admin: bool = self
+            .viewer
+            .as_ref()
+            .map(|viewer| viewer.role.is_admin())
+            .unwrap_or(false);
+
+        let is_mentor_or_coordinator: bool = self
+            .small_group
+            .as_ref()
+            .map(|group| !group.mentors.is_empty() || !group.semester.coordinators.is_empty())
+            .unwrap_or(false);
+
+        is_admin || is_mentor_or_coordinator
+    }
+}
+
+impl GradingQueue {
+    /// Get the projects in a small group, with each enrolled student's submission.
+    pub async fn get(
+        small_group_id: i64,
+        status_update_id: i64,
+    ) -> Result<Vec<grading_queue::GradingQueueSmallGroupProjects>, TelescopeError> {
+        send_query::<Self>(grading_queue::Variables {
+            small_group_id,
+            status_update_id,
+        })
+        .await
+        .map(|response| response.small_group_projects)
+    }
+}
+
+impl GradeSubmission {
+    /// Grade a submission. Return `Ok(false)` if there is no submission to grade.
+    pub async fn execute(
+        status_update_id: i64,
+        username: String,
+        grade: f64,
+        grader_comments: Option<String>,
+        grader_username: String,
+    ) -> Result<bool, TelescopeError> {
+        send_query::<Self>(grade_submission::Variables {
+            status_update_id,
+            username,
+            grade,
+            grader_comments,
+            grader_username,
+        })
+        .await
+        .map(|response| response.update_status_update_submissions_by_pk.is_some())
+    }
+}
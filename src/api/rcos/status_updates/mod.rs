@@ -0,0 +1,6 @@
+//! Queries and mutations for weekly status updates and their submissions.
+
+pub mod get;
+pub mod grading;
+pub mod open;
+pub mod submit;
@@ -0,0 +1,29 @@
+//! GraphQL query to get the status updates open to a user.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get open status updates.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/status_updates/open.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct OpenStatusUpdates;
+
+impl OpenStatusUpdates {
+    /// Get the status updates currently open to a user.
+    pub async fn get(
+        username: String,
+    ) -> Result<Vec<open_status_updates::OpenStatusUpdatesStatusUpdates>, TelescopeError> {
+        send_query::<Self>(open_status_updates::Variables {
+            username,
+            now: Utc::now(),
+        })
+        .await
+        .map(|response| response.status_updates)
+    }
+}
@@ -0,0 +1,38 @@
+//! GraphQL mutation to submit a status update.
+
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL mutation to submit or edit a status update submission.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/status_updates/submit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SubmitStatusUpdate;
+
+impl SubmitStatusUpdate {
+    /// Submit a user's answers to a status update, replacing any previous answers.
+    pub async fn execute(
+        status_update_id: i64,
+        username: String,
+        this_week: String,
+        next_week: String,
+        blockers: String,
+    ) -> Result<(), TelescopeError> {
+        send_query::<Self>(submit_status_update::Variables {
+            status_update_id,
+            username,
+            this_week,
+            next_week,
+            blockers,
+        })
+        .await?
+        .insert_status_update_submissions_one
+        .ok_or(TelescopeError::ise(
+            "Status update submission did not return a record.",
+        ))
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,120 @@
+//! GraphQL queries and mutations used to manage users from the admin panel.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::search_strings::resolve_search_string;
+use crate::api::rcos::send_query;
+use crate::api::rcos::users::UserRole;
+use crate::error::TelescopeError;
+
+/// The most users returned by a search.
+pub const SEARCH_LIMIT: i64 = 50;
+
+/// Type representing GraphQL query to search users.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/admin/search.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct AdminUserSearch;
+
+/// Type representing GraphQL query to get a user's role and coordinator status.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/admin/details.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct AdminUserDetails;
+
+/// Type representing GraphQL mutation to set a user's role.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/admin/set_role.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SetUserRole;
+
+/// Type representing GraphQL mutation to mark a coordinator.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/admin/set_coordinator.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SetCoordinator;
+
+/// Type representing GraphQL mutation to unmark a coordinator.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/admin/unset_coordinator.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct UnsetCoordinator;
+
+impl AdminUserSearch {
+    /// Search for users whose username or name contains the search string.
+    pub async fn get(
+        search: Option<String>,
+    ) -> Result<Vec<admin_user_search::AdminUserSearchUsers>, TelescopeError> {
+        send_query::<Self>(admin_user_search::Variables {
+            search: resolve_search_string(search),
+            limit: SEARCH_LIMIT,
+        })
+        .await
+        .map(|response| response.users)
+    }
+}
+
+impl AdminUserDetails {
+    /// Get a user's details and the list of semesters.
+    pub async fn get(username: String) -> Result<admin_user_details::ResponseData, TelescopeError> {
+        send_query::<Self>(admin_user_details::Variables { username }).await
+    }
+}
+
+impl SetUserRole {
+    /// Set a user's role. Return the username if the user exists.
+    pub async fn execute(
+        username: String,
+        role: UserRole,
+    ) -> Result<Option<String>, TelescopeError> {
+        send_query::<Self>(set_user_role::Variables { username, role })
+            .await
+            .map(|response| response.update_users_by_pk.map(|obj| obj.username))
+    }
+}
+
+impl SetCoordinator {
+    /// Mark a user as a coordinator for a semester, enrolling them if necessary.
+    pub async fn execute(username: String, semester_id: String) -> Result<(), TelescopeError> {
+        send_query::<Self>(set_coordinator::Variables {
+            username,
+            semester_id,
+        })
+        .await?
+        .insert_enrollments_one
+        .ok_or(TelescopeError::ise(
+            "Coordinator update did not return an enrollment.",
+        ))
+        .map(|_| ())
+    }
+}
+
+impl UnsetCoordinator {
+    /// Unmark a user as a coordinator for a semester. Return the username if the user is
+    /// enrolled in the semester.
+    pub async fn execute(
+        username: String,
+        semester_id: String,
+    ) -> Result<Option<String>, TelescopeError> {
+        send_query::<Self>(unset_coordinator::Variables {
+            username,
+            semester_id,
+        })
+        .await
+        .map(|response| response.update_enrollments_by_pk.map(|obj| obj.username))
+    }
+}
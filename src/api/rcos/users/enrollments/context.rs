@@ -0,0 +1,26 @@
+//! GraphQL query to get the context for the enrollment form.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the enrollment form context.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/enrollments/context.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct EnrollmentContext;
+
+impl EnrollmentContext {
+    /// Get the enrollment form context for a user.
+    pub async fn get(username: String) -> Result<enrollment_context::ResponseData, TelescopeError> {
+        send_query::<Self>(enrollment_context::Variables {
+            username,
+            now: Utc::today().naive_utc(),
+        })
+        .await
+    }
+}
@@ -1 +1,14 @@
 //! GraphQL operations on user enrollments.
+
+use crate::env::global_config;
+use chrono::{Duration, NaiveDate};
+
+pub mod context;
+pub mod upsert;
+
+/// The last day users can change their enrollment for a semester starting on a given date.
+/// The RCOS database does not store an add/drop deadline, so the length of the add/drop
+/// period is configured.
+pub fn add_drop_deadline(semester_start: NaiveDate) -> NaiveDate {
+    semester_start + Duration::days(global_config().add_drop_period_days)
+}
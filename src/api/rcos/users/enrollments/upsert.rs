@@ -0,0 +1,38 @@
+//! GraphQL mutation to create or update a user's enrollment.
+
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL mutation to create or update an enrollment.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/enrollments/upsert.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct UpsertEnrollment;
+
+impl UpsertEnrollment {
+    /// Create or update a user's enrollment in a semester.
+    pub async fn execute(
+        username: String,
+        semester_id: String,
+        project_id: Option<i64>,
+        credits: i64,
+        is_for_pay: bool,
+    ) -> Result<(), TelescopeError> {
+        send_query::<Self>(upsert_enrollment::Variables {
+            username,
+            semester_id,
+            project_id,
+            credits,
+            is_for_pay,
+        })
+        .await?
+        .insert_enrollments_one
+        .ok_or(TelescopeError::ise(
+            "Enrollment upsert did not return a record.",
+        ))
+        .map(|_| ())
+    }
+}
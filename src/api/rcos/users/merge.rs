@@ -0,0 +1,266 @@
+//! GraphQL queries and mutations to find and merge duplicate users.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::api::rcos::users::UserAccountType;
+use crate::error::TelescopeError;
+use std::collections::HashSet;
+
+/// Type representing GraphQL query to list possible duplicate users.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/admin/duplicates.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct DuplicateUsers;
+
+/// Type representing GraphQL query to preview merging two users.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/admin/merge_preview.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct MergePreview;
+
+/// Type representing GraphQL mutation to merge two users.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/users/admin/merge.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct MergeUsers;
+
+impl DuplicateUsers {
+    /// Get the pairs of users that may be the same person.
+    pub async fn get() -> Result<Vec<duplicate_users::DuplicateUsersDuplicateUsers>, TelescopeError>
+    {
+        send_query::<Self>(duplicate_users::Variables {})
+            .await
+            .map(|response| response.duplicate_users)
+    }
+}
+
+/// What will happen when one user is merged into another.
+#[derive(Serialize, Clone, Debug)]
+pub struct MergePlan {
+    /// The user that stays.
+    pub keep: merge_preview::MergePreviewKeep,
+    /// The user that is merged into `keep` and then deleted.
+    pub remove: merge_preview::MergePreviewRemove,
+    /// Accounts moved to `keep`.
+    pub moved_accounts: Vec<merge_preview::MergePreviewRemoveUserAccounts>,
+    /// Accounts deleted because `keep` already has an account of the same type.
+    pub dropped_accounts: Vec<merge_preview::MergePreviewRemoveUserAccounts>,
+    /// Titles of the semesters whose enrollments move to `keep`.
+    pub moved_enrollments: Vec<String>,
+    /// Titles of the semesters both users are enrolled in. These block the merge.
+    pub conflicting_enrollments: Vec<String>,
+    /// The number of meeting attendances moved to `keep`.
+    pub moved_attendances: usize,
+    /// Meetings both users attended. `remove`'s attendance is deleted.
+    pub dropped_meeting_ids: Vec<i64>,
+    /// The number of small group mentor assignments moved to `keep`.
+    pub moved_small_groups: usize,
+    /// Small groups both users mentor. `remove`'s assignment is deleted.
+    pub dropped_small_group_ids: Vec<i64>,
+    /// Is `remove` a faculty advisor that will become `keep`?
+    pub moved_faculty_advisor: bool,
+    /// Are both users faculty advisors? `remove`'s faculty advisor record is deleted.
+    pub dropped_faculty_advisor: bool,
+    /// The number of status update submissions moved to `keep`.
+    pub moved_submissions: usize,
+    /// The number of status updates both users submitted. These block the merge.
+    pub conflicting_submissions: usize,
+    /// The number of meetings hosted by `remove`. These are hosted by `keep` after the merge.
+    pub hosted_meetings: i64,
+    /// The number of status update submissions graded by `remove`, which move to `keep`.
+    pub graded_submissions: i64,
+    /// The number of bonus attendances awarded to `remove`, which move to `keep`.
+    pub bonus_attendances: i64,
+    /// The number of final grade appeals made by `remove`, which move to `keep`.
+    pub grade_appeals: i64,
+    /// The number of project pitches submitted by `remove`, which move to `keep`.
+    pub project_pitches: i64,
+    /// The number of workshop proposals submitted by `remove`, which move to `keep`.
+    pub workshop_proposals: i64,
+    /// The number of project presentation grades given by `remove`, which move to `keep`.
+    pub presentation_grades: i64,
+}
+
+impl MergePlan {
+    /// Get the plan to merge the user `remove` into the user `keep`. Error if they are the
+    /// same user or either one does not exist.
+    pub async fn get(keep: String, remove: String) -> Result<Self, TelescopeError> {
+        if keep == remove {
+            return Err(TelescopeError::BadRequest {
+                header: "Same User".into(),
+                message: "A user cannot be merged into themselves.".into(),
+                show_status_code: false,
+            });
+        }
+
+        let preview = send_query::<MergePreview>(merge_preview::Variables { keep, remove }).await?;
+        let not_found = || {
+            TelescopeError::resource_not_found(
+                "User Not Found",
+                "Could not find both users to merge.",
+            )
+        };
+        let keep = preview.keep.ok_or_else(not_found)?;
+        let remove = preview.remove.ok_or_else(not_found)?;
+
+        let keep_account_types: HashSet<UserAccountType> =
+            keep.user_accounts.iter().map(|a| a.type_).collect();
+        let (dropped_accounts, moved_accounts): (Vec<_>, Vec<_>) = remove
+            .user_accounts
+            .iter()
+            .cloned()
+            .partition(|account| keep_account_types.contains(&account.type_));
+
+        let keep_semesters: HashSet<&str> = keep
+            .enrollments
+            .iter()
+            .map(|e| e.semester_id.as_str())
+            .collect();
+        let (conflicting_enrollments, moved_enrollments): (Vec<_>, Vec<_>) = remove
+            .enrollments
+            .iter()
+            .partition(|e| keep_semesters.contains(e.semester_id.as_str()));
+
+        let keep_meetings: HashSet<i64> = keep
+            .meeting_attendances
+            .iter()
+            .map(|a| a.meeting_id)
+            .collect();
+        let dropped_meeting_ids: Vec<i64> = remove
+            .meeting_attendances
+            .iter()
+            .map(|a| a.meeting_id)
+            .filter(|id| keep_meetings.contains(id))
+            .collect();
+
+        let keep_small_groups: HashSet<i64> = keep
+            .small_group_mentors
+            .iter()
+            .map(|m| m.small_group_id)
+            .collect();
+        let dropped_small_group_ids: Vec<i64> = remove
+            .small_group_mentors
+            .iter()
+            .map(|m| m.small_group_id)
+            .filter(|id| keep_small_groups.contains(id))
+            .collect();
+
+        let remove_is_advisor: bool = !preview.remove_faculty_advisors.is_empty();
+        let keep_is_advisor: bool = !preview.keep_faculty_advisors.is_empty();
+
+        let keep_status_updates: HashSet<i64> = keep
+            .status_update_submissions
+            .iter()
+            .map(|s| s.status_update_id)
+            .collect();
+        let conflicting_submissions: usize = remove
+            .status_update_submissions
+            .iter()
+            .filter(|s| keep_status_updates.contains(&s.status_update_id))
+            .count();
+
+        return Ok(MergePlan {
+            moved_accounts,
+            dropped_accounts,
+            moved_enrollments: moved_enrollments
+                .into_iter()
+                .map(|e| e.semester.title.clone())
+                .collect(),
+            conflicting_enrollments: conflicting_enrollments
+                .into_iter()
+                .map(|e| e.semester.title.clone())
+                .collect(),
+            moved_attendances: remove.meeting_attendances.len() - dropped_meeting_ids.len(),
+            dropped_meeting_ids,
+            moved_small_groups: remove.small_group_mentors.len() - dropped_small_group_ids.len(),
+            dropped_small_group_ids,
+            moved_faculty_advisor: remove_is_advisor && !keep_is_advisor,
+            dropped_faculty_advisor: remove_is_advisor && keep_is_advisor,
+            moved_submissions: remove.status_update_submissions.len() - conflicting_submissions,
+            conflicting_submissions,
+            hosted_meetings: remove
+                .hosted_meetings
+                .aggregate
+                .as_ref()
+                .and_then(|a| a.count)
+                .unwrap_or(0),
+            graded_submissions: remove
+                .graded_submissions
+                .aggregate
+                .as_ref()
+                .and_then(|a| a.count)
+                .unwrap_or(0),
+            bonus_attendances: remove
+                .bonus_attendances_aggregate
+                .aggregate
+                .as_ref()
+                .and_then(|a| a.count)
+                .unwrap_or(0),
+            grade_appeals: remove
+                .final_grade_appeals_aggregate
+                .aggregate
+                .as_ref()
+                .and_then(|a| a.count)
+                .unwrap_or(0),
+            project_pitches: remove
+                .project_pitches_aggregate
+                .aggregate
+                .as_ref()
+                .and_then(|a| a.count)
+                .unwrap_or(0),
+            workshop_proposals: remove
+                .workshop_proposals_aggregate
+                .aggregate
+                .as_ref()
+                .and_then(|a| a.count)
+                .unwrap_or(0),
+            presentation_grades: remove
+                .project_presentation_grades_aggregate
+                .aggregate
+                .as_ref()
+                .and_then(|a| a.count)
+                .unwrap_or(0),
+            keep,
+            remove,
+        });
+    }
+
+    /// Can this merge go ahead? Both users enrolled in the same semester or submitting the
+    /// same status update have to be resolved by hand first.
+    pub fn can_merge(&self) -> bool {
+        self.conflicting_enrollments.is_empty() && self.conflicting_submissions == 0
+    }
+
+    /// Merge the users. Return the username of the deleted user if it was deleted.
+    pub async fn execute(&self) -> Result<Option<String>, TelescopeError> {
+        let dropped_account_types: Vec<UserAccountType> = self
+            .dropped_accounts
+            .iter()
+            .map(|account| account.type_)
+            .collect();
+
+        send_query::<MergeUsers>(merge_users::Variables {
+            keep: self.keep.username.clone(),
+            remove: self.remove.username.clone(),
+            dropped_account_types,
+            dropped_meeting_ids: self.dropped_meeting_ids.clone(),
+            dropped_small_group_ids: self.dropped_small_group_ids.clone(),
+            dropped_faculty_advisors: if self.dropped_faculty_advisor {
+                vec![self.remove.username.clone()]
+            } else {
+                vec![]
+            },
+        })
+        .await
+        .map(|response| response.delete_users_by_pk.map(|user| user.username))
+    }
+}
@@ -1,11 +1,13 @@
 //! API interactions for RCOS users from the central RCOS API.
 
 pub mod accounts;
+pub mod admin;
 pub mod create;
 pub mod developers_page;
 pub mod discord_whois;
 pub mod edit_profile;
 pub mod enrollments;
+pub mod merge;
 pub mod navbar_auth;
 pub mod profile;
 pub mod role_lookup;
@@ -0,0 +1,28 @@
+//! GraphQL query to get the context for the workshop proposal form.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the workshop proposal form context.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/workshop_proposals/context.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct WorkshopProposalContext;
+
+impl WorkshopProposalContext {
+    /// Get the workshop proposal form context for a user.
+    pub async fn get(
+        username: String,
+    ) -> Result<workshop_proposal_context::ResponseData, TelescopeError> {
+        send_query::<Self>(workshop_proposal_context::Variables {
+            username,
+            now: Utc::today().naive_utc(),
+        })
+        .await
+    }
+}
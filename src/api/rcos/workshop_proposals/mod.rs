@@ -0,0 +1,8 @@
+//! GraphQL queries and mutations on workshop proposals.
+//!
+//! Users propose bonus workshops with three ranked time slots. Approving a proposal
+//! schedules a bonus session at one of those slots, hosted by the proposer.
+
+pub mod context;
+pub mod review;
+pub mod submit;
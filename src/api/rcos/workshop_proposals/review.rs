@@ -0,0 +1,106 @@
+//! GraphQL queries and mutations to review workshop proposals.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+use chrono::Utc;
+
+/// Type representing GraphQL query to get the workshop proposals in ongoing semesters.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/workshop_proposals/queue.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct WorkshopProposalQueue;
+
+/// Type representing GraphQL query to get a workshop proposal for review.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/workshop_proposals/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct WorkshopProposal;
+
+/// Type representing GraphQL mutation to approve or reject a workshop proposal.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/workshop_proposals/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ReviewWorkshopProposal;
+
+/// Type representing GraphQL mutation to approve a workshop proposal and schedule the workshop.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/workshop_proposals/review.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct ApproveWorkshopProposal;
+
+impl WorkshopProposalQueue {
+    /// Get the workshop proposals in ongoing semesters, oldest first.
+    pub async fn get(
+    ) -> Result<Vec<workshop_proposal_queue::WorkshopProposalQueueWorkshopProposals>, TelescopeError>
+    {
+        send_query::<Self>(workshop_proposal_queue::Variables {
+            now: Utc::today().naive_utc(),
+        })
+        .await
+        .map(|response| response.workshop_proposals)
+    }
+}
+
+impl WorkshopProposal {
+    /// Get a workshop proposal by ID. The reviewer's enrollment in the proposal's semester
+    /// is included.
+    pub async fn get(
+        workshop_proposal_id: i64,
+        reviewer: String,
+    ) -> Result<Option<workshop_proposal::WorkshopProposalProposal>, TelescopeError> {
+        send_query::<Self>(workshop_proposal::Variables {
+            workshop_proposal_id,
+            reviewer,
+        })
+        .await
+        .map(|response| response.proposal)
+    }
+}
+
+impl ReviewWorkshopProposal {
+    /// Approve or reject a workshop proposal. Return `Ok(false)` if the proposal does not
+    /// exist.
+    pub async fn execute(
+        workshop_proposal_id: i64,
+        is_approved: bool,
+        reviewer_username: String,
+        reviewer_comments: Option<String>,
+    ) -> Result<bool, TelescopeError> {
+        send_query::<Self>(review_workshop_proposal::Variables {
+            workshop_proposal_id,
+            is_approved,
+            reviewer_username,
+            reviewer_comments,
+        })
+        .await
+        .map(|response| response.update_workshop_proposals_by_pk.is_some())
+    }
+}
+
+impl ApproveWorkshopProposal {
+    /// Approve a workshop proposal and create the meeting for the workshop. Return the ID of the
+    /// created meeting, or `None` if the proposal does not exist.
+    pub async fn execute(
+        vars: approve_workshop_proposal::Variables,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(vars).await.map(|response| {
+            response
+                .update_workshop_proposals_by_pk
+                .and(response.insert_meetings_one)
+                .map(|meeting| meeting.meeting_id)
+        })
+    }
+}
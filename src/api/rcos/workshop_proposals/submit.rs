@@ -0,0 +1,27 @@
+//! GraphQL mutation to submit a workshop proposal.
+
+use crate::api::rcos::prelude::*;
+use crate::api::rcos::send_query;
+use crate::error::TelescopeError;
+
+/// Type representing GraphQL mutation to submit a workshop proposal.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/rcos/schema.json",
+    query_path = "graphql/rcos/workshop_proposals/submit.graphql",
+    response_derives = "Debug,Clone,Serialize"
+)]
+pub struct SubmitWorkshopProposal;
+
+impl SubmitWorkshopProposal {
+    /// Submit a workshop proposal. Return the created proposal's ID.
+    pub async fn execute(
+        vars: submit_workshop_proposal::Variables,
+    ) -> Result<Option<i64>, TelescopeError> {
+        send_query::<Self>(vars).await.map(|response| {
+            response
+                .insert_workshop_proposals_one
+                .map(|obj| obj.workshop_proposal_id)
+        })
+    }
+}
@@ -0,0 +1,126 @@
+//! Audit trail of changes made through the admin panel.
+//!
+//! The RCOS database does not have a table for this, so entries are appended to a JSON lines
+//! file (one JSON object per line) at the path given by the `audit_log_path` config option.
+//! Entries are never modified or removed by Telescope.
+
+use crate::env::global_config;
+use crate::error::TelescopeError;
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::sync::Mutex;
+
+lazy_static! {
+    /// Lock held while writing to the audit log, so that concurrent entries do not interleave.
+    static ref AUDIT_LOG_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// A single change recorded in the audit trail.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the change was made.
+    pub timestamp: DateTime<Utc>,
+    /// The username of the user who made the change.
+    pub actor: String,
+    /// A short machine readable name for the kind of change (e.g. `role_change`).
+    pub action: String,
+    /// The username (or other identifier) of the record that was changed.
+    pub target: String,
+    /// A human readable description of the change.
+    pub description: String,
+}
+
+impl AuditEntry {
+    /// Create an audit entry timestamped now.
+    pub fn new(
+        actor: impl Into<String>,
+        action: impl Into<String>,
+        target: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        AuditEntry {
+            timestamp: Utc::now(),
+            actor: actor.into(),
+            action: action.into(),
+            target: target.into(),
+            description: description.into(),
+        }
+    }
+
+    /// Append this entry to the audit log. The file is written on the blocking thread pool.
+    pub async fn write(self) -> Result<(), TelescopeError> {
+        let line: String = serde_json::to_string(&self)
+            .map_err(|e| TelescopeError::ise(format!("Could not serialize audit entry: {}", e)))?;
+
+        web::block(move || {
+            // Hold the lock while writing. A poisoned lock only means another write panicked.
+            let _guard = AUDIT_LOG_LOCK
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&global_config().audit_log_path)
+                .and_then(|mut file| writeln!(file, "{}", line))
+        })
+        .await
+        .map_err(|e| TelescopeError::ise(format!("Could not write to audit log: {}", e)))?;
+
+        info!(
+            "Audit: {} {} {}: {}",
+            self.actor, self.action, self.target, self.description
+        );
+        return Ok(());
+    }
+
+    /// Record a change that has already been made. Since the change cannot be undone at this
+    /// point, a failure to write the audit log is logged instead of failing the request.
+    pub async fn record(self) {
+        let summary: String = format!(
+            "{} {} {}: {}",
+            self.actor, self.action, self.target, self.description
+        );
+
+        if let Err(e) = self.write().await {
+            error!("Could not record audit entry ({}): {}", summary, e);
+        }
+    }
+}
+
+/// Read the most recent audit entries, newest first. If `target` is given, only entries about
+/// that target are returned. The file is read on the blocking thread pool.
+pub async fn recent_entries(
+    limit: usize,
+    target: Option<&str>,
+) -> Result<Vec<AuditEntry>, TelescopeError> {
+    let target: Option<String> = target.map(str::to_string);
+
+    web::block(move || read_entries(limit, target.as_deref()))
+        .await
+        .map_err(|e| TelescopeError::ise(format!("Could not read audit log: {}", e)))
+}
+
+/// Read the most recent audit entries from the audit log file. This blocks.
+fn read_entries(limit: usize, target: Option<&str>) -> Result<Vec<AuditEntry>, io::Error> {
+    let file: File = match File::open(&global_config().audit_log_path) {
+        Ok(file) => file,
+        // Nothing has been recorded yet.
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut entries: Vec<AuditEntry> = BufReader::new(file)
+        .lines()
+        .filter_map(|line| line.ok())
+        // Skip lines that do not parse rather than failing the whole page.
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(line.as_str()).ok())
+        .filter(|entry| target.map(|t| entry.target == t).unwrap_or(true))
+        .collect();
+
+    entries.reverse();
+    entries.truncate(limit);
+    return Ok(entries);
+}
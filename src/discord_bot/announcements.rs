@@ -0,0 +1,69 @@
+//! Posting announcements to the RCOS Discord.
+//!
+//! This does not go through the listening bot client -- it sends a single message using the
+//! bot token over Discord's HTTP API.
+
+use crate::discord_bot::embed_common;
+use crate::env::{global_config, DiscordConfig};
+use crate::error::TelescopeError;
+use serenity::http::Http;
+use serenity::model::id::ChannelId;
+use serenity::utils::Color;
+
+/// The embed color of announcements.
+const ANNOUNCEMENT_COLOR: Color = Color::new(0xE2393B); // RCOS red
+
+/// Discord limits embed descriptions to 4096 characters.
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// Post an announcement to the configured announcements channel.
+pub async fn post_announcement(
+    announcement_id: i64,
+    title: &str,
+    body_markdown: &str,
+) -> Result<(), TelescopeError> {
+    let discord_conf: &DiscordConfig = &global_config().discord_config;
+
+    // Announcements can only be posted if there is a channel to post them in.
+    let channel_id: u64 =
+        discord_conf
+            .announcements_channel_id
+            .ok_or(TelescopeError::BadRequest {
+                header: "No Announcements Channel".into(),
+                message: "Telescope is not configured with a Discord channel to post \
+            announcements to."
+                    .into(),
+                show_status_code: false,
+            })?;
+
+    // Discord renders most markdown, so the body is sent as is (truncated if necessary).
+    let mut description: String = body_markdown.chars().take(MAX_DESCRIPTION_LENGTH).collect();
+    if description.len() < body_markdown.len() {
+        description.pop();
+        description.push('…');
+    }
+
+    let url: String = format!(
+        "{}/#announcement-{}",
+        discord_conf.telescope_url.trim_end_matches('/'),
+        announcement_id
+    );
+
+    let http = Http::new_with_token(discord_conf.bot_token.as_str());
+    ChannelId(channel_id)
+        .send_message(&http, |create_message| {
+            create_message.embed(|create_embed| {
+                embed_common(create_embed)
+                    .color(ANNOUNCEMENT_COLOR)
+                    .title(title)
+                    .url(url)
+                    .description(description)
+            })
+        })
+        .await
+        .map_err(|e| {
+            TelescopeError::ise(format!("Could not post announcement to Discord: {}", e))
+        })?;
+
+    return Ok(());
+}
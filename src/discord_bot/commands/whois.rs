@@ -2,9 +2,10 @@
 
 use crate::api::rcos::users::discord_whois::DiscordWhoIs;
 use crate::discord_bot::commands::InteractionResult;
+use crate::discord_bot::embed_common;
 use crate::env::global_config;
 use crate::web::profile_for;
-use serenity::builder::{CreateApplicationCommand, CreateApplicationCommandOption, CreateEmbed};
+use serenity::builder::{CreateApplicationCommand, CreateApplicationCommandOption};
 use serenity::client::Context;
 use serenity::model::interactions::application_command::ApplicationCommandInteraction;
 use serenity::model::interactions::{
@@ -163,21 +164,3 @@ async fn handle(ctx: &Context, interaction: &ApplicationCommandInteraction) -> S
         })
         .await;
 }
-
-/// Add common data to a Discord embed. This includes the author, footer, and timestamp.
-fn embed_common(create_embed: &mut CreateEmbed) -> &mut CreateEmbed {
-    create_embed
-        // Timestamp is always now
-        .timestamp(&chrono::Utc::now())
-        // Footer is telescope version
-        .footer(|create_footer| {
-            create_footer.text(format!("Telescope {}", env!("CARGO_PKG_VERSION")))
-        })
-        // Author links to telescope's github.
-        .author(|create_author| {
-            create_author
-                // Don't include the telescope icon - we only link to the github
-                .name("Telescope")
-                .url("https://github.com/rcos/Telescope")
-        })
-}
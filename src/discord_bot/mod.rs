@@ -1,5 +1,6 @@
 //! Discord functionality built on top of serenity.
 
+pub mod announcements;
 mod commands;
 mod event_handler;
 
@@ -8,6 +9,7 @@ use crate::env::{global_config, DiscordConfig};
 use actix::{Actor, ActorContext, ActorFuture, AsyncContext, Context, Supervised};
 use futures::future::LocalBoxFuture;
 use futures::Future;
+use serenity::builder::CreateEmbed;
 use serenity::{Client, Result as SerenityResult};
 use std::pin::Pin;
 use std::task::Context as StdContext;
@@ -49,6 +51,24 @@ impl DiscordBot {
     }
 }
 
+/// Add common data to a Discord embed. This includes the author, footer, and timestamp.
+pub fn embed_common(create_embed: &mut CreateEmbed) -> &mut CreateEmbed {
+    create_embed
+        // Timestamp is always now
+        .timestamp(&chrono::Utc::now())
+        // Footer is telescope version
+        .footer(|create_footer| {
+            create_footer.text(format!("Telescope {}", env!("CARGO_PKG_VERSION")))
+        })
+        // Author links to telescope's github.
+        .author(|create_author| {
+            create_author
+                // Don't include the telescope icon - we only link to the github
+                .name("Telescope")
+                .url("https://github.com/rcos/Telescope")
+        })
+}
+
 /// Future representing the indefinite async computation of the Discord bot
 /// listening for events.
 struct ListeningFuture {
@@ -97,7 +117,7 @@ impl Actor for DiscordBot {
 
         // Log a link to invite the bot to a server.
         info!("Invite bot using \
-        https://discord.com/api/oauth2/authorize?client_id={}&permissions=2147549184&response_type=code&scope=bot%20applications.commands",
+        https://discord.com/api/oauth2/authorize?client_id={}&permissions=2147567616&response_type=code&scope=bot%20applications.commands",
               discord_conf.client_id.as_str());
 
         // Listen for incoming Discord events on this actor's context.
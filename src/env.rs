@@ -15,6 +15,134 @@ pub struct GithubOauthConfig {
     pub client_secret: ClientSecret,
 }
 
+/// The GitLab instance used when none is configured.
+const DEFAULT_GITLAB_INSTANCE: &'static str = "https://gitlab.com";
+
+/// Credentials for a GitLab OAuth application. Create one under "Applications" in the user
+/// or group settings of the GitLab instance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GitLabOauthConfig {
+    /// The GitLab OAuth application ID.
+    pub client_id: ClientId,
+    /// The GitLab OAuth application secret.
+    pub client_secret: ClientSecret,
+    /// The URL of a self-hosted GitLab instance. Defaults to <https://gitlab.com>.
+    #[serde(default)]
+    instance_url: Option<String>,
+}
+
+impl GitLabOauthConfig {
+    /// Get the base URL of the GitLab instance, without a trailing slash.
+    pub fn instance_url(&self) -> &str {
+        self.instance_url
+            .as_deref()
+            .unwrap_or(DEFAULT_GITLAB_INSTANCE)
+            .trim_end_matches('/')
+    }
+}
+
+/// Credentials for a BitBucket OAuth consumer. Create one under "OAuth consumers" in the
+/// BitBucket workspace settings.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BitBucketOauthConfig {
+    /// The BitBucket OAuth consumer key.
+    pub client_id: ClientId,
+    /// The BitBucket OAuth consumer secret.
+    pub client_secret: ClientSecret,
+}
+
+/// The CAS server used when none is configured.
+const DEFAULT_CAS_URL: &'static str = "https://cas.auth.rpi.edu/cas";
+
+/// The ID token claim identifying the user when none is configured.
+const DEFAULT_OIDC_CLAIM: &'static str = "sub";
+
+/// Config for signing in with an OpenID Connect identity provider. The provider's endpoints
+/// and signing keys are discovered from the issuer URL. Users are identified by a configured
+/// claim of the ID token, which has to be their RCS ID since the RCOS database has no account
+/// type for OpenID Connect.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OidcConfig {
+    /// The issuer URL. The discovery document is expected at
+    /// `{issuer_url}/.well-known/openid-configuration`.
+    pub issuer_url: String,
+    /// The OAuth2 client ID registered with the provider.
+    pub client_id: ClientId,
+    /// The OAuth2 client secret. Public clients rely on PKCE alone and do not have one.
+    #[serde(default)]
+    pub client_secret: Option<ClientSecret>,
+    /// The ID token claim identifying the user. Defaults to `sub`.
+    #[serde(default)]
+    claim: Option<String>,
+    /// Confirm that the claim is the user's RCS ID, so that users signed in this way are the
+    /// same as users signed in through RPI CAS. This is required, and should only be set if the
+    /// provider is RPI's, since anyone who controls the claim can sign in as any RCS ID.
+    #[serde(default)]
+    pub claim_is_rcs_id: bool,
+    /// The name shown on the sign in button. Defaults to "Single Sign-On".
+    #[serde(default)]
+    display_name: Option<String>,
+    /// Scopes to request in addition to `openid`. Defaults to `profile` and `email`.
+    #[serde(default)]
+    scopes: Option<Vec<String>>,
+}
+
+impl OidcConfig {
+    /// Get the issuer URL without a trailing slash.
+    pub fn issuer_url(&self) -> &str {
+        self.issuer_url.trim_end_matches('/')
+    }
+
+    /// Get the ID token claim identifying the user.
+    pub fn claim(&self) -> &str {
+        self.claim.as_deref().unwrap_or(DEFAULT_OIDC_CLAIM)
+    }
+
+    /// Get the name of the identity provider to show users.
+    pub fn display_name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or("Single Sign-On")
+    }
+
+    /// Get the scopes to request, always including `openid`.
+    pub fn scopes(&self) -> Vec<String> {
+        let configured: Vec<String> = self
+            .scopes
+            .clone()
+            .unwrap_or_else(|| vec!["profile".into(), "email".into()]);
+
+        let mut scopes: Vec<String> = vec!["openid".into()];
+        scopes.extend(configured.into_iter().filter(|scope| scope != "openid"));
+        return scopes;
+    }
+}
+
+/// Keys used to encrypt identity cookies. Keys are 32 bytes, written as 64 hexadecimal
+/// characters. Generate them with `telescope generate-cookie-key`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CookieKeysConfig {
+    /// The key new cookies are encrypted with.
+    #[serde(default, skip_serializing)]
+    pub primary: Option<String>,
+    /// Keys that cookies are still accepted from while keys are being rotated.
+    #[serde(default, skip_serializing)]
+    pub previous: Vec<String>,
+    /// A file of keys, one per line with the primary key first, used instead of the keys
+    /// above. Empty lines and lines starting with '#' are ignored.
+    #[serde(default)]
+    pub key_file: Option<PathBuf>,
+}
+
+/// Where the identities of authenticated users are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionStoreKind {
+    /// In the encrypted identity cookie. Sessions cannot be listed or revoked.
+    Cookie,
+    /// In memory on the server. The cookie only holds a session ID. Everyone is signed out
+    /// when Telescope restarts.
+    Memory,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DiscordConfig {
     /// The Discord application client id.
@@ -34,6 +162,11 @@ pub struct DiscordConfig {
     /// This bot only adds commands to guilds to avoid being used outside of RCOS
     /// approved servers.
     pub guild_ids: Vec<u64>,
+
+    /// The ID of the discord channel that announcements are posted to. If this is not set,
+    /// announcements cannot be posted to Discord.
+    #[serde(default)]
+    pub announcements_channel_id: Option<u64>,
 }
 
 /// The config of the server instance.
@@ -49,12 +182,45 @@ struct TelescopeConfig {
     /// Discord application config and credentials.
     discord_config: Option<DiscordConfig>,
 
+    /// GitLab OAuth application credentials. GitLab sign in is disabled without these.
+    gitlab_credentials: Option<GitLabOauthConfig>,
+
+    /// BitBucket OAuth consumer credentials. BitBucket sign in is disabled without these.
+    bitbucket_credentials: Option<BitBucketOauthConfig>,
+
+    /// OpenID Connect identity provider config. OpenID Connect sign in is disabled without it.
+    oidc: Option<OidcConfig>,
+
+    /// The base URL of the CAS server used for RPI CAS sign in.
+    cas_url: Option<String>,
+
+    /// The keys used to encrypt identity cookies.
+    cookie_keys: Option<CookieKeysConfig>,
+
+    /// Where the identities of authenticated users are stored.
+    session_store: Option<SessionStoreKind>,
+
     /// The URL of the RCOS central API (in the OpenAPI Spec via RCOS-data).
     api_url: Option<String>,
 
     /// The JWT secret used to authenticate with the central API.
     jwt_secret: Option<String>,
 
+    /// The secret used to sign calendar subscription links.
+    calendar_secret: Option<String>,
+
+    /// The file that admin actions are recorded to.
+    audit_log_path: Option<PathBuf>,
+
+    /// The file that calendar subscription link resets are recorded to.
+    calendar_resets_path: Option<PathBuf>,
+
+    /// The fraction of required meetings students have to attend.
+    attendance_threshold: Option<f64>,
+
+    /// The number of days after the start of a semester that users can change their enrollment.
+    add_drop_period_days: Option<i64>,
+
     /// Profiles. These can be used and specified at runtime to override values
     /// defined globally. Profiles are scoped and can have sub profiles.
     profile: Option<HashMap<String, TelescopeConfig>>,
@@ -73,10 +239,38 @@ pub struct ConcreteConfig {
     pub github_credentials: GithubOauthConfig,
     /// The Discord Config and Credentials.
     pub discord_config: DiscordConfig,
+    /// The GitLab OAuth Application Credentials, if GitLab sign in is enabled.
+    pub gitlab_credentials: Option<GitLabOauthConfig>,
+    /// The BitBucket OAuth Consumer Credentials, if BitBucket sign in is enabled.
+    pub bitbucket_credentials: Option<BitBucketOauthConfig>,
+    /// The OpenID Connect identity provider config, if OpenID Connect sign in is enabled.
+    pub oidc: Option<OidcConfig>,
+    /// The base URL of the CAS server, without a trailing slash. Defaults to RPI's CAS server.
+    pub cas_url: String,
+    /// The keys used to encrypt identity cookies. If these are not set, a random key is
+    /// generated at startup.
+    pub cookie_keys: Option<CookieKeysConfig>,
+    /// Where the identities of authenticated users are stored. Defaults to the identity cookie.
+    pub session_store: SessionStoreKind,
     /// The url of the RCOS API that telescope will read and write to.
     pub api_url: String,
     /// The JWT secret used to authenticate with the central API.
     pub jwt_secret: String,
+    /// The secret used to sign calendar subscription links. If this is not set, a random
+    /// secret is generated at startup.
+    #[serde(skip_serializing)]
+    pub calendar_secret: Option<String>,
+    /// The file that admin actions are recorded to. Defaults to `audit.log`.
+    pub audit_log_path: PathBuf,
+    /// The file that calendar subscription link resets are recorded to. Defaults to
+    /// `calendar_resets.log`.
+    pub calendar_resets_path: PathBuf,
+    /// The fraction of required meetings students have to attend. Students below this are
+    /// highlighted on the attendance report. Defaults to 0.6.
+    pub attendance_threshold: f64,
+    /// The number of days after the start of a semester that users can still add or change
+    /// their enrollment. Defaults to 14, following RPI's add/drop period.
+    pub add_drop_period_days: i64,
 }
 
 impl TelescopeConfig {
@@ -112,12 +306,51 @@ impl TelescopeConfig {
             discord_config: self
                 .reverse_lookup(profile_slice, |c| c.discord_config.clone())
                 .expect("Could not resolve Discord credentials"),
+            gitlab_credentials: self
+                .reverse_lookup(profile_slice, |c| c.gitlab_credentials.clone()),
+            bitbucket_credentials: self
+                .reverse_lookup(profile_slice, |c| c.bitbucket_credentials.clone()),
+            oidc: self
+                .reverse_lookup(profile_slice, |c| c.oidc.clone())
+                .map(|oidc| {
+                    // OpenID Connect accounts are stored as RCS IDs, so the provider has to
+                    // be trusted to release them.
+                    if !oidc.claim_is_rcs_id {
+                        eprintln!(
+                            "OpenID Connect sign in requires `claim_is_rcs_id = true`. The \
+                            RCOS database has no account type for other identifiers."
+                        );
+                        exit(1)
+                    }
+                    oidc
+                }),
+            cas_url: self
+                .reverse_lookup(profile_slice, |c| c.cas_url.clone())
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| DEFAULT_CAS_URL.to_string()),
+            cookie_keys: self.reverse_lookup(profile_slice, |c| c.cookie_keys.clone()),
+            session_store: self
+                .reverse_lookup(profile_slice, |c| c.session_store)
+                .unwrap_or(SessionStoreKind::Cookie),
             api_url: self
                 .reverse_lookup(profile_slice, |c| c.api_url.clone())
                 .expect("Could not resolve RCOS central API URL."),
             jwt_secret: self
                 .reverse_lookup(profile_slice, |c| c.jwt_secret.clone())
                 .expect("Could not resolve JWT secret."),
+            calendar_secret: self.reverse_lookup(profile_slice, |c| c.calendar_secret.clone()),
+            audit_log_path: self
+                .reverse_lookup(profile_slice, |c| c.audit_log_path.clone())
+                .unwrap_or_else(|| PathBuf::from("audit.log")),
+            calendar_resets_path: self
+                .reverse_lookup(profile_slice, |c| c.calendar_resets_path.clone())
+                .unwrap_or_else(|| PathBuf::from("calendar_resets.log")),
+            attendance_threshold: self
+                .reverse_lookup(profile_slice, |c| c.attendance_threshold)
+                .unwrap_or(0.6),
+            add_drop_period_days: self
+                .reverse_lookup(profile_slice, |c| c.add_drop_period_days)
+                .unwrap_or(14),
         }
     }
 
@@ -174,6 +407,20 @@ struct CommandLine {
     /// 'dev.local'
     #[structopt(short = "p", long = "profile", env)]
     profile: Option<String>,
+    /// A command to run instead of starting the server.
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+/// Commands that can be run instead of starting the server.
+#[derive(Debug, Serialize, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+enum Command {
+    /// Generate a new key to encrypt identity cookies with and print it.
+    ///
+    /// To rotate keys, make the new key the primary key and move the old primary key to the
+    /// previous keys until the cookies encrypted with it have expired.
+    GenerateCookieKey,
 }
 
 lazy_static! {
@@ -208,6 +455,12 @@ fn cli() -> ConcreteConfig {
     // Get the command line args.
     let commandline: CommandLine = CommandLine::from_args();
 
+    // Run any command instead of reading the config.
+    if let Some(Command::GenerateCookieKey) = commandline.command {
+        println!("{}", crate::web::cookie_keys::generate_key());
+        exit(0);
+    }
+
     // Read the config file into a string.
     let mut confing_file_string = String::new();
     File::open(&commandline.config_file)
@@ -127,6 +127,28 @@ pub enum TelescopeError {
     /// Error sending to or receiving from the RPI CAS system.
     /// This should report as a Gateway error.
     RpiCasError(String),
+
+    #[error(ignore)]
+    #[display(fmt = "CAS ticket rejected ({}): {}", code, description)]
+    /// The CAS server rejected the service ticket, usually because it expired or was already
+    /// used. The user should sign in again. This should report as a bad request.
+    CasTicketRejected {
+        /// The CAS failure code.
+        code: String,
+        /// The description sent by the CAS server.
+        description: String,
+    },
+
+    #[error(ignore)]
+    #[display(fmt = "CAS request rejected ({}): {}", code, description)]
+    /// The CAS server rejected Telescope's validation request. This means Telescope or the
+    /// CAS server is misconfigured and should report as an internal server error.
+    CasRequestRejected {
+        /// The CAS failure code.
+        code: String,
+        /// The description sent by the CAS server.
+        description: String,
+    },
 }
 
 impl TelescopeError {
@@ -267,6 +289,20 @@ impl TelescopeError {
                 Telescope GitHub. Internal Error: {}", err)
             ),
 
+            TelescopeError::CasTicketRejected { code, description } => jumbotron::new(
+                format!("{} - Sign In Expired", status_code),
+                format!("The RPI CAS service did not accept this sign in. This usually means \
+                it took too long or the page was refreshed. Please sign in again. CAS error \
+                {}: {}", code, description)
+            ),
+
+            TelescopeError::CasRequestRejected { code, description } => jumbotron::new(
+                format!("{} - RPI CAS Configuration Error", status_code),
+                format!("The RPI CAS service rejected Telescope's request. Please contact a \
+                coordinator and create an issue on the Telescope GitHub. CAS error {}: {}",
+                code, description)
+            ),
+
             TelescopeError::GraphQLError { platform, errors } => {
                 // Map all errors to their `Display` formatting.
                 let errs: Vec<String> = errors.iter().map(|e| format!("{}", e)).collect();
@@ -360,6 +396,7 @@ impl ResponseError for TelescopeError {
             TelescopeError::NotAuthenticated => StatusCode::UNAUTHORIZED,
             TelescopeError::Forbidden => StatusCode::FORBIDDEN,
             TelescopeError::RpiCasError(_) => StatusCode::BAD_GATEWAY,
+            TelescopeError::CasTicketRejected { .. } => StatusCode::BAD_REQUEST,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -21,23 +21,21 @@ extern crate graphql_client;
 
 use actix::prelude::*;
 use actix_files as afs;
-use actix_identity::{CookieIdentityPolicy, IdentityService};
+use actix_identity::IdentityService;
 use actix_web::cookie::SameSite;
-use actix_web::{middleware, web as aweb, web::get, App, HttpServer};
+use actix_web::{middleware, web as aweb, App, HttpServer};
 use chrono::Offset;
-use rand::rngs::OsRng;
-use rand::Rng;
 
 use web::middlewares;
 
 use crate::discord_bot::DiscordBot;
-use crate::{
-    templates::static_pages::{sponsors::SponsorsPage, StaticPage},
-    web::csrf::CsrfJanitor,
-};
+use crate::web::cookie_keys::CookieKeys;
+use crate::web::csrf::CsrfJanitor;
+use crate::web::sessions::{session_store, SessionJanitor, SESSION_LIFETIME_DAYS};
 
 pub mod api;
 mod app_data;
+mod audit;
 mod discord_bot;
 mod env;
 mod error;
@@ -54,25 +52,32 @@ async fn main() -> std::io::Result<()> {
     // Start global CSRF token janitor.
     CsrfJanitor.start();
 
+    // Start the janitor for expired sessions if they are stored on the server.
+    if session_store().is_some() {
+        SessionJanitor.start();
+    }
+
     // Create and start the discord bot under a Supervisor that will
     // restart it if it crashes.
     Supervisor::start(|_| DiscordBot);
 
     // Setup identity middleware.
-    // Create secure random sequence to encrypt cookie identities.
-    let cookie_key: [u8; 32] = OsRng::default().gen::<[u8; 32]>();
+    // Load the keys to encrypt cookie identities with.
+    let cookie_keys: CookieKeys = CookieKeys::load();
 
     // Construct and start main server instance.
     let web_server = HttpServer::new(move || {
         // Create cookie policy.
-        let cookie_policy = CookieIdentityPolicy::new(&cookie_key)
-            // Transmit cookies over HTTPS only.
-            .secure(true)
-            .name("telescope_auth")
-            // Same-Site needs to be Lax because of the caddy proxy it seems?
-            .same_site(SameSite::Lax)
-            // Cookies expire after a day.
-            .max_age_time(time::Duration::days(1));
+        let cookie_policy = cookie_keys.policy(|policy| {
+            policy
+                // Transmit cookies over HTTPS only.
+                .secure(true)
+                .name("telescope_auth")
+                // Same-Site needs to be Lax because of the caddy proxy it seems?
+                .same_site(SameSite::Lax)
+                // Cookies expire when the session would.
+                .max_age_time(time::Duration::days(SESSION_LIFETIME_DAYS))
+        });
 
         App::new()
             // Middleware to render telescope errors into pages
@@ -91,7 +96,6 @@ async fn main() -> std::io::Result<()> {
                     // Show listings of directories
                     .show_files_listing(),
             )
-            .route("/sponsors", get().to(SponsorsPage::page))
             .default_service(aweb::to(web::services::not_found::not_found))
     })
     // Bind to 80 (this gets reversed proxied by Caddy later)
@@ -1,7 +1,11 @@
 //! Templates for users to login and register with.
 
+use crate::env::global_config;
 use crate::templates::Template;
-use crate::web::services::auth::oauth2_providers::{discord::DiscordOAuth, github::GitHubOauth};
+use crate::web::services::auth::oauth2_providers::{
+    bitbucket::BitBucketOauth, discord::DiscordOAuth, github::GitHubOauth, gitlab::GitLabOauth,
+};
+use crate::web::services::auth::oidc::Oidc;
 use crate::web::services::auth::rpi_cas::RpiCas;
 use crate::web::services::auth::IdentityProvider;
 use serde_json::{Map, Value};
@@ -59,7 +63,7 @@ fn item(
 /// Create a template to offer the user options to login.
 pub fn login() -> Template {
     // Make list of identity providers in login configuration.
-    let items: Vec<Map<String, Value>> = vec![
+    let mut items: Vec<Map<String, Value>> = vec![
         item(
             GitHubOauth::login_path(),
             "btn-github mb-2",
@@ -74,9 +78,43 @@ pub fn login() -> Template {
             // a Feather icon. Do not use it in other places, as it won't work.
             Some("discord"),
         ),
-        item(RpiCas::login_path(), "btn-rpi", "Login using RPI CAS", None),
     ];
 
+    // GitLab and BitBucket are only offered if they are configured.
+    if global_config().gitlab_credentials.is_some() {
+        items.push(item(
+            GitLabOauth::login_path(),
+            "btn-gitlab mb-2",
+            "Login using GitLab",
+            Some("gitlab"),
+        ));
+    }
+
+    if global_config().bitbucket_credentials.is_some() {
+        items.push(item(
+            BitBucketOauth::login_path(),
+            "btn-bitbucket mb-2",
+            "Login using BitBucket",
+            None,
+        ));
+    }
+
+    if let Some(oidc) = global_config().oidc.as_ref() {
+        items.push(item(
+            Oidc::login_path(),
+            "btn-dark mb-2",
+            format!("Login using {}", oidc.display_name()),
+            None,
+        ));
+    }
+
+    items.push(item(
+        RpiCas::login_path(),
+        "btn-rpi",
+        "Login using RPI CAS",
+        None,
+    ));
+
     // Create and return template.
     return empty().field(HEADER, "Sign In").field(ITEMS, items);
 }
@@ -84,7 +122,7 @@ pub fn login() -> Template {
 /// Create a template to offer the users options to register a new account.
 pub fn register() -> Template {
     // Make list of identity providers in account creation configuration.
-    let items: Vec<Map<String, Value>> = vec![
+    let mut items: Vec<Map<String, Value>> = vec![
         item(
             GitHubOauth::register_path(),
             "btn-github mb-2",
@@ -99,14 +137,42 @@ pub fn register() -> Template {
             // a Feather icon. Do not use it in other places, as it won't work.
             Some("discord"),
         ),
-        item(
-            RpiCas::register_path(),
-            "btn-rpi",
-            "Register using RPI CAS",
-            None,
-        ),
     ];
 
+    if global_config().gitlab_credentials.is_some() {
+        items.push(item(
+            GitLabOauth::register_path(),
+            "btn-gitlab mb-2",
+            "Register using GitLab",
+            Some("gitlab"),
+        ));
+    }
+
+    if global_config().bitbucket_credentials.is_some() {
+        items.push(item(
+            BitBucketOauth::register_path(),
+            "btn-bitbucket mb-2",
+            "Register using BitBucket",
+            None,
+        ));
+    }
+
+    if let Some(oidc) = global_config().oidc.as_ref() {
+        items.push(item(
+            Oidc::register_path(),
+            "btn-dark mb-2",
+            format!("Register using {}", oidc.display_name()),
+            None,
+        ));
+    }
+
+    items.push(item(
+        RpiCas::register_path(),
+        "btn-rpi",
+        "Register using RPI CAS",
+        None,
+    ));
+
     // Create and return template.
     return empty().field(HEADER, "Create account").field(ITEMS, items);
 }
@@ -23,6 +23,7 @@ pub fn register_helpers(registry: &mut Handlebars) {
     );
     registry.register_helper("format_user_role", wrap_helper(format_user_role));
     registry.register_helper("domain_of", wrap_helper(domain_of_helper));
+    registry.register_helper("format_grade", wrap_helper(format_grade_helper));
     registry.register_helper("url_encode", wrap_helper(url_encode_helper));
     registry.register_helper("render_markdown", wrap_helper(markdown_renderer_helper));
 }
@@ -210,6 +211,23 @@ fn format_user_role(h: &Helper<'_, '_>, out: &mut dyn Output) -> HelperResult {
     Ok(())
 }
 
+/// Helper to format a grade (usually an average) to at most one decimal place.
+fn format_grade_helper(h: &Helper<'_, '_>, out: &mut dyn Output) -> HelperResult {
+    let grade: f64 = h
+        .param(0)
+        .and_then(|param| param.value().as_f64())
+        .ok_or(RenderError::new("format_grade helper expects a number"))?;
+
+    // Whole numbers are displayed without a decimal.
+    out.write(round_grade(grade).to_string().as_str())?;
+    Ok(())
+}
+
+/// Round a grade (usually an average) to one decimal place for display.
+pub fn round_grade(grade: f64) -> f64 {
+    (grade * 10.0).round() / 10.0
+}
+
 /// Helper to urlencode a query string.
 /// This accepts a series of hash arguments and encodes all of them.
 fn url_encode_helper(h: &Helper<'_, '_>, out: &mut dyn Output) -> HelperResult {
@@ -241,13 +259,18 @@ fn markdown_renderer_helper(h: &Helper<'_, '_>, out: &mut dyn Output) -> HelperR
             .ok_or(RenderError::new(
                 "render_markdown expects a markdown string parameter.",
             ))?;
+    // Write the rendered HTML to the handlebars output.
+    out.write(render_markdown(markdown_source).as_str())?;
+    return Ok(());
+}
+
+/// Render a markdown string to HTML. This is what the `render_markdown` helper writes.
+pub fn render_markdown(markdown_source: &str) -> String {
     // Make a new parser with all options enabled.
     let parser = MarkdownParser::new_ext(markdown_source, MarkdownOptions::all());
-    // Make and write an html buffer with the rendered markdown.
+    // Make an html buffer with the rendered markdown.
     // Set the initial capacity at at least the length of the source markdown.
     let mut buffer = String::with_capacity(markdown_source.len());
     pulldown_cmark::html::push_html(&mut buffer, parser);
-    // Write the rendered HTML to the handlebars output.
-    out.write(buffer.as_str())?;
-    return Ok(());
+    return buffer;
 }
@@ -0,0 +1,163 @@
+//! Keys for encrypting identity cookies.
+//!
+//! Keys are loaded from the config or a key file so that users stay signed in when Telescope
+//! restarts. To rotate keys, the new key becomes the primary key, which all cookies are
+//! encrypted with, and the old key is kept as a previous key. Cookies encrypted with a previous
+//! key are still accepted, and are encrypted with the primary key again when they are saved.
+
+use crate::env::{global_config, CookieKeysConfig};
+use actix_identity::{CookieIdentityPolicy, IdentityPolicy};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::Error;
+use futures::future::{ready, Ready};
+use rand::rngs::OsRng;
+use rand::Rng;
+use std::fs;
+use std::process::exit;
+
+/// The number of bytes in a cookie key.
+const KEY_LENGTH: usize = 32;
+
+/// A key to encrypt identity cookies with.
+type CookieKey = [u8; KEY_LENGTH];
+
+/// Generate a new random cookie key, encoded as hexadecimal.
+pub fn generate_key() -> String {
+    let key: CookieKey = OsRng::default().gen::<CookieKey>();
+    key.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decode a hexadecimal cookie key.
+fn decode_key(hex: &str) -> Result<CookieKey, String> {
+    let hex: &str = hex.trim();
+    if hex.len() != KEY_LENGTH * 2 || !hex.is_ascii() {
+        return Err(format!(
+            "Cookie keys must be {} hexadecimal characters.",
+            KEY_LENGTH * 2
+        ));
+    }
+
+    let mut key: CookieKey = [0; KEY_LENGTH];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .map_err(|_| "Cookie keys must be hexadecimal.".to_string())?;
+    }
+    return Ok(key);
+}
+
+/// The keys used to encrypt and decrypt identity cookies.
+#[derive(Clone)]
+pub struct CookieKeys {
+    /// The key that cookies are encrypted with.
+    primary: CookieKey,
+    /// Keys that cookies are still decrypted with, newest first.
+    previous: Vec<CookieKey>,
+}
+
+impl CookieKeys {
+    /// Load the cookie keys from the global config. Exit if they are invalid. If there are no
+    /// keys configured, generate a random key, which signs everyone out on restart.
+    pub fn load() -> Self {
+        match global_config().cookie_keys.as_ref() {
+            Some(config) => Self::from_config(config).unwrap_or_else(|e| {
+                error!("Could not load cookie keys: {}", e);
+                exit(1)
+            }),
+
+            None => {
+                warn!("No cookie keys configured. Using a random key. Users will be signed out when Telescope restarts.");
+                CookieKeys {
+                    primary: OsRng::default().gen::<CookieKey>(),
+                    previous: Vec::new(),
+                }
+            }
+        }
+    }
+
+    /// Decode the keys in the config, or in the key file if there is one.
+    fn from_config(config: &CookieKeysConfig) -> Result<Self, String> {
+        let encoded: Vec<String> = match config.key_file.as_ref() {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|e| format!("Could not read key file at {}: {}", path.display(), e))?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect(),
+
+            // Don't silently promote a previous key to the primary key.
+            None if config.primary.is_none() && !config.previous.is_empty() => {
+                return Err("Previous cookie keys are set without a primary cookie key.".into())
+            }
+
+            None => config
+                .primary
+                .iter()
+                .chain(config.previous.iter())
+                .cloned()
+                .collect(),
+        };
+
+        let mut keys = encoded
+            .iter()
+            .map(|key| decode_key(key))
+            .collect::<Result<Vec<CookieKey>, String>>()?
+            .into_iter();
+
+        let primary: CookieKey = keys.next().ok_or("There is no primary cookie key.")?;
+        return Ok(CookieKeys {
+            primary,
+            previous: keys.collect(),
+        });
+    }
+
+    /// Make an identity policy using these keys. The builder is applied to the cookie policy
+    /// of every key, so that all of them read and write the same cookie.
+    pub fn policy(
+        &self,
+        builder: impl Fn(CookieIdentityPolicy) -> CookieIdentityPolicy,
+    ) -> RotatingCookiePolicy {
+        RotatingCookiePolicy {
+            primary: builder(CookieIdentityPolicy::new(&self.primary)),
+            previous: self
+                .previous
+                .iter()
+                .map(|key| builder(CookieIdentityPolicy::new(key)))
+                .collect(),
+        }
+    }
+}
+
+/// A cookie identity policy that accepts cookies encrypted with any of the cookie keys and
+/// encrypts cookies with the primary key.
+pub struct RotatingCookiePolicy {
+    /// The policy using the primary key.
+    primary: CookieIdentityPolicy,
+    /// The policies using previous keys.
+    previous: Vec<CookieIdentityPolicy>,
+}
+
+impl IdentityPolicy for RotatingCookiePolicy {
+    type Future = Ready<Result<Option<String>, Error>>;
+    type ResponseFuture = <CookieIdentityPolicy as IdentityPolicy>::ResponseFuture;
+
+    fn from_request(&self, req: &mut ServiceRequest) -> Self::Future {
+        // Cookies that cannot be decrypted with a key have no identity, so try the next key.
+        for policy in std::iter::once(&self.primary).chain(self.previous.iter()) {
+            match policy.from_request(req).into_inner() {
+                Ok(None) => continue,
+                result => return ready(result),
+            }
+        }
+        return ready(Ok(None));
+    }
+
+    fn to_response<B>(
+        &self,
+        identity: Option<String>,
+        changed: bool,
+        response: &mut ServiceResponse<B>,
+    ) -> Self::ResponseFuture {
+        self.primary.to_response(identity, changed, response)
+    }
+}
@@ -9,29 +9,25 @@ use oauth2::CsrfToken;
 use std::sync::Arc;
 use std::time::Duration as StdDuration;
 
-/// Extract the remote IP address string from an HTTP request's headers.
-fn extract_ip_addr(req: &HttpRequest) -> Result<String, TelescopeError> {
-    // Get the request headers.
-    req.headers()
-        // We use the X-Forwarded-For header that Caddy sets.
+/// Extract the remote IP address string from an HTTP request.
+pub fn extract_ip_addr(req: &HttpRequest) -> Result<String, TelescopeError> {
+    // We use the X-Forwarded-For header that Caddy sets. Clients can send their own header,
+    // which Caddy appends the address it sees to, so only the last address can be trusted.
+    // See https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/X-Forwarded-For.
+    // The header is split as bytes, so that non-ASCII addresses sent by the client do not
+    // stop the last one from being read.
+    let forwarded: Option<String> = req
+        .headers()
         .get("X-Forwarded-For")
-        // Get the remote IP from the header.
-        .map(|header_value| {
-            // Convert the header value to a string.
-            header_value
-                .to_str()
-                // This should be ASCII, and should not error.
-                .expect("XFF header should be ASCII")
-                // Split on commas, as defined at
-                // https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/X-Forwarded-For
-                .split(",")
-                // Get the first one -- this should be the remote client ID.
-                .next()
-                // There should always be at least one.
-                .expect("No first address in XFF header")
-                // Convert it to a string.
-                .to_string()
-        })
+        .and_then(|header_value| header_value.as_bytes().rsplit(|b| *b == b',').next())
+        .and_then(|last| std::str::from_utf8(last).ok())
+        .map(str::trim)
+        .filter(|addr| !addr.is_empty())
+        .map(str::to_string);
+
+    // Fall back to the address of the peer when not behind Caddy.
+    forwarded
+        .or_else(|| req.peer_addr().map(|addr| addr.ip().to_string()))
         .ok_or(TelescopeError::IpExtractionError)
 }
 
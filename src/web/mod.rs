@@ -1,12 +1,17 @@
 //! Web services and utilities.
 
+use actix_web::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use actix_web::HttpResponse;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
 use reqwest::header::HeaderValue;
 
 use crate::web::services::user::profile::ProfileQuery;
 
+pub mod cookie_keys;
 pub mod csrf;
 pub mod middlewares;
 pub mod services;
+pub mod sessions;
 
 lazy_static! {
     static ref TELESCOPE_USER_AGENT: String =
@@ -30,3 +35,64 @@ pub fn profile_for(username: &str) -> String {
     // Put it in the correct part of the query for now.
     return format!("/user?{}", encoded);
 }
+
+/// The format of values from `datetime-local` form inputs.
+const DATETIME_LOCAL_FORMAT: &'static str = "%Y-%m-%dT%H:%M";
+
+/// Parse the value of a `datetime-local` form input, which is in the server's local time.
+pub fn parse_datetime_local(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value.trim(), DATETIME_LOCAL_FORMAT)
+        .ok()
+        // Expect that there is only one valid local time for this.
+        .and_then(|naive| Local.from_local_datetime(&naive).single())
+        .map(|local| local.with_timezone(&Utc))
+}
+
+/// Format a timestamp as the value of a `datetime-local` form input.
+pub fn format_datetime_local(timestamp: &DateTime<Utc>) -> String {
+    timestamp
+        .with_timezone(&Local)
+        .format(DATETIME_LOCAL_FORMAT)
+        .to_string()
+}
+
+/// Characters that make spreadsheet programs treat a cell as a formula.
+const CSV_FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Format a record as a line of a CSV file (RFC 4180). Fields containing commas, quotes, or
+/// line breaks are quoted. Fields that would be read as formulas by spreadsheet programs are
+/// prefixed with a single quote, since exports contain user supplied text.
+pub fn csv_line<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line: String = fields
+        .iter()
+        .map(|field| {
+            let field: &str = field.as_ref();
+            let field: String = if field.starts_with(&CSV_FORMULA_PREFIXES[..]) {
+                format!("'{}", field)
+            } else {
+                field.to_string()
+            };
+
+            if field.contains(|c: char| c == ',' || c == '"' || c == '\r' || c == '\n') {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+
+    line.push_str("\r\n");
+    return line;
+}
+
+/// Make a response that downloads a CSV file with the given name.
+pub fn csv_download(filename: &str, body: String) -> HttpResponse {
+    HttpResponse::Ok()
+        .header(CONTENT_TYPE, "text/csv;charset=UTF-8")
+        .header(
+            CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(body)
+}
@@ -0,0 +1,325 @@
+//! Announcement management services.
+//!
+//! Unlike the rest of the admin panel, these are open to current coordinators as well as
+//! admins.
+
+use crate::api::rcos::announcements::{
+    announcement::AnnouncementAnnouncement, edit_announcement, Announcement, Announcements,
+    CreateAnnouncement, EditAnnouncement, ExpireAnnouncement,
+};
+use crate::api::rcos::semesters::list::SemesterList;
+use crate::discord_bot::announcements::post_announcement;
+use crate::env::global_config;
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::templates::Template;
+use crate::web::{format_datetime_local, parse_datetime_local};
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+/// The handlebars template for the announcement list.
+const INDEX_TEMPLATE: &'static str = "admin/announcements/index";
+
+/// The handlebars template for the announcement creation and edit form.
+const FORM_TEMPLATE: &'static str = "admin/announcements/form";
+
+/// The number of announcements listed on the management page.
+const LIST_LENGTH: i64 = 100;
+
+/// Register announcement management pages under `/admin/announcements`.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(index)
+        .service(creation_page)
+        .service(submit_creation)
+        .service(edit_page)
+        .service(submit_edit)
+        .service(expire)
+        .service(post_to_discord);
+}
+
+/// Is Telescope configured to post announcements to Discord?
+fn discord_enabled() -> bool {
+    global_config()
+        .discord_config
+        .announcements_channel_id
+        .is_some()
+}
+
+/// Page listing recent announcements.
+#[get("")]
+async fn index(req: HttpRequest) -> Result<Template, TelescopeError> {
+    // Mark which announcements are still showing on the homepage.
+    let now: DateTime<Utc> = Utc::now();
+    let announcements: Vec<Value> = Announcements::get(LIST_LENGTH)
+        .await?
+        .into_iter()
+        .map(|announcement| {
+            let is_active: bool = announcement
+                .close_date_time
+                .map(|close| close > now)
+                .unwrap_or(true);
+            json!({ "announcement": announcement, "is_active": is_active })
+        })
+        .collect();
+
+    return Template::new(INDEX_TEMPLATE)
+        .field("announcements", announcements)
+        .field("discord_enabled", discord_enabled())
+        .render_into_page(&req, "Announcements")
+        .await;
+}
+
+/// Make the announcement form with the list of semesters.
+async fn make_form(page_title: &str) -> Result<FormTemplate, TelescopeError> {
+    let mut form = FormTemplate::new(FORM_TEMPLATE, page_title);
+    form.template = json!({
+        "semesters": SemesterList::get().await?,
+        "discord_enabled": discord_enabled(),
+    });
+    return Ok(form);
+}
+
+/// Form submitted to create or edit an announcement.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct AnnouncementForm {
+    /// The semester this announcement is for.
+    semester_id: String,
+    /// The announcement title.
+    title: String,
+    /// The body of the announcement in markdown.
+    body_markdown: String,
+    /// When the announcement stops showing on the homepage (from a `datetime-local` input).
+    /// Empty if the announcement does not expire.
+    #[serde(default)]
+    close_date_time: String,
+    /// Present if the announcement should be posted to Discord once it is saved.
+    post_to_discord: Option<String>,
+}
+
+/// The fields of an announcement form after validation.
+struct ValidatedAnnouncement {
+    semester_id: String,
+    title: String,
+    body_markdown: String,
+    close_date_time: Option<DateTime<Utc>>,
+    post_to_discord: bool,
+}
+
+impl AnnouncementForm {
+    /// Validate this form, saving the submitted values and any issues to the form template.
+    fn validate(self, form: &mut FormTemplate) -> Option<ValidatedAnnouncement> {
+        form.template["data"] = json!({
+            "semester_id": &self.semester_id,
+            "title": &self.title,
+            "body_markdown": &self.body_markdown,
+            "close_date_time": &self.close_date_time,
+        });
+        form.template["post_to_discord"] = json!(self.post_to_discord.is_some());
+
+        let mut valid: bool = true;
+
+        if self.title.trim().is_empty() {
+            form.template["issues"]["title"] = json!("Announcements must have a title.");
+            valid = false;
+        }
+
+        if self.body_markdown.trim().is_empty() {
+            form.template["issues"]["body_markdown"] = json!("Announcements must have a body.");
+            valid = false;
+        }
+
+        // Parse the close time (entered in local time) if there is one.
+        let close_date_time: Option<DateTime<Utc>> = if self.close_date_time.trim().is_empty() {
+            None
+        } else {
+            let parsed: Option<DateTime<Utc>> = parse_datetime_local(self.close_date_time.as_str());
+
+            if parsed.is_none() {
+                form.template["issues"]["close_date_time"] = json!("Invalid date and time.");
+                valid = false;
+            }
+            parsed
+        };
+
+        if self.post_to_discord.is_some() && !discord_enabled() {
+            form.template["issues"]["post_to_discord"] =
+                json!("There is no Discord channel configured for announcements.");
+            valid = false;
+        }
+
+        if !valid {
+            return None;
+        }
+
+        return Some(ValidatedAnnouncement {
+            semester_id: self.semester_id,
+            title: self.title.trim().to_string(),
+            body_markdown: self.body_markdown,
+            close_date_time,
+            post_to_discord: self.post_to_discord.is_some(),
+        });
+    }
+}
+
+/// Page to create an announcement.
+#[get("/create")]
+async fn creation_page() -> Result<FormTemplate, TelescopeError> {
+    make_form("Create Announcement").await
+}
+
+/// Endpoint to submit announcement creation.
+#[post("/create")]
+async fn submit_creation(
+    Form(input): Form<AnnouncementForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let mut form: FormTemplate = make_form("Create Announcement").await?;
+    let validated: ValidatedAnnouncement = input
+        .validate(&mut form)
+        .ok_or(TelescopeError::invalid_form(&form))?;
+
+    let announcement_id: i64 = CreateAnnouncement::execute(
+        validated.title.clone(),
+        validated.body_markdown.clone(),
+        validated.semester_id,
+        validated.close_date_time,
+    )
+    .await?
+    .ok_or(TelescopeError::ise(
+        "Announcement creation did not return ID.",
+    ))?;
+
+    // The announcement is already saved, so a failure to post it is logged rather than shown.
+    if validated.post_to_discord {
+        if let Err(err) = post_announcement(
+            announcement_id,
+            validated.title.as_str(),
+            validated.body_markdown.as_str(),
+        )
+        .await
+        {
+            error!(
+                "Could not post announcement {} to Discord: {}",
+                announcement_id, err
+            );
+        }
+    }
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/admin/announcements")
+        .finish())
+}
+
+/// Get an announcement or return a resource not found error.
+async fn get_announcement(
+    announcement_id: i64,
+) -> Result<AnnouncementAnnouncement, TelescopeError> {
+    Announcement::get(announcement_id)
+        .await?
+        .ok_or(TelescopeError::resource_not_found(
+            "Announcement Not Found",
+            "Could not find an announcement for this ID.",
+        ))
+}
+
+/// Make the edit form for an existing announcement.
+async fn make_edit_form(
+    announcement: &AnnouncementAnnouncement,
+) -> Result<FormTemplate, TelescopeError> {
+    let mut form: FormTemplate = make_form("Edit Announcement").await?;
+    form.template["announcement_id"] = json!(announcement.announcement_id);
+    form.template["data"] = json!({
+        "semester_id": &announcement.semester_id,
+        "title": &announcement.title,
+        "body_markdown": &announcement.body_markdown,
+        "close_date_time": announcement.close_date_time.as_ref().map(format_datetime_local),
+    });
+    return Ok(form);
+}
+
+/// Page to edit an announcement.
+#[get("/{announcement_id}/edit")]
+async fn edit_page(Path(announcement_id): Path<i64>) -> Result<FormTemplate, TelescopeError> {
+    let announcement = get_announcement(announcement_id).await?;
+    return make_edit_form(&announcement).await;
+}
+
+/// Endpoint to submit announcement edits.
+#[post("/{announcement_id}/edit")]
+async fn submit_edit(
+    Path(announcement_id): Path<i64>,
+    Form(input): Form<AnnouncementForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let announcement = get_announcement(announcement_id).await?;
+    let mut form: FormTemplate = make_edit_form(&announcement).await?;
+    let validated: ValidatedAnnouncement = input
+        .validate(&mut form)
+        .ok_or(TelescopeError::invalid_form(&form))?;
+
+    EditAnnouncement::execute(edit_announcement::Variables {
+        announcement_id,
+        title: validated.title.clone(),
+        body_markdown: validated.body_markdown.clone(),
+        semester_id: validated.semester_id,
+        close_date_time: validated.close_date_time,
+    })
+    .await?
+    .ok_or(TelescopeError::resource_not_found(
+        "Announcement Not Found",
+        "Could not find an announcement for this ID.",
+    ))?;
+
+    // The announcement is already saved, so a failure to post it is logged rather than shown.
+    if validated.post_to_discord {
+        if let Err(err) = post_announcement(
+            announcement_id,
+            validated.title.as_str(),
+            validated.body_markdown.as_str(),
+        )
+        .await
+        {
+            error!(
+                "Could not post announcement {} to Discord: {}",
+                announcement_id, err
+            );
+        }
+    }
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/admin/announcements")
+        .finish())
+}
+
+/// Expire an announcement immediately, removing it from the homepage.
+#[post("/{announcement_id}/expire")]
+async fn expire(Path(announcement_id): Path<i64>) -> Result<HttpResponse, TelescopeError> {
+    ExpireAnnouncement::execute(announcement_id).await?.ok_or(
+        TelescopeError::resource_not_found(
+            "Announcement Not Found",
+            "Could not find an announcement for this ID.",
+        ),
+    )?;
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/admin/announcements")
+        .finish())
+}
+
+/// Post an existing announcement to Discord.
+#[post("/{announcement_id}/discord")]
+async fn post_to_discord(Path(announcement_id): Path<i64>) -> Result<HttpResponse, TelescopeError> {
+    let announcement = get_announcement(announcement_id).await?;
+    post_announcement(
+        announcement_id,
+        announcement.title.as_str(),
+        announcement.body_markdown.as_str(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/admin/announcements")
+        .finish())
+}
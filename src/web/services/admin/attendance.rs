@@ -0,0 +1,316 @@
+//! Per-semester attendance report for faculty advisors.
+//!
+//! The report counts large group meetings and the student's own small group meetings that have
+//! started so far, plus bonus attendance, for every student enrolled in a semester. Students
+//! are grouped by the small group their project is in, and students below the configured
+//! attendance threshold are highlighted. The same data can be downloaded as a CSV.
+
+use crate::api::rcos::meetings::attendance_report::{
+    attendance_report::AttendanceReportSemester, AttendanceReport,
+};
+use crate::api::rcos::semesters::list::SemesterList;
+use crate::env::global_config;
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::admin::small_group_meetings::SmallGroupMeetings;
+use crate::web::services::admin::SemesterQuery;
+use crate::web::{csv_download, csv_line};
+use actix_web::web::{Query, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+use std::collections::HashMap;
+
+/// Register attendance report services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(report).service(export);
+}
+
+/// A student's attendance in a semester.
+#[derive(Serialize, Clone, Debug)]
+struct StudentAttendance {
+    username: String,
+    first_name: String,
+    last_name: String,
+    rcs_id: Option<String>,
+    /// The ID of the small group the student's project is in.
+    small_group_id: Option<i64>,
+    /// The title of the small group the student's project is in.
+    small_group: Option<String>,
+    large_group_attended: i64,
+    /// The number of meetings of the student's own small group they attended.
+    small_group_attended: i64,
+    /// The number of meetings of the student's own small group so far.
+    small_group_required: i64,
+    bonus_attended: i64,
+    /// The fraction of required meetings attended, counting bonus attendance. This is at most
+    /// 1, and 1 if there were no required meetings.
+    rate: f64,
+    /// The rate formatted as a percentage.
+    percent: String,
+    /// Is the rate below the configured threshold?
+    below_threshold: bool,
+}
+
+/// A summary of the attendance of the students in a small group.
+#[derive(Serialize, Clone, Debug)]
+struct SmallGroupSummary {
+    /// The small group title. `None` for students who are not in a small group.
+    title: Option<String>,
+    /// The number of meetings of this small group so far.
+    required: i64,
+    students: usize,
+    below_threshold: usize,
+    /// The average attendance rate of the students in the group.
+    average_rate: f64,
+    /// The average rate formatted as a percentage.
+    percent: String,
+}
+
+/// The computed attendance report for a semester.
+#[derive(Serialize, Clone, Debug)]
+struct Report {
+    semester_id: String,
+    title: String,
+    large_group_required: i64,
+    threshold: f64,
+    /// The threshold formatted as a percentage.
+    threshold_percent: String,
+    students: Vec<StudentAttendance>,
+    small_groups: Vec<SmallGroupSummary>,
+}
+
+impl Report {
+    /// Compute the report from the API data.
+    fn new(data: AttendanceReportSemester) -> Self {
+        let threshold: f64 = global_config().attendance_threshold;
+
+        let large_group_required: i64 = data
+            .large_group_meetings
+            .aggregate
+            .and_then(|a| a.count)
+            .unwrap_or(0);
+
+        // Students only need to attend their own small group's meetings.
+        let small_group_meetings: SmallGroupMeetings = SmallGroupMeetings::new(
+            data.small_groups.iter().map(|group| {
+                (
+                    group.small_group_id,
+                    group
+                        .small_group_projects
+                        .iter()
+                        .map(|project| project.project_id)
+                        .collect(),
+                    group
+                        .small_group_mentors
+                        .iter()
+                        .map(|mentor| mentor.username.clone())
+                        .collect(),
+                )
+            }),
+            data.small_group_meetings
+                .iter()
+                .map(|meeting| meeting.host_username.as_deref()),
+        );
+
+        // Map each small group to its title.
+        let group_titles: HashMap<i64, String> = data
+            .small_groups
+            .iter()
+            .map(|group| (group.small_group_id, group.title.clone()))
+            .collect();
+
+        let students: Vec<StudentAttendance> = data
+            .enrollments
+            .into_iter()
+            .map(|enrollment| {
+                let user = enrollment.user;
+                let small_group_id: Option<i64> =
+                    small_group_meetings.group_of_project(enrollment.project_id);
+                let small_group_required: i64 = small_group_meetings.required(small_group_id);
+
+                let large_group_attended: i64 = user
+                    .large_group_attended
+                    .aggregate
+                    .and_then(|a| a.count)
+                    .unwrap_or(0);
+                let small_group_attended: i64 = small_group_meetings.count_attended(
+                    small_group_id,
+                    user.small_group_attended
+                        .iter()
+                        .map(|attendance| attendance.meeting.host_username.as_deref()),
+                );
+                let bonus_attended: i64 = enrollment
+                    .bonus_attendances_aggregate
+                    .aggregate
+                    .and_then(|a| a.count)
+                    .unwrap_or(0);
+
+                let required: i64 = large_group_required + small_group_required;
+                let attended: i64 = large_group_attended + small_group_attended + bonus_attended;
+                let rate: f64 = if required == 0 {
+                    1.0
+                } else {
+                    (attended as f64 / required as f64).min(1.0)
+                };
+
+                StudentAttendance {
+                    username: enrollment.username,
+                    first_name: user.first_name,
+                    last_name: user.last_name,
+                    rcs_id: user.rcs_id.into_iter().next().map(|a| a.account_id),
+                    small_group_id,
+                    small_group: small_group_id.and_then(|id| group_titles.get(&id).cloned()),
+                    large_group_attended,
+                    small_group_attended,
+                    small_group_required,
+                    bonus_attended,
+                    rate,
+                    percent: percent(rate),
+                    below_threshold: rate < threshold,
+                }
+            })
+            .collect();
+
+        // Summarize each small group, in order, then the students not in any small group.
+        let mut groups: Vec<Option<i64>> = data
+            .small_groups
+            .iter()
+            .map(|group| Some(group.small_group_id))
+            .collect();
+        groups.push(None);
+
+        let small_groups: Vec<SmallGroupSummary> = groups
+            .into_iter()
+            .filter_map(|group_id| {
+                let members: Vec<&StudentAttendance> = students
+                    .iter()
+                    .filter(|student| student.small_group_id == group_id)
+                    .collect();
+
+                if members.is_empty() {
+                    return None;
+                }
+
+                let average_rate: f64 =
+                    members.iter().map(|s| s.rate).sum::<f64>() / members.len() as f64;
+
+                Some(SmallGroupSummary {
+                    title: group_id.and_then(|id| group_titles.get(&id).cloned()),
+                    required: small_group_meetings.required(group_id),
+                    students: members.len(),
+                    below_threshold: members.iter().filter(|s| s.below_threshold).count(),
+                    average_rate,
+                    percent: percent(average_rate),
+                })
+            })
+            .collect();
+
+        Report {
+            semester_id: data.semester_id,
+            title: data.title,
+            large_group_required,
+            threshold,
+            threshold_percent: percent(threshold),
+            students,
+            small_groups,
+        }
+    }
+
+    /// Get the report for a semester. Error if the semester does not exist.
+    async fn get(semester_id: String) -> Result<Self, TelescopeError> {
+        AttendanceReport::get(semester_id)
+            .await?
+            .map(Report::new)
+            .ok_or(TelescopeError::resource_not_found(
+                "Semester Not Found",
+                "Could not find a semester for this ID.",
+            ))
+    }
+}
+
+/// Format an attendance rate as a percentage.
+fn percent(rate: f64) -> String {
+    format!("{:.0}%", rate * 100.0)
+}
+
+/// Attendance report page.
+#[get("/attendance")]
+async fn report(
+    req: HttpRequest,
+    Query(SemesterQuery { semester }): Query<SemesterQuery>,
+) -> Result<Template, TelescopeError> {
+    let semesters = SemesterList::get().await?;
+
+    // Default to the most recent semester.
+    let semester_id: Option<String> = semester
+        .filter(|s| !s.trim().is_empty())
+        .or_else(|| semesters.first().map(|s| s.semester_id.clone()));
+
+    let report: Option<Report> = match semester_id.clone() {
+        Some(semester_id) => Some(Report::get(semester_id).await?),
+        None => None,
+    };
+
+    Template::new("admin/attendance")
+        .field("semesters", semesters)
+        .field("semester_id", semester_id)
+        .field("report", report)
+        .render_into_page(&req, "Attendance Report")
+        .await
+}
+
+/// Download the attendance report for a semester as a CSV.
+#[get("/attendance/export")]
+async fn export(
+    Query(SemesterQuery { semester }): Query<SemesterQuery>,
+) -> Result<HttpResponse, TelescopeError> {
+    let semester_id: String =
+        semester
+            .filter(|s| !s.trim().is_empty())
+            .ok_or(TelescopeError::BadRequest {
+                header: "No Semester Selected".into(),
+                message: "Select a semester to export.".into(),
+                show_status_code: false,
+            })?;
+
+    let report: Report = Report::get(semester_id.clone()).await?;
+
+    let mut body: String = csv_line(&[
+        "Last Name",
+        "First Name",
+        "RCS ID",
+        "Username",
+        "Small Group",
+        "Large Group Attended",
+        "Large Group Required",
+        "Small Group Attended",
+        "Small Group Required",
+        "Bonus Attended",
+        "Attendance Rate",
+        "Below Threshold",
+    ]);
+
+    for student in report.students.iter() {
+        body.push_str(
+            csv_line(&[
+                student.last_name.clone(),
+                student.first_name.clone(),
+                student.rcs_id.clone().unwrap_or_default(),
+                student.username.clone(),
+                student.small_group.clone().unwrap_or_default(),
+                student.large_group_attended.to_string(),
+                report.large_group_required.to_string(),
+                student.small_group_attended.to_string(),
+                student.small_group_required.to_string(),
+                student.bonus_attended.to_string(),
+                student.percent.clone(),
+                if student.below_threshold { "yes" } else { "no" }.to_string(),
+            ])
+            .as_str(),
+        );
+    }
+
+    Ok(csv_download(
+        format!("attendance_{}.csv", semester_id).as_str(),
+        body,
+    ))
+}
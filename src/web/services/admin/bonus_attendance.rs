@@ -0,0 +1,184 @@
+//! Services to award bonus attendance.
+//!
+//! Coordinators search the users enrolled in a semester, the same way as meeting host
+//! selection, and award bonus attendance to any number of them at once. Like announcements,
+//! these are open to current coordinators as well as admins.
+
+use crate::api::rcos::bonus_attendances::{
+    award, BonusAttendanceCandidates, BonusAttendances, DeleteBonusAttendance,
+};
+use crate::api::rcos::semesters::list::SemesterList;
+use crate::audit::AuditEntry;
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, Query, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+/// The handlebars template for the bonus attendance page.
+const TEMPLATE: &'static str = "admin/bonus_attendance";
+
+/// Register the bonus attendance page and its forms under `/admin/bonus_attendance`.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(index).service(submit_award).service(remove);
+}
+
+/// Query parameters on the bonus attendance page.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct BonusAttendanceQuery {
+    /// The semester ID. Defaults to the most recent semester.
+    #[serde(default)]
+    semester: Option<String>,
+    /// Search for users to award bonus attendance to.
+    #[serde(default)]
+    search: Option<String>,
+}
+
+/// Redirect back to the bonus attendance page for a semester.
+fn back_to_semester(semester_id: String) -> HttpResponse {
+    let query: String = serde_urlencoded::to_string(BonusAttendanceQuery {
+        semester: Some(semester_id),
+        search: None,
+    })
+    .expect("Could not URL-encode semester ID");
+
+    HttpResponse::Found()
+        .header(LOCATION, format!("/admin/bonus_attendance?{}", query))
+        .finish()
+}
+
+/// Page to search users and award bonus attendance, listing what was awarded in the semester.
+#[get("")]
+async fn index(
+    req: HttpRequest,
+    Query(BonusAttendanceQuery { semester, search }): Query<BonusAttendanceQuery>,
+) -> Result<Template, TelescopeError> {
+    let semesters = SemesterList::get().await?;
+
+    // Default to the most recent semester.
+    let semester_id: Option<String> = semester
+        .filter(|s| !s.trim().is_empty())
+        .or_else(|| semesters.first().map(|s| s.semester_id.clone()));
+
+    // Ignore empty searches.
+    let search: Option<String> = search.filter(|s| !s.trim().is_empty());
+
+    let (candidates, awarded) = match semester_id.clone() {
+        Some(semester_id) => {
+            let candidates = match search.clone() {
+                Some(search) => {
+                    BonusAttendanceCandidates::get(semester_id.clone(), Some(search)).await?
+                }
+                None => Vec::new(),
+            };
+            (candidates, BonusAttendances::get(semester_id).await?)
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    Template::new(TEMPLATE)
+        .field("semesters", semesters)
+        .field("semester_id", semester_id)
+        .field("search", search)
+        .field("candidates", candidates)
+        .field("awarded", awarded)
+        .render_into_page(&req, "Bonus Attendance")
+        .await
+}
+
+/// Award bonus attendance. The form has the `semester_id`, the `reason`, and a `username`
+/// entry for each selected user, so it is read as a list of pairs.
+#[post("")]
+async fn submit_award(
+    auth: AuthenticationCookie,
+    Form(fields): Form<Vec<(String, String)>>,
+) -> Result<HttpResponse, TelescopeError> {
+    let actor: String = auth.get_rcos_username_or_error().await?;
+
+    // Get the value of a single form field.
+    let field = |name: &str| -> Option<String> {
+        fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let semester_id: String = field("semester_id").ok_or(TelescopeError::BadRequest {
+        header: "No Semester Selected".into(),
+        message: "Select a semester to award bonus attendance in.".into(),
+        show_status_code: false,
+    })?;
+
+    let reason: String = field("reason").ok_or(TelescopeError::BadRequest {
+        header: "No Reason Given".into(),
+        message: "Enter the reason bonus attendance is being awarded, like the event name.".into(),
+        show_status_code: false,
+    })?;
+
+    let mut usernames: Vec<String> = fields
+        .iter()
+        .filter(|(key, _)| key == "username")
+        .map(|(_, value)| value.clone())
+        .collect();
+    usernames.sort();
+    usernames.dedup();
+
+    if usernames.is_empty() {
+        return Err(TelescopeError::BadRequest {
+            header: "No Users Selected".into(),
+            message: "Select at least one user to award bonus attendance to.".into(),
+            show_status_code: false,
+        });
+    }
+
+    award(semester_id.as_str(), usernames.as_slice(), reason.as_str()).await?;
+
+    for username in usernames.iter() {
+        AuditEntry::new(
+            actor.as_str(),
+            "bonus_attendance_awarded",
+            username.as_str(),
+            format!(
+                "Awarded bonus attendance for semester {}: {}",
+                semester_id, reason
+            ),
+        )
+        .record()
+        .await;
+    }
+
+    Ok(back_to_semester(semester_id))
+}
+
+/// Remove a bonus attendance record.
+#[post("/{bonus_attendance_id}/delete")]
+async fn remove(
+    auth: AuthenticationCookie,
+    Path(bonus_attendance_id): Path<i64>,
+) -> Result<HttpResponse, TelescopeError> {
+    let actor: String = auth.get_rcos_username_or_error().await?;
+
+    let removed = DeleteBonusAttendance::execute(bonus_attendance_id)
+        .await?
+        .ok_or(TelescopeError::resource_not_found(
+            "Bonus Attendance Not Found",
+            "Could not find a bonus attendance record for this ID.",
+        ))?;
+
+    AuditEntry::new(
+        actor,
+        "bonus_attendance_removed",
+        removed.username.as_str(),
+        format!(
+            "Removed bonus attendance for semester {}: {}",
+            removed.semester_id,
+            removed.reason.as_deref().unwrap_or("no reason given")
+        ),
+    )
+    .record()
+    .await;
+
+    Ok(back_to_semester(removed.semester_id))
+}
@@ -0,0 +1,334 @@
+//! Services for faculty advisors to enter grades and handle grade appeals.
+//!
+//! The grading sheet lists every student enrolled in a semester with a summary of their
+//! attendance, status update grades, and presentation grade next to their mid-year and final
+//! grades. Attendance is out of the large group meetings and the student's own small group
+//! meetings. Grade changes and appeal decisions are recorded to the audit trail.
+
+use crate::api::rcos::grades::appeals::{GradeAppeals, SetAppealHandled};
+use crate::api::rcos::grades::sheet::{GradingSheet, SetGrades};
+use crate::api::rcos::semesters::list::SemesterList;
+use crate::audit::AuditEntry;
+use crate::error::TelescopeError;
+use crate::templates::helpers::round_grade;
+use crate::templates::Template;
+use crate::web::services::admin::small_group_meetings::SmallGroupMeetings;
+use crate::web::services::admin::SemesterQuery;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Query, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+/// The number of handled appeals shown in the appeals inbox.
+const HANDLED_APPEALS: i64 = 50;
+
+/// Register grading services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(sheet)
+        .service(set_grades)
+        .service(appeals)
+        .service(handle_appeal);
+}
+
+/// Grading sheet for a semester.
+#[get("/grades")]
+async fn sheet(
+    req: HttpRequest,
+    Query(SemesterQuery { semester }): Query<SemesterQuery>,
+) -> Result<Template, TelescopeError> {
+    let semesters = SemesterList::get().await?;
+
+    // Default to the most recent semester.
+    let semester_id: Option<String> = semester
+        .filter(|s| !s.trim().is_empty())
+        .or_else(|| semesters.first().map(|s| s.semester_id.clone()));
+
+    let sheet = match semester_id.clone() {
+        Some(semester_id) => GradingSheet::get(semester_id).await?,
+        None => None,
+    };
+
+    let large_group_count: i64 = sheet
+        .as_ref()
+        .and_then(|sheet| sheet.large_group_meetings.aggregate.as_ref())
+        .and_then(|a| a.count)
+        .unwrap_or(0);
+
+    // Students only need to attend their own small group's meetings.
+    let small_group_meetings: SmallGroupMeetings = sheet
+        .as_ref()
+        .map(|sheet| {
+            SmallGroupMeetings::new(
+                sheet.small_groups.iter().map(|group| {
+                    (
+                        group.small_group_id,
+                        group
+                            .small_group_projects
+                            .iter()
+                            .map(|project| project.project_id)
+                            .collect(),
+                        group
+                            .small_group_mentors
+                            .iter()
+                            .map(|mentor| mentor.username.clone())
+                            .collect(),
+                    )
+                }),
+                sheet
+                    .small_group_meetings
+                    .iter()
+                    .map(|meeting| meeting.host_username.as_deref()),
+            )
+        })
+        .unwrap_or_default();
+
+    // Summarize each student's semester.
+    let students: Vec<serde_json::Value> = sheet
+        .as_ref()
+        .map(|sheet| {
+            sheet
+                .enrollments
+                .iter()
+                .map(|enrollment| {
+                    let small_group: Option<i64> = small_group_meetings.group_of_project(
+                        enrollment
+                            .project
+                            .as_ref()
+                            .map(|project| project.project_id),
+                    );
+                    let required: i64 =
+                        large_group_count + small_group_meetings.required(small_group);
+
+                    let large_group_attended: i64 = enrollment
+                        .user
+                        .large_group_attended
+                        .aggregate
+                        .as_ref()
+                        .and_then(|a| a.count)
+                        .unwrap_or(0);
+                    let small_group_attended: i64 = small_group_meetings.count_attended(
+                        small_group,
+                        enrollment
+                            .user
+                            .small_group_attended
+                            .iter()
+                            .map(|attendance| attendance.meeting.host_username.as_deref()),
+                    );
+
+                    let bonus_attended: i64 = enrollment
+                        .bonus_attendances_aggregate
+                        .aggregate
+                        .as_ref()
+                        .and_then(|a| a.count)
+                        .unwrap_or(0);
+
+                    let status_updates = enrollment.user.status_updates.aggregate.as_ref();
+                    let submitted: i64 = status_updates.and_then(|a| a.count).unwrap_or(0);
+                    let status_update_average: Option<f64> = status_updates
+                        .and_then(|a| a.avg.as_ref())
+                        .and_then(|avg| avg.grade);
+
+                    let presentation_grade: Option<f64> = enrollment
+                        .project
+                        .as_ref()
+                        .and_then(|project| project.presentation.aggregate.as_ref())
+                        .and_then(|a| a.avg.as_ref())
+                        .and_then(|avg| avg.grade);
+
+                    let open_appeals: i64 = enrollment
+                        .open_appeals
+                        .aggregate
+                        .as_ref()
+                        .and_then(|a| a.count)
+                        .unwrap_or(0);
+
+                    json!({
+                        "username": &enrollment.username,
+                        "first_name": &enrollment.user.first_name,
+                        "last_name": &enrollment.user.last_name,
+                        "credits": enrollment.credits,
+                        "is_for_pay": enrollment.is_for_pay,
+                        "project": &enrollment.project,
+                        "attended": large_group_attended + small_group_attended,
+                        "required": required,
+                        "bonus_attended": bonus_attended,
+                        "status_updates_submitted": submitted,
+                        "status_update_average": status_update_average.map(round_grade),
+                        "presentation_grade": presentation_grade.map(round_grade),
+                        "has_open_appeal": open_appeals > 0,
+                        "mid_year_grade": enrollment.mid_year_grade,
+                        "final_grade": enrollment.final_grade,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let status_update_count: i64 = sheet
+        .as_ref()
+        .and_then(|sheet| sheet.status_updates_aggregate.aggregate.as_ref())
+        .and_then(|a| a.count)
+        .unwrap_or(0);
+
+    Template::new("admin/grades/sheet")
+        .field("semesters", semesters)
+        .field("semester_id", semester_id)
+        .field("semester", sheet.map(|sheet| sheet.title))
+        .field("status_update_count", status_update_count)
+        .field("students", students)
+        .render_into_page(&req, "Grading Sheet")
+        .await
+}
+
+/// Form to set a student's grades. Empty fields clear the grade.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct GradesForm {
+    semester_id: String,
+    username: String,
+    #[serde(default)]
+    mid_year_grade: String,
+    #[serde(default)]
+    final_grade: String,
+}
+
+/// Parse a grade from the grading sheet. Empty strings are no grade.
+fn parse_grade(value: &str) -> Result<Option<f64>, TelescopeError> {
+    let value: &str = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|grade| grade.is_finite() && *grade >= 0.0)
+        .map(Some)
+        .ok_or(TelescopeError::BadRequest {
+            header: "Invalid Grade".into(),
+            message: "Grades must be non-negative numbers.".into(),
+            show_status_code: false,
+        })
+}
+
+/// Format a grade for the audit trail.
+fn describe_grade(grade: Option<f64>) -> String {
+    grade
+        .map(|grade| grade.to_string())
+        .unwrap_or_else(|| "none".into())
+}
+
+/// Set a student's mid-year and final grades from the grading sheet.
+#[post("/grades/set")]
+async fn set_grades(
+    auth: AuthenticationCookie,
+    Form(GradesForm {
+        semester_id,
+        username,
+        mid_year_grade,
+        final_grade,
+    }): Form<GradesForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let actor: String = auth.get_rcos_username_or_error().await?;
+    let mid_year_grade: Option<f64> = parse_grade(mid_year_grade.as_str())?;
+    let final_grade: Option<f64> = parse_grade(final_grade.as_str())?;
+
+    let found: bool = SetGrades::execute(
+        semester_id.clone(),
+        username.clone(),
+        mid_year_grade,
+        final_grade,
+    )
+    .await?;
+
+    if !found {
+        return Err(TelescopeError::resource_not_found(
+            "Enrollment Not Found",
+            "This user is not enrolled in this semester.",
+        ));
+    }
+
+    AuditEntry::new(
+        actor,
+        "grade_change",
+        username.as_str(),
+        format!(
+            "Set grades for semester {}: mid-year {}, final {}.",
+            semester_id,
+            describe_grade(mid_year_grade),
+            describe_grade(final_grade)
+        ),
+    )
+    .record()
+    .await;
+
+    let query: String = serde_urlencoded::to_string(SemesterQuery {
+        semester: Some(semester_id),
+    })
+    .expect("Could not URL-encode semester ID");
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/admin/grades?{}", query))
+        .finish())
+}
+
+/// Inbox of grade appeals.
+#[get("/appeals")]
+async fn appeals(req: HttpRequest) -> Result<Template, TelescopeError> {
+    let appeals = GradeAppeals::get(HANDLED_APPEALS).await?;
+
+    Template::new("admin/grades/appeals")
+        .field("open", appeals.open)
+        .field("handled", appeals.handled)
+        .render_into_page(&req, "Grade Appeals")
+        .await
+}
+
+/// Form to mark a grade appeal handled or reopen it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct HandleAppealForm {
+    semester_id: String,
+    username: String,
+    is_handled: bool,
+}
+
+/// Mark a grade appeal handled or reopen it.
+#[post("/appeals/handle")]
+async fn handle_appeal(
+    auth: AuthenticationCookie,
+    Form(HandleAppealForm {
+        semester_id,
+        username,
+        is_handled,
+    }): Form<HandleAppealForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let actor: String = auth.get_rcos_username_or_error().await?;
+
+    let found: bool =
+        SetAppealHandled::execute(semester_id.clone(), username.clone(), is_handled).await?;
+    if !found {
+        return Err(TelescopeError::resource_not_found(
+            "Appeal Not Found",
+            "Could not find a grade appeal for this user in this semester.",
+        ));
+    }
+
+    let (action, description) = if is_handled {
+        (
+            "grade_appeal_handled",
+            format!("Marked grade appeal for semester {} handled.", semester_id),
+        )
+    } else {
+        (
+            "grade_appeal_reopened",
+            format!("Reopened grade appeal for semester {}.", semester_id),
+        )
+    };
+    AuditEntry::new(actor, action, username.as_str(), description)
+        .record()
+        .await;
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/admin/appeals")
+        .finish())
+}
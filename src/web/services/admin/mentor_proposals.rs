@@ -0,0 +1,120 @@
+//! Mentor proposal review queue.
+//!
+//! Coordinators and admins approve or reject applications to mentor in ongoing semesters.
+//! Approved applicants can then be assigned to small groups.
+
+use crate::api::rcos::mentor_proposals::review::{
+    mentor_proposal::MentorProposalProposal, MentorProposal, MentorProposalQueue,
+    ReviewMentorProposal,
+};
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::admin::review::{
+    check_reviewer_enrolled, normalize_comments, ReviewForm,
+};
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+/// The handlebars template for the mentor proposal review queue.
+const QUEUE_TEMPLATE: &'static str = "admin/mentor_proposals/queue";
+
+/// The handlebars template for reviewing a single mentor proposal.
+const REVIEW_TEMPLATE: &'static str = "admin/mentor_proposals/review";
+
+/// Register the mentor application queue and review pages under `/admin/mentor_proposals`.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(queue)
+        .service(review_page)
+        .service(submit_review);
+}
+
+/// The review queue for mentor proposals in ongoing semesters.
+#[get("")]
+async fn queue(req: HttpRequest) -> Result<Template, TelescopeError> {
+    let proposals = MentorProposalQueue::get().await?;
+
+    // Show pending proposals separately from reviewed ones.
+    let (reviewed, pending): (Vec<_>, Vec<_>) = proposals
+        .into_iter()
+        .partition(|proposal| proposal.is_approved.is_some());
+
+    return Template::new(QUEUE_TEMPLATE)
+        .field("pending", pending)
+        .field("reviewed", reviewed)
+        .render_into_page(&req, "Mentor Proposals")
+        .await;
+}
+
+/// Get a mentor proposal and check that the reviewer is enrolled in its semester.
+async fn get_proposal_for_review(
+    semester_id: String,
+    username: String,
+    reviewer: String,
+) -> Result<MentorProposalProposal, TelescopeError> {
+    let response = MentorProposal::get(semester_id, username, reviewer).await?;
+    let proposal: MentorProposalProposal =
+        response.proposal.ok_or(TelescopeError::resource_not_found(
+            "Proposal Not Found",
+            "Could not find a mentor proposal for this user and semester.",
+        ))?;
+
+    let semester_title: &str = proposal
+        .applicant
+        .as_ref()
+        .map(|applicant| applicant.semester.title.as_str())
+        .unwrap_or("this semester");
+    check_reviewer_enrolled(
+        response.reviewer_enrollment.is_some(),
+        semester_title,
+        "mentor proposals",
+    )?;
+
+    return Ok(proposal);
+}
+
+/// Page to review a mentor proposal.
+#[get("/{semester_id}/{username}")]
+async fn review_page(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+    Path((semester_id, username)): Path<(String, String)>,
+) -> Result<Template, TelescopeError> {
+    let reviewer: String = auth.get_rcos_username_or_error().await?;
+    let proposal = get_proposal_for_review(semester_id, username, reviewer).await?;
+
+    return Template::new(REVIEW_TEMPLATE)
+        .field("proposal", proposal)
+        .render_into_page(&req, "Review Mentor Proposal")
+        .await;
+}
+
+/// Endpoint to submit a mentor proposal review.
+#[post("/{semester_id}/{username}")]
+async fn submit_review(
+    auth: AuthenticationCookie,
+    Path((semester_id, username)): Path<(String, String)>,
+    Form(ReviewForm {
+        decision,
+        reviewer_comments,
+    }): Form<ReviewForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let reviewer: String = auth.get_rcos_username_or_error().await?;
+    // Check that the proposal exists and the reviewer can review it.
+    get_proposal_for_review(semester_id.clone(), username.clone(), reviewer.clone()).await?;
+
+    ReviewMentorProposal::execute(
+        semester_id,
+        username,
+        decision.is_approved(),
+        reviewer,
+        normalize_comments(reviewer_comments),
+    )
+    .await?;
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/admin/mentor_proposals")
+        .finish())
+}
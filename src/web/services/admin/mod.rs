@@ -1,7 +1,20 @@
 //! Services for the admin panel.
 
+mod announcements;
+mod attendance;
+mod bonus_attendance;
+mod grades;
+mod mentor_proposals;
+mod organizations;
+mod pay_requests;
+mod pitches;
+mod review;
 mod semesters;
+mod small_group_meetings;
+mod users;
+mod workshop_proposals;
 
+use crate::api::rcos::semesters::current::coordinators::CurrentCoordinators;
 use crate::api::rcos::users::role_lookup::RoleLookup;
 use crate::api::rcos::users::UserRole;
 use crate::error::TelescopeError;
@@ -13,6 +26,14 @@ use actix_web::web::ServiceConfig;
 use actix_web::HttpRequest;
 use futures::future::LocalBoxFuture;
 
+/// Query parameters to select a semester on admin pages.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct SemesterQuery {
+    /// The semester ID. Pages default to the most recent semester.
+    #[serde(default)]
+    semester: Option<String>,
+}
+
 /// Check that a user is an admin.
 fn admin_authorization(username: String) -> LocalBoxFuture<'static, AuthorizationResult> {
     Box::pin(async move {
@@ -31,6 +52,35 @@ fn admin_authorization(username: String) -> LocalBoxFuture<'static, Authorizatio
     })
 }
 
+/// Check that a user is a current coordinator or an admin.
+fn coordinator_authorization(username: String) -> LocalBoxFuture<'static, AuthorizationResult> {
+    Box::pin(async move {
+        let role: UserRole = RoleLookup::get(username.clone())
+            .await?
+            // The role should not be none, since the account needs to exist at this point.
+            .expect("Viewer's account does not exist.");
+
+        if role.is_admin() || CurrentCoordinators::contains(username.as_str()).await? {
+            Ok(())
+        } else {
+            Err(TelescopeError::Forbidden)
+        }
+    })
+}
+
+/// Register services in a scope open to current coordinators and admins.
+fn register_coordinator_scope(
+    config: &mut ServiceConfig,
+    path: &str,
+    services: fn(&mut ServiceConfig),
+) {
+    config.service(
+        aweb::scope(path)
+            .wrap(Authorization::new(coordinator_authorization))
+            .configure(services),
+    );
+}
+
 /// Register admin panel services.
 pub fn register(config: &mut ServiceConfig) {
     // Create admin authorization middleware.
@@ -44,6 +94,27 @@ pub fn register(config: &mut ServiceConfig) {
             .to(index),
     );
 
+    // Announcement management, review queues, and bonus attendance are open to coordinators
+    // too. These have to be registered before the admin scope, which would otherwise match
+    // their paths.
+    register_coordinator_scope(config, "/admin/announcements", announcements::register);
+    register_coordinator_scope(config, "/admin/pitches", pitches::register);
+    register_coordinator_scope(
+        config,
+        "/admin/mentor_proposals",
+        mentor_proposals::register,
+    );
+    register_coordinator_scope(
+        config,
+        "/admin/workshop_proposals",
+        workshop_proposals::register,
+    );
+    register_coordinator_scope(
+        config,
+        "/admin/bonus_attendance",
+        bonus_attendance::register,
+    );
+
     // Route every sub-service through the admin scope.
     config.service(
         // Create the admin scope.
@@ -51,7 +122,17 @@ pub fn register(config: &mut ServiceConfig) {
             // Verify that the viewer has the admin role.
             .wrap(admin_authorization_middleware)
             // Semester services
-            .configure(semesters::register),
+            .configure(semesters::register)
+            // User management services
+            .configure(users::register)
+            // Pay request review and payroll export
+            .configure(pay_requests::register)
+            // Grading sheet and grade appeals
+            .configure(grades::register)
+            // Attendance report and export
+            .configure(attendance::register)
+            // External organization management
+            .configure(organizations::register),
     );
 }
 
@@ -0,0 +1,264 @@
+//! External organization management services.
+//!
+//! External organizations are the companies and groups that sponsor RCOS projects. Contact
+//! emails are only shown here; the public organization pages list the organization's
+//! projects.
+
+use crate::api::rcos::external_organizations::list::ExternalOrganizations;
+use crate::api::rcos::external_organizations::mutations::{
+    CreateExternalOrganization, DeleteExternalOrganization, EditExternalOrganization,
+};
+use crate::api::rcos::external_organizations::organization::{
+    get_external_organization::GetExternalOrganizationOrganization, GetExternalOrganization,
+};
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::templates::Template;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+use url::Url;
+
+/// The handlebars template for the organization creation and edit form.
+const FORM_TEMPLATE: &'static str = "admin/organizations/form";
+
+/// Register organization management services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(index)
+        .service(creation_page)
+        .service(submit_creation)
+        .service(edit_page)
+        .service(submit_edit)
+        .service(delete);
+}
+
+/// Page listing all external organizations.
+#[get("/organizations")]
+async fn index(req: HttpRequest) -> Result<Template, TelescopeError> {
+    Template::new("admin/organizations/index")
+        .field("organizations", ExternalOrganizations::get().await?)
+        .render_into_page(&req, "External Organizations")
+        .await
+}
+
+/// Form submitted to create or edit an external organization.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct OrganizationForm {
+    /// The organization's name.
+    title: String,
+    /// The organization's homepage.
+    homepage: String,
+    /// Contact emails, one per line.
+    #[serde(default)]
+    contact_emails: String,
+}
+
+/// The fields of an organization form after validation.
+struct ValidatedOrganization {
+    title: String,
+    homepage: String,
+    /// Contact emails as `mailto:` URLs, which is how the database stores them.
+    contact_emails: Vec<Url>,
+}
+
+/// Format contact email URLs as plain addresses, one per line, for the form.
+fn format_contact_emails(contact_emails: &[Url]) -> String {
+    contact_emails
+        .iter()
+        .map(|url| match url.scheme() {
+            "mailto" => url.path().to_string(),
+            _ => url.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+impl OrganizationForm {
+    /// Validate this form, saving the submitted values and any issues to the form template.
+    fn validate(self, form: &mut FormTemplate) -> Option<ValidatedOrganization> {
+        form.template["data"] = json!({
+            "title": &self.title,
+            "homepage": &self.homepage,
+            "contact_emails": &self.contact_emails,
+        });
+
+        let mut valid: bool = true;
+
+        let title: String = self.title.trim().to_string();
+        if title.is_empty() {
+            form.template["issues"]["title"] = json!("Organizations must have a name.");
+            valid = false;
+        }
+
+        let homepage: String = self.homepage.trim().to_string();
+        if Url::parse(homepage.as_str()).is_err() {
+            form.template["issues"]["homepage"] = json!("Could not parse this URL.");
+            valid = false;
+        }
+
+        // Parse each email into a mailto URL.
+        let mut contact_emails: Vec<Url> = Vec::new();
+        for line in self.contact_emails.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+
+            let parsed: Option<Url> = Some(line)
+                .filter(|email| email.contains('@') && !email.contains(':'))
+                .and_then(|email| Url::parse(format!("mailto:{}", email).as_str()).ok());
+
+            match parsed {
+                Some(url) => contact_emails.push(url),
+                None => {
+                    form.template["issues"]["contact_emails"] =
+                        json!(format!("\"{}\" is not an email address.", line));
+                    valid = false;
+                }
+            }
+        }
+
+        return valid.then(|| ValidatedOrganization {
+            title,
+            homepage,
+            contact_emails,
+        });
+    }
+}
+
+/// Page to create an external organization.
+#[get("/organizations/create")]
+async fn creation_page() -> FormTemplate {
+    FormTemplate::new(FORM_TEMPLATE, "Create Organization")
+}
+
+/// Endpoint to submit organization creation.
+#[post("/organizations/create")]
+async fn submit_creation(
+    Form(input): Form<OrganizationForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let mut form = FormTemplate::new(FORM_TEMPLATE, "Create Organization");
+    let validated: ValidatedOrganization = input
+        .validate(&mut form)
+        .ok_or(TelescopeError::invalid_form(&form))?;
+
+    let external_organization_id: i64 = CreateExternalOrganization::execute(
+        validated.title,
+        validated.homepage,
+        validated.contact_emails,
+    )
+    .await?
+    .ok_or(TelescopeError::ise(
+        "Organization creation did not return ID.",
+    ))?;
+
+    Ok(HttpResponse::Found()
+        .header(
+            LOCATION,
+            format!("/organizations/{}", external_organization_id),
+        )
+        .finish())
+}
+
+/// Get an organization or return a resource not found error.
+async fn get_organization(
+    external_organization_id: i64,
+) -> Result<GetExternalOrganizationOrganization, TelescopeError> {
+    GetExternalOrganization::get(external_organization_id)
+        .await?
+        .ok_or(TelescopeError::resource_not_found(
+            "Organization Not Found",
+            "Could not find an external organization for this ID.",
+        ))
+}
+
+/// Make the edit form for an existing organization.
+fn make_edit_form(organization: &GetExternalOrganizationOrganization) -> FormTemplate {
+    let mut form = FormTemplate::new(FORM_TEMPLATE, "Edit Organization");
+    form.template = json!({
+        "organization": organization,
+        "data": {
+            "title": &organization.title,
+            "homepage": &organization.homepage,
+            "contact_emails": format_contact_emails(&organization.contact_emails),
+        }
+    });
+    return form;
+}
+
+/// Page to edit an external organization.
+#[get("/organizations/{external_organization_id}/edit")]
+async fn edit_page(
+    Path(external_organization_id): Path<i64>,
+) -> Result<FormTemplate, TelescopeError> {
+    let organization = get_organization(external_organization_id).await?;
+    return Ok(make_edit_form(&organization));
+}
+
+/// Endpoint to submit organization edits.
+#[post("/organizations/{external_organization_id}/edit")]
+async fn submit_edit(
+    Path(external_organization_id): Path<i64>,
+    Form(input): Form<OrganizationForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let organization = get_organization(external_organization_id).await?;
+    let mut form: FormTemplate = make_edit_form(&organization);
+    let validated: ValidatedOrganization = input
+        .validate(&mut form)
+        .ok_or(TelescopeError::invalid_form(&form))?;
+
+    EditExternalOrganization::execute(
+        external_organization_id,
+        validated.title,
+        validated.homepage,
+        validated.contact_emails,
+    )
+    .await?
+    .ok_or(TelescopeError::resource_not_found(
+        "Organization Not Found",
+        "Could not find an external organization for this ID.",
+    ))?;
+
+    Ok(HttpResponse::Found()
+        .header(
+            LOCATION,
+            format!("/organizations/{}", external_organization_id),
+        )
+        .finish())
+}
+
+/// Delete an external organization. Organizations that have sponsored projects cannot be
+/// deleted, since the projects still reference them.
+#[post("/organizations/{external_organization_id}/delete")]
+async fn delete(Path(external_organization_id): Path<i64>) -> Result<HttpResponse, TelescopeError> {
+    let organization = get_organization(external_organization_id).await?;
+
+    let project_count: i64 = organization
+        .project_count
+        .aggregate
+        .as_ref()
+        .and_then(|a| a.count)
+        .unwrap_or(0);
+
+    if project_count > 0 {
+        return Err(TelescopeError::BadRequest {
+            header: "Organization Has Projects".into(),
+            message: format!(
+                "{} has sponsored projects and cannot be deleted.",
+                organization.title
+            ),
+            show_status_code: false,
+        });
+    }
+
+    DeleteExternalOrganization::execute(external_organization_id)
+        .await?
+        .ok_or(TelescopeError::resource_not_found(
+            "Organization Not Found",
+            "Could not find an external organization for this ID.",
+        ))?;
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/admin/organizations")
+        .finish())
+}
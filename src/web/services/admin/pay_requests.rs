@@ -0,0 +1,163 @@
+//! Services for faculty advisors to review pay requests and export payroll.
+//!
+//! Approving a pay request marks the enrollment for pay. Denying it takes the enrollment off
+//! pay. Every decision is recorded to the audit trail.
+
+use crate::api::rcos::pay_requests::review::{ForPayStudents, PayRequests, ReviewPayRequest};
+use crate::api::rcos::semesters::list::SemesterList;
+use crate::audit::AuditEntry;
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::admin::SemesterQuery;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use crate::web::{csv_download, csv_line};
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Query, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+/// Register pay request services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(index).service(review).service(export);
+}
+
+/// Page listing the pay requests in a semester.
+#[get("/pay_requests")]
+async fn index(
+    req: HttpRequest,
+    Query(SemesterQuery { semester }): Query<SemesterQuery>,
+) -> Result<Template, TelescopeError> {
+    let semesters = SemesterList::get().await?;
+
+    // Default to the most recent semester.
+    let semester_id: Option<String> = semester
+        .filter(|s| !s.trim().is_empty())
+        .or_else(|| semesters.first().map(|s| s.semester_id.clone()));
+
+    let requests = match semester_id.clone() {
+        Some(semester_id) => PayRequests::get(semester_id).await?,
+        None => Vec::new(),
+    };
+
+    // Split the requests by whether a decision has been made.
+    let (pending, reviewed): (Vec<_>, Vec<_>) = requests
+        .into_iter()
+        .partition(|request| request.is_approved.is_none());
+
+    Template::new("admin/pay_requests/index")
+        .field("semesters", semesters)
+        .field("semester_id", semester_id)
+        .field("pending", pending)
+        .field("reviewed", reviewed)
+        .render_into_page(&req, "Pay Requests")
+        .await
+}
+
+/// Form to approve or deny a pay request.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ReviewForm {
+    semester_id: String,
+    username: String,
+    is_approved: bool,
+}
+
+/// Approve or deny a pay request.
+#[post("/pay_requests/review")]
+async fn review(
+    auth: AuthenticationCookie,
+    Form(ReviewForm {
+        semester_id,
+        username,
+        is_approved,
+    }): Form<ReviewForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let actor: String = auth.get_rcos_username_or_error().await?;
+
+    let found: bool =
+        ReviewPayRequest::execute(semester_id.clone(), username.clone(), is_approved).await?;
+    if !found {
+        return Err(TelescopeError::resource_not_found(
+            "Pay Request Not Found",
+            "Could not find a pay request for this user in this semester.",
+        ));
+    }
+
+    let (action, description) = if is_approved {
+        (
+            "pay_request_approved",
+            format!("Approved pay request for semester {}.", semester_id),
+        )
+    } else {
+        (
+            "pay_request_denied",
+            format!("Denied pay request for semester {}.", semester_id),
+        )
+    };
+    AuditEntry::new(actor, action, username.as_str(), description)
+        .record()
+        .await;
+
+    let query: String = serde_urlencoded::to_string(SemesterQuery {
+        semester: Some(semester_id),
+    })
+    .expect("Could not URL-encode semester ID");
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/admin/pay_requests?{}", query))
+        .finish())
+}
+
+/// Download a CSV of the students approved for pay in a semester, for payroll.
+#[get("/pay_requests/export")]
+async fn export(
+    Query(SemesterQuery { semester }): Query<SemesterQuery>,
+) -> Result<HttpResponse, TelescopeError> {
+    let semester_id: String =
+        semester
+            .filter(|s| !s.trim().is_empty())
+            .ok_or(TelescopeError::BadRequest {
+                header: "No Semester Selected".into(),
+                message: "Select a semester to export.".into(),
+                show_status_code: false,
+            })?;
+
+    let students = ForPayStudents::get(semester_id.clone()).await?;
+
+    let mut body: String = csv_line(&[
+        "Last Name",
+        "First Name",
+        "RCS ID",
+        "Username",
+        "Credits",
+        "Project",
+    ]);
+
+    for student in students {
+        let rcs_id: String = student
+            .user
+            .rcs_id
+            .first()
+            .map(|account| account.account_id.clone())
+            .unwrap_or_default();
+        let project: String = student
+            .project
+            .map(|project| project.title)
+            .unwrap_or_default();
+
+        body.push_str(
+            csv_line(&[
+                student.user.last_name,
+                student.user.first_name,
+                rcs_id,
+                student.username,
+                student.credits.to_string(),
+                project,
+            ])
+            .as_str(),
+        );
+    }
+
+    Ok(csv_download(
+        format!("for_pay_{}.csv", semester_id).as_str(),
+        body,
+    ))
+}
@@ -0,0 +1,172 @@
+//! Project pitch review queue.
+//!
+//! Coordinators and admins approve or reject the project pitches for ongoing semesters.
+//! Approving a pitch for a new project creates the project and makes the pitcher its lead.
+//! Approving a pitch for an existing project adds the pitcher to that project.
+
+use crate::api::rcos::project_pitches::review::{
+    project_pitch::ProjectPitchPitch, ApprovePitch, ApprovePitchWithProject, PitchReviewQueue,
+    ProjectPitch, RejectPitch,
+};
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::admin::review::{
+    check_reviewer_enrolled, normalize_comments, ReviewDecision, ReviewForm,
+};
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+/// The handlebars template for the pitch review queue.
+const QUEUE_TEMPLATE: &'static str = "admin/pitches/queue";
+
+/// The handlebars template for reviewing a single pitch.
+const REVIEW_TEMPLATE: &'static str = "admin/pitches/review";
+
+/// Register the pitch queue and review pages under `/admin/pitches`.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(queue)
+        .service(review_page)
+        .service(submit_review);
+}
+
+/// The review queue for project pitches in ongoing semesters.
+#[get("")]
+async fn queue(req: HttpRequest) -> Result<Template, TelescopeError> {
+    let pitches = PitchReviewQueue::get().await?;
+
+    // Show pending pitches separately from reviewed ones.
+    let (reviewed, pending): (Vec<_>, Vec<_>) = pitches
+        .into_iter()
+        .partition(|pitch| pitch.reviewer_username.is_some());
+
+    return Template::new(QUEUE_TEMPLATE)
+        .field("pending", pending)
+        .field("reviewed", reviewed)
+        .render_into_page(&req, "Project Pitches")
+        .await;
+}
+
+/// Get a pitch and check that the reviewer is enrolled in its semester.
+async fn get_pitch_for_review(
+    semester_id: String,
+    username: String,
+    reviewer: String,
+) -> Result<ProjectPitchPitch, TelescopeError> {
+    let response = ProjectPitch::get(semester_id, username, reviewer).await?;
+    let pitch: ProjectPitchPitch = response.pitch.ok_or(TelescopeError::resource_not_found(
+        "Pitch Not Found",
+        "Could not find a project pitch for this user and semester.",
+    ))?;
+
+    check_reviewer_enrolled(
+        response.reviewer_enrollment.is_some(),
+        pitch.semester.title.as_str(),
+        "project pitches",
+    )?;
+
+    return Ok(pitch);
+}
+
+/// Page to review a pitch.
+#[get("/{semester_id}/{username}")]
+async fn review_page(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+    Path((semester_id, username)): Path<(String, String)>,
+) -> Result<Template, TelescopeError> {
+    let reviewer: String = auth.get_rcos_username_or_error().await?;
+    let pitch = get_pitch_for_review(semester_id, username, reviewer).await?;
+
+    return Template::new(REVIEW_TEMPLATE)
+        .field("pitch", pitch)
+        .render_into_page(&req, "Review Project Pitch")
+        .await;
+}
+
+/// Endpoint to submit a pitch review.
+#[post("/{semester_id}/{username}")]
+async fn submit_review(
+    auth: AuthenticationCookie,
+    Path((semester_id, username)): Path<(String, String)>,
+    Form(ReviewForm {
+        decision,
+        reviewer_comments,
+    }): Form<ReviewForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let reviewer: String = auth.get_rcos_username_or_error().await?;
+    let pitch =
+        get_pitch_for_review(semester_id.clone(), username.clone(), reviewer.clone()).await?;
+
+    // Approved pitches are final, so that projects are not created twice.
+    if pitch.is_approved {
+        return Err(TelescopeError::BadRequest {
+            header: "Pitch Already Approved".into(),
+            message: "This pitch has already been approved.".into(),
+            show_status_code: false,
+        });
+    }
+
+    let reviewer_comments: Option<String> = normalize_comments(reviewer_comments);
+
+    match decision {
+        ReviewDecision::Approve => match pitch.existing_project_id {
+            Some(project_id) => {
+                let approved: bool = ApprovePitch::execute(
+                    semester_id,
+                    username,
+                    reviewer,
+                    reviewer_comments,
+                    project_id,
+                )
+                .await?;
+
+                if !approved {
+                    return Err(TelescopeError::ise(
+                        "Pitch approval did not update the pitch and enrollment.",
+                    ));
+                }
+            }
+
+            // Create the project for pitches of new projects.
+            None => {
+                let stack: Vec<String> = pitch
+                    .proposed_stack
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|item| item.trim().to_string())
+                    .filter(|item| !item.is_empty())
+                    .collect();
+
+                ApprovePitchWithProject::execute(
+                    semester_id,
+                    username,
+                    reviewer,
+                    reviewer_comments,
+                    pitch.proposed_title.unwrap_or_default(),
+                    pitch.proposed_description.unwrap_or_default(),
+                    stack,
+                )
+                .await?
+                .ok_or(TelescopeError::ise("Project creation did not return ID."))?;
+            }
+        },
+
+        ReviewDecision::Reject => {
+            let rejected: bool =
+                RejectPitch::execute(semester_id, username, reviewer, reviewer_comments).await?;
+
+            if !rejected {
+                return Err(TelescopeError::ise(
+                    "Pitch rejection did not update the pitch.",
+                ));
+            }
+        }
+    }
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/admin/pitches")
+        .finish())
+}
@@ -0,0 +1,55 @@
+//! Pieces shared by the coordinator review queues for project pitches, mentor proposals,
+//! and workshop proposals.
+
+use crate::error::TelescopeError;
+
+/// The decision on a review, from the button used to submit the review form.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approve,
+    Reject,
+}
+
+impl ReviewDecision {
+    /// Is this decision an approval?
+    pub fn is_approved(self) -> bool {
+        self == ReviewDecision::Approve
+    }
+}
+
+/// Form submitted to approve or reject something without any other input.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReviewForm {
+    /// The reviewer's decision.
+    pub decision: ReviewDecision,
+    /// Comments for the submitter.
+    #[serde(default)]
+    pub reviewer_comments: String,
+}
+
+/// Treat blank reviewer comments as no comments.
+pub fn normalize_comments(comments: String) -> Option<String> {
+    Some(comments).filter(|comments| !comments.trim().is_empty())
+}
+
+/// Reviews reference the reviewer's enrollment, so reviewers have to be enrolled in the semester
+/// of whatever they review. `reviewing` is the plural of what is reviewed, for the error message.
+pub fn check_reviewer_enrolled(
+    is_enrolled: bool,
+    semester_title: &str,
+    reviewing: &str,
+) -> Result<(), TelescopeError> {
+    if is_enrolled {
+        return Ok(());
+    }
+
+    Err(TelescopeError::BadRequest {
+        header: "Not Enrolled".into(),
+        message: format!(
+            "You must be enrolled in {} to review its {}.",
+            semester_title, reviewing
+        ),
+        show_status_code: false,
+    })
+}
@@ -0,0 +1,77 @@
+//! Which small group meetings each student is required to attend.
+//!
+//! Students only have to attend the meetings of their own small group, which is the small group
+//! their project is in. A small group's meetings are the small group meetings hosted by one of
+//! its mentors. Small group meetings without a host do not belong to any small group.
+
+use std::collections::{HashMap, HashSet};
+
+/// The small groups of a semester and their meetings.
+#[derive(Clone, Debug, Default)]
+pub struct SmallGroupMeetings {
+    /// The small group each project is in.
+    group_of_project: HashMap<i64, i64>,
+    /// The mentors of each small group.
+    mentors: HashMap<i64, HashSet<String>>,
+    /// The number of meetings of each small group.
+    required: HashMap<i64, i64>,
+}
+
+impl SmallGroupMeetings {
+    /// Build from the small groups of a semester, as their ID, project IDs, and mentor
+    /// usernames, and the hosts of the small group meetings to count.
+    pub fn new<'a>(
+        groups: impl IntoIterator<Item = (i64, Vec<i64>, Vec<String>)>,
+        meeting_hosts: impl IntoIterator<Item = Option<&'a str>>,
+    ) -> Self {
+        let mut result = SmallGroupMeetings::default();
+        for (group_id, projects, mentors) in groups {
+            for project_id in projects {
+                result.group_of_project.insert(project_id, group_id);
+            }
+            result
+                .mentors
+                .insert(group_id, mentors.into_iter().collect());
+        }
+
+        for host in meeting_hosts {
+            for (group_id, mentors) in result.mentors.iter() {
+                if host.map(|host| mentors.contains(host)).unwrap_or(false) {
+                    *result.required.entry(*group_id).or_insert(0) += 1;
+                }
+            }
+        }
+
+        return result;
+    }
+
+    /// Get the small group of a student's project.
+    pub fn group_of_project(&self, project_id: Option<i64>) -> Option<i64> {
+        project_id.and_then(|id| self.group_of_project.get(&id).copied())
+    }
+
+    /// The number of meetings a student in a small group is required to attend. Students who
+    /// are not in a small group have none.
+    pub fn required(&self, group_id: Option<i64>) -> i64 {
+        group_id
+            .and_then(|id| self.required.get(&id).copied())
+            .unwrap_or(0)
+    }
+
+    /// Count the attended small group meetings, by host, that belong to a student's small group.
+    pub fn count_attended<'a>(
+        &self,
+        group_id: Option<i64>,
+        attended_hosts: impl IntoIterator<Item = Option<&'a str>>,
+    ) -> i64 {
+        let mentors: &HashSet<String> = match group_id.and_then(|id| self.mentors.get(&id)) {
+            Some(mentors) => mentors,
+            None => return 0,
+        };
+
+        attended_hosts
+            .into_iter()
+            .filter(|host| host.map(|host| mentors.contains(host)).unwrap_or(false))
+            .count() as i64
+    }
+}
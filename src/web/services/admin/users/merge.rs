@@ -0,0 +1,118 @@
+//! Duplicate user detection and merging.
+//!
+//! The RCOS database has a view of users that may be the same person. Admins pick which of
+//! the two to keep, review everything that will move onto it, and then merge. The other user
+//! is deleted and the merge is recorded to the audit trail under both usernames.
+
+use crate::api::rcos::users::merge::{DuplicateUsers, MergePlan};
+use crate::audit::AuditEntry;
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use crate::web::services::user::profile::ProfileQuery;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Query, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+/// Register duplicate user services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(duplicates).service(preview).service(merge);
+}
+
+/// The two users in a merge, as a query string or a form.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct MergeUsers {
+    /// The username that stays.
+    keep: String,
+    /// The username merged into `keep` and then deleted.
+    remove: String,
+}
+
+/// Page listing possible duplicate users.
+#[get("/users/duplicates")]
+async fn duplicates(req: HttpRequest) -> Result<Template, TelescopeError> {
+    Template::new("admin/users/duplicates")
+        .field("duplicates", DuplicateUsers::get().await?)
+        .render_into_page(&req, "Duplicate Users")
+        .await
+}
+
+/// Page previewing a merge.
+#[get("/users/merge")]
+async fn preview(
+    req: HttpRequest,
+    Query(MergeUsers { keep, remove }): Query<MergeUsers>,
+) -> Result<Template, TelescopeError> {
+    let plan: MergePlan = MergePlan::get(keep, remove).await?;
+
+    Template::new("admin/users/merge")
+        .field("can_merge", plan.can_merge())
+        .field("plan", plan)
+        .render_into_page(&req, "Merge Users")
+        .await
+}
+
+/// Merge two users.
+#[post("/users/merge")]
+async fn merge(
+    auth: AuthenticationCookie,
+    Form(MergeUsers { keep, remove }): Form<MergeUsers>,
+) -> Result<HttpResponse, TelescopeError> {
+    let actor: String = auth.get_rcos_username_or_error().await?;
+
+    // Plan the merge again, in case anything changed since the preview.
+    let plan: MergePlan = MergePlan::get(keep.clone(), remove.clone()).await?;
+    if !plan.can_merge() {
+        return Err(TelescopeError::BadRequest {
+            header: "Cannot Merge Users".into(),
+            message: "Both users are enrolled in the same semester or submitted the same \
+                status update. Resolve these by hand before merging."
+                .into(),
+            show_status_code: false,
+        });
+    }
+
+    plan.execute().await?.ok_or(TelescopeError::ise(
+        "User merge did not delete the merged user.",
+    ))?;
+
+    let mut summary: String = format!(
+        "{} accounts, {} enrollments, {} meeting attendances, {} hosted meetings, and {} status \
+        update submissions moved; {} duplicate accounts and {} duplicate attendances deleted.",
+        plan.moved_accounts.len(),
+        plan.moved_enrollments.len(),
+        plan.moved_attendances,
+        plan.hosted_meetings,
+        plan.moved_submissions,
+        plan.dropped_accounts.len(),
+        plan.dropped_meeting_ids.len(),
+    );
+    if plan.moved_faculty_advisor {
+        summary.push_str(" Faculty advisor record moved.");
+    }
+
+    AuditEntry::new(
+        actor.as_str(),
+        "user_merge",
+        keep.as_str(),
+        format!("Merged {} into this user. {}", remove, summary),
+    )
+    .record()
+    .await;
+
+    AuditEntry::new(
+        actor,
+        "user_merge",
+        remove.as_str(),
+        format!("Merged into {} and deleted. {}", keep, summary),
+    )
+    .record()
+    .await;
+
+    let query: String = serde_urlencoded::to_string(ProfileQuery { username: keep })
+        .expect("Could not URL-encode username");
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/admin/users/edit?{}", query))
+        .finish())
+}
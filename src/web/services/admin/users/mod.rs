@@ -0,0 +1,246 @@
+//! Services for managing users from the admin panel.
+//!
+//! Admins can search users, change user roles (overriding the rules that apply when users
+//! change their own role), and mark users as coordinators. Every change is recorded to the
+//! audit trail. Admins can also merge duplicate users, and revoke all of a user's sessions when
+//! sessions are stored on the server.
+
+use crate::api::rcos::users::admin::{
+    AdminUserDetails, AdminUserSearch, SetCoordinator, SetUserRole, UnsetCoordinator,
+};
+use crate::api::rcos::users::UserRole;
+use crate::audit::{recent_entries, AuditEntry};
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use crate::web::services::user::profile::ProfileQuery;
+use crate::web::sessions::{session_store, SessionStore};
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Query, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+mod merge;
+
+/// The number of audit entries shown on a user's page.
+const USER_AUDIT_ENTRIES: usize = 20;
+
+/// The number of audit entries shown on the audit log page.
+const AUDIT_LOG_ENTRIES: usize = 200;
+
+/// Register user management services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(search)
+        .service(user_page)
+        .service(change_role)
+        .service(change_coordinator)
+        .service(revoke_sessions)
+        .service(audit_log)
+        .configure(merge::register);
+}
+
+/// Query parameters on the user search page.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct SearchQuery {
+    search: Option<String>,
+}
+
+/// Page to search users.
+#[get("/users")]
+async fn search(
+    req: HttpRequest,
+    Query(SearchQuery { search }): Query<SearchQuery>,
+) -> Result<Template, TelescopeError> {
+    // Ignore empty searches.
+    let search: Option<String> = search.filter(|s| !s.trim().is_empty());
+    let users = AdminUserSearch::get(search.clone()).await?;
+
+    Template::new("admin/users/index")
+        .field("search", search)
+        .field("users", users)
+        .render_into_page(&req, "Users")
+        .await
+}
+
+/// Page to manage a single user.
+#[get("/users/edit")]
+async fn user_page(
+    req: HttpRequest,
+    Query(ProfileQuery { username }): Query<ProfileQuery>,
+) -> Result<Template, TelescopeError> {
+    let details = AdminUserDetails::get(username.clone()).await?;
+    let user = details.user.ok_or(TelescopeError::resource_not_found(
+        "User Not Found",
+        "Could not find a user by this username.",
+    ))?;
+
+    // Mark each semester the user coordinated.
+    let semesters: Vec<serde_json::Value> = details
+        .semesters
+        .iter()
+        .map(|semester| {
+            let is_coordinator: bool = user
+                .enrollments
+                .iter()
+                .any(|e| e.semester_id == semester.semester_id && e.is_coordinator);
+            json!({
+                "semester_id": &semester.semester_id,
+                "title": &semester.title,
+                "is_coordinator": is_coordinator,
+            })
+        })
+        .collect();
+
+    // Count the user's active sessions if they are stored on the server.
+    let session_count: Option<usize> = match session_store() {
+        Some(store) => Some(store.list_for_user(username.as_str()).await?.len()),
+        None => None,
+    };
+
+    let title: String = format!("Manage {} {}", user.first_name, user.last_name);
+    Template::new("admin/users/edit")
+        .field("user", user)
+        .field("sessions_enabled", session_count.is_some())
+        .field("session_count", session_count)
+        .field("roles", UserRole::ALL_ROLES)
+        .field("semesters", semesters)
+        .field(
+            "audit",
+            recent_entries(USER_AUDIT_ENTRIES, Some(username.as_str())).await?,
+        )
+        .render_into_page(&req, title)
+        .await
+}
+
+/// Redirect back to a user's management page.
+fn back_to_user(username: &str) -> HttpResponse {
+    let query: String = serde_urlencoded::to_string(ProfileQuery {
+        username: username.to_string(),
+    })
+    .expect("Could not URL-encode username");
+
+    HttpResponse::Found()
+        .header(LOCATION, format!("/admin/users/edit?{}", query))
+        .finish()
+}
+
+/// Form to change a user's role.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct RoleForm {
+    username: String,
+    role: UserRole,
+}
+
+/// Change a user's role. Admins can set any role, regardless of
+/// [`UserRole::can_switch_to`].
+#[post("/users/role")]
+async fn change_role(
+    auth: AuthenticationCookie,
+    Form(RoleForm { username, role }): Form<RoleForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let actor: String = auth.get_rcos_username_or_error().await?;
+
+    let previous: UserRole = AdminUserDetails::get(username.clone())
+        .await?
+        .user
+        .map(|user| user.role)
+        .ok_or(TelescopeError::resource_not_found(
+            "User Not Found",
+            "Could not find a user by this username.",
+        ))?;
+
+    if previous != role {
+        SetUserRole::execute(username.clone(), role)
+            .await?
+            .ok_or(TelescopeError::ise("Role change did not return username."))?;
+
+        AuditEntry::new(
+            actor,
+            "role_change",
+            username.as_str(),
+            format!(
+                "Changed role from {} to {} (admin override).",
+                previous, role
+            ),
+        )
+        .record()
+        .await;
+    }
+
+    Ok(back_to_user(username.as_str()))
+}
+
+/// Form to mark or unmark a coordinator.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CoordinatorForm {
+    username: String,
+    semester_id: String,
+    is_coordinator: bool,
+}
+
+/// Mark or unmark a user as a coordinator for a semester.
+#[post("/users/coordinator")]
+async fn change_coordinator(
+    auth: AuthenticationCookie,
+    Form(CoordinatorForm {
+        username,
+        semester_id,
+        is_coordinator,
+    }): Form<CoordinatorForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let actor: String = auth.get_rcos_username_or_error().await?;
+
+    let description: String = if is_coordinator {
+        SetCoordinator::execute(username.clone(), semester_id.clone()).await?;
+        format!("Marked as coordinator for semester {}.", semester_id)
+    } else {
+        // Users who are not enrolled are not coordinators, so there is nothing to record.
+        let unmarked: Option<String> =
+            UnsetCoordinator::execute(username.clone(), semester_id.clone()).await?;
+        if unmarked.is_none() {
+            return Ok(back_to_user(username.as_str()));
+        }
+        format!("Unmarked as coordinator for semester {}.", semester_id)
+    };
+    AuditEntry::new(actor, "coordinator_change", username.as_str(), description)
+        .record()
+        .await;
+
+    Ok(back_to_user(username.as_str()))
+}
+
+/// Revoke all of a user's sessions, signing them out everywhere.
+#[post("/users/revoke_sessions")]
+async fn revoke_sessions(
+    auth: AuthenticationCookie,
+    Form(ProfileQuery { username }): Form<ProfileQuery>,
+) -> Result<HttpResponse, TelescopeError> {
+    let actor: String = auth.get_rcos_username_or_error().await?;
+
+    let store: &dyn SessionStore = session_store().ok_or(TelescopeError::BadRequest {
+        header: "Sessions Not Available".into(),
+        message: "Sessions are not stored on the server, so they cannot be revoked.".into(),
+        show_status_code: false,
+    })?;
+
+    let removed: usize = store.remove_all_for_user(username.as_str()).await?;
+    AuditEntry::new(
+        actor,
+        "revoke_sessions",
+        username.as_str(),
+        format!("Revoked {} sessions.", removed),
+    )
+    .record()
+    .await;
+
+    Ok(back_to_user(username.as_str()))
+}
+
+/// Page showing the most recent entries in the audit trail.
+#[get("/audit")]
+async fn audit_log(req: HttpRequest) -> Result<Template, TelescopeError> {
+    Template::new("admin/users/audit")
+        .field("entries", recent_entries(AUDIT_LOG_ENTRIES, None).await?)
+        .render_into_page(&req, "Audit Log")
+        .await
+}
@@ -0,0 +1,294 @@
+//! Workshop proposal review queue.
+//!
+//! Coordinators and admins review proposed workshops for ongoing semesters. The review page
+//! lists the meetings that overlap each of the proposed time slots. Approving a proposal
+//! creates a bonus session at the chosen slot, hosted by the proposer.
+
+use crate::api::rcos::meetings::authorization_for::{AuthorizationFor, UserMeetingAuthorization};
+use crate::api::rcos::meetings::creation::create::normalize_url;
+use crate::api::rcos::meetings::get::{meetings::MeetingsMeetings, Meetings};
+use crate::api::rcos::meetings::MeetingType;
+use crate::api::rcos::workshop_proposals::review::{
+    approve_workshop_proposal, workshop_proposal::WorkshopProposalProposal,
+    ApproveWorkshopProposal, ReviewWorkshopProposal, WorkshopProposal, WorkshopProposalQueue,
+};
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::admin::review::{
+    check_reviewer_enrolled, normalize_comments, ReviewDecision,
+};
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use serde_json::Value;
+
+/// The handlebars template for the workshop proposal review queue.
+const QUEUE_TEMPLATE: &'static str = "admin/workshop_proposals/queue";
+
+/// The handlebars template for reviewing a single workshop proposal.
+const REVIEW_TEMPLATE: &'static str = "admin/workshop_proposals/review";
+
+/// The default length of a workshop in minutes.
+const DEFAULT_DURATION_MINUTES: i64 = 60;
+
+/// The longest a workshop can be in minutes.
+const MAX_DURATION_MINUTES: i64 = 8 * 60;
+
+/// Register the workshop queue and scheduling pages under `/admin/workshop_proposals`.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(queue)
+        .service(review_page)
+        .service(submit_review);
+}
+
+/// The review queue for workshop proposals in ongoing semesters.
+#[get("")]
+async fn queue(req: HttpRequest) -> Result<Template, TelescopeError> {
+    let proposals = WorkshopProposalQueue::get().await?;
+
+    // Show pending proposals separately from reviewed ones.
+    let (reviewed, pending): (Vec<_>, Vec<_>) = proposals
+        .into_iter()
+        .partition(|proposal| proposal.is_approved.is_some());
+
+    return Template::new(QUEUE_TEMPLATE)
+        .field("pending", pending)
+        .field("reviewed", reviewed)
+        .render_into_page(&req, "Workshop Proposals")
+        .await;
+}
+
+/// Get a workshop proposal and check that the reviewer is enrolled in its semester.
+async fn get_proposal_for_review(
+    workshop_proposal_id: i64,
+    reviewer: String,
+) -> Result<WorkshopProposalProposal, TelescopeError> {
+    let proposal: WorkshopProposalProposal = WorkshopProposal::get(workshop_proposal_id, reviewer)
+        .await?
+        .ok_or(TelescopeError::resource_not_found(
+            "Proposal Not Found",
+            "Could not find a workshop proposal for this ID.",
+        ))?;
+
+    check_reviewer_enrolled(
+        !proposal.semester.reviewer_enrollment.is_empty(),
+        proposal.semester.title.as_str(),
+        "workshop proposals",
+    )?;
+
+    return Ok(proposal);
+}
+
+/// The proposed time slots of a workshop, in order of preference.
+fn time_slots(proposal: &WorkshopProposalProposal) -> [DateTime<Utc>; 3] {
+    [
+        proposal.first_choice_at,
+        proposal.second_choice_at,
+        proposal.third_choice_at,
+    ]
+}
+
+/// Page to review a workshop proposal.
+#[get("/{workshop_proposal_id}")]
+async fn review_page(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+    Path(workshop_proposal_id): Path<i64>,
+) -> Result<Template, TelescopeError> {
+    let reviewer: String = auth.get_rcos_username_or_error().await?;
+    let proposal = get_proposal_for_review(workshop_proposal_id, reviewer.clone()).await?;
+
+    // Get every meeting the reviewer can see around the proposed time slots.
+    let slots: [DateTime<Utc>; 3] = time_slots(&proposal);
+    let duration: Duration = Duration::minutes(DEFAULT_DURATION_MINUTES);
+    let earliest: DateTime<Utc> = *slots.iter().min().expect("There are three slots");
+    let latest: DateTime<Utc> = *slots.iter().max().expect("There are three slots");
+    let authorization: UserMeetingAuthorization = AuthorizationFor::get(Some(reviewer)).await?;
+    // Meetings are filtered by start time, so look back a day for long meetings.
+    let nearby: Vec<MeetingsMeetings> = Meetings::get(
+        earliest - Duration::days(1),
+        latest + duration,
+        authorization.can_view_drafts(),
+        authorization.viewable_types(),
+    )
+    .await?;
+
+    // List the meetings that overlap each slot.
+    let slots: Vec<Value> = slots
+        .iter()
+        .enumerate()
+        .map(|(index, start)| {
+            let end: DateTime<Utc> = *start + duration;
+            let conflicts: Vec<&MeetingsMeetings> = nearby
+                .iter()
+                .filter(|meeting| meeting.start_date_time < end && meeting.end_date_time > *start)
+                .collect();
+
+            json!({
+                "choice": index + 1,
+                "start": start,
+                "conflicts": conflicts,
+            })
+        })
+        .collect();
+
+    return Template::new(REVIEW_TEMPLATE)
+        .field("proposal", proposal)
+        .field("slots", slots)
+        .field("default_duration", DEFAULT_DURATION_MINUTES)
+        .field("max_duration", MAX_DURATION_MINUTES)
+        .render_into_page(&req, "Review Workshop Proposal")
+        .await;
+}
+
+/// Form submitted to review a workshop proposal. Approvals also schedule the workshop.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct WorkshopReviewForm {
+    /// The reviewer's decision.
+    decision: ReviewDecision,
+    /// Comments for the proposer.
+    #[serde(default)]
+    reviewer_comments: String,
+    /// The chosen time slot (1, 2, or 3). Only used for approvals.
+    #[serde(default)]
+    choice: Option<usize>,
+    /// The length of the workshop in minutes. Only used for approvals.
+    #[serde(default)]
+    duration_minutes: Option<i64>,
+    /// Where the workshop will be held.
+    #[serde(default)]
+    location: Option<String>,
+    /// Checkbox for remote workshops.
+    #[serde(default)]
+    is_remote: Option<bool>,
+    /// The link to join a remote workshop.
+    #[serde(default)]
+    meeting_url: Option<String>,
+}
+
+/// Endpoint to submit a workshop proposal review.
+#[post("/{workshop_proposal_id}")]
+async fn submit_review(
+    auth: AuthenticationCookie,
+    Path(workshop_proposal_id): Path<i64>,
+    Form(form): Form<WorkshopReviewForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let reviewer: String = auth.get_rcos_username_or_error().await?;
+    let proposal = get_proposal_for_review(workshop_proposal_id, reviewer.clone()).await?;
+
+    // Approved proposals are final, so that workshops are not scheduled twice.
+    if proposal.is_approved == Some(true) {
+        return Err(TelescopeError::BadRequest {
+            header: "Proposal Already Approved".into(),
+            message: "This workshop proposal has already been approved.".into(),
+            show_status_code: false,
+        });
+    }
+
+    let reviewer_comments: Option<String> = normalize_comments(form.reviewer_comments);
+
+    match form.decision {
+        ReviewDecision::Approve => {
+            // Get the chosen slot.
+            let start: DateTime<Utc> = form
+                .choice
+                .filter(|choice| (1..=3).contains(choice))
+                .map(|choice| time_slots(&proposal)[choice - 1])
+                .ok_or(TelescopeError::BadRequest {
+                    header: "No Time Slot Selected".into(),
+                    message: "Please choose one of the proposed time slots.".into(),
+                    show_status_code: false,
+                })?;
+
+            let duration_minutes: i64 = form.duration_minutes.unwrap_or(DEFAULT_DURATION_MINUTES);
+            if duration_minutes <= 0 || duration_minutes > MAX_DURATION_MINUTES {
+                return Err(TelescopeError::BadRequest {
+                    header: "Invalid Duration".into(),
+                    message: format!(
+                        "Workshops must be between 1 and {} minutes long.",
+                        MAX_DURATION_MINUTES
+                    ),
+                    show_status_code: false,
+                });
+            }
+
+            let end: DateTime<Utc> = start
+                .checked_add_signed(Duration::minutes(duration_minutes))
+                .ok_or(TelescopeError::BadRequest {
+                    header: "Invalid Duration".into(),
+                    message: "The workshop would end at an invalid time.".into(),
+                    show_status_code: false,
+                })?;
+
+            // Like other meetings, workshops have to be inside their semester.
+            let start_date: NaiveDate = start.with_timezone(&Local).naive_local().date();
+            let end_date: NaiveDate = end.with_timezone(&Local).naive_local().date();
+            if start_date < proposal.semester.start_date || end_date > proposal.semester.end_date {
+                return Err(TelescopeError::BadRequest {
+                    header: "Time Slot Outside Semester".into(),
+                    message: format!(
+                        "The chosen time slot is outside of {}.",
+                        proposal.semester.title
+                    ),
+                    show_status_code: false,
+                });
+            }
+
+            let location: Option<String> = form
+                .location
+                .map(|location| location.trim().to_string())
+                .filter(|location| !location.is_empty());
+
+            // Schedule the workshop as a bonus session hosted by the proposer, in the same
+            // mutation as the approval.
+            let meeting_id: i64 =
+                ApproveWorkshopProposal::execute(approve_workshop_proposal::Variables {
+                    workshop_proposal_id,
+                    reviewer_username: reviewer,
+                    reviewer_comments,
+                    host_username: proposal.username.clone(),
+                    title: proposal.title.clone(),
+                    start,
+                    end,
+                    description: proposal.topic.clone(),
+                    is_remote: form.is_remote.unwrap_or(false),
+                    location,
+                    meeting_url: normalize_url(form.meeting_url),
+                    semester_id: proposal.semester_id.clone(),
+                    kind: MeetingType::BonusSession,
+                })
+                .await?
+                .ok_or(TelescopeError::ise(
+                    "Workshop approval did not return a meeting ID.",
+                ))?;
+
+            // Send the reviewer to the new meeting.
+            Ok(HttpResponse::Found()
+                .header(LOCATION, format!("/meeting/{}", meeting_id))
+                .finish())
+        }
+
+        ReviewDecision::Reject => {
+            let rejected: bool = ReviewWorkshopProposal::execute(
+                workshop_proposal_id,
+                false,
+                reviewer,
+                reviewer_comments,
+            )
+            .await?;
+
+            if !rejected {
+                return Err(TelescopeError::ise(
+                    "Workshop rejection did not update the proposal.",
+                ));
+            }
+
+            Ok(HttpResponse::Found()
+                .header(LOCATION, "/admin/workshop_proposals")
+                .finish())
+        }
+    }
+}
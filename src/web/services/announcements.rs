@@ -0,0 +1,117 @@
+//! Atom feed of RCOS announcements.
+
+use crate::api::rcos::announcements::{announcements::AnnouncementsAnnouncements, Announcements};
+use crate::env::global_config;
+use crate::error::TelescopeError;
+use crate::templates::helpers::render_markdown;
+use actix_web::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use actix_web::web::ServiceConfig;
+use actix_web::HttpResponse;
+use chrono::{DateTime, Utc};
+
+/// The number of announcements included in the feed.
+const FEED_LENGTH: i64 = 50;
+
+/// Register announcement services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(feed);
+}
+
+/// Escape text for use in XML content and attribute values.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Build an Atom document (RFC 4287) with an entry for each announcement.
+fn make_feed(announcements: &[AnnouncementsAnnouncements]) -> String {
+    let base_url: &str = global_config()
+        .discord_config
+        .telescope_url
+        .trim_end_matches('/');
+
+    // The feed was last updated when the newest announcement was made.
+    let updated: DateTime<Utc> = announcements
+        .first()
+        .map(|announcement| announcement.created_at)
+        .unwrap_or_else(Utc::now);
+
+    let mut feed = String::new();
+    feed.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    feed.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    feed.push_str("  <title>RCOS Announcements</title>\n");
+    feed.push_str(format!("  <id>{}/announcements.atom</id>\n", escape_xml(base_url)).as_str());
+    feed.push_str(
+        format!(
+            "  <link rel=\"self\" href=\"{}/announcements.atom\"/>\n",
+            escape_xml(base_url)
+        )
+        .as_str(),
+    );
+    feed.push_str(format!("  <link href=\"{}/\"/>\n", escape_xml(base_url)).as_str());
+    feed.push_str(format!("  <updated>{}</updated>\n", updated.to_rfc3339()).as_str());
+    feed.push_str("  <author><name>RCOS</name></author>\n");
+
+    for announcement in announcements {
+        let link: String = format!(
+            "{}/#announcement-{}",
+            base_url, announcement.announcement_id
+        );
+
+        feed.push_str("  <entry>\n");
+        feed.push_str(format!("    <id>{}</id>\n", escape_xml(link.as_str())).as_str());
+        feed.push_str(
+            format!(
+                "    <title>{}</title>\n",
+                escape_xml(announcement.title.as_str())
+            )
+            .as_str(),
+        );
+        feed.push_str(format!("    <link href=\"{}\"/>\n", escape_xml(link.as_str())).as_str());
+        feed.push_str(
+            format!(
+                "    <published>{}</published>\n",
+                announcement.created_at.to_rfc3339()
+            )
+            .as_str(),
+        );
+        feed.push_str(
+            format!(
+                "    <updated>{}</updated>\n",
+                announcement.created_at.to_rfc3339()
+            )
+            .as_str(),
+        );
+        feed.push_str(
+            format!(
+                "    <category term=\"{}\"/>\n",
+                escape_xml(announcement.semester.title.as_str())
+            )
+            .as_str(),
+        );
+        feed.push_str(
+            format!(
+                "    <content type=\"html\">{}</content>\n",
+                escape_xml(render_markdown(announcement.body_markdown.as_str()).as_str())
+            )
+            .as_str(),
+        );
+        feed.push_str("  </entry>\n");
+    }
+
+    feed.push_str("</feed>\n");
+    return feed;
+}
+
+/// The Atom feed of recent announcements. This includes expired announcements.
+#[get("/announcements.atom")]
+async fn feed() -> Result<HttpResponse, TelescopeError> {
+    let announcements: Vec<AnnouncementsAnnouncements> = Announcements::get(FEED_LENGTH).await?;
+    return Ok(HttpResponse::Ok()
+        .header(CONTENT_TYPE, "application/atom+xml;charset=UTF-8")
+        .header(CACHE_CONTROL, "no-cache")
+        .body(make_feed(announcements.as_slice())));
+}
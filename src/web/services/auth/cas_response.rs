@@ -0,0 +1,374 @@
+//! Parsing of CAS `serviceValidate` responses.
+//!
+//! See the [CAS protocol specification](https://apereo.github.io/cas/6.6.x/protocol/CAS-Protocol-Specification.html)
+//! for the response format. CAS 2.0 servers only return the authenticated user, while CAS 3.0
+//! servers can also release attributes about them, which are used to pre-fill registration.
+
+use crate::error::TelescopeError;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+
+/// The XML namespace of CAS responses.
+const CAS_NAMESPACE: &'static str = "http://www.yale.edu/tp/cas";
+
+/// Attribute names that CAS servers commonly release the user's first name under.
+const FIRST_NAME_ATTRIBUTES: [&'static str; 4] =
+    ["firstName", "givenName", "given_name", "first_name"];
+
+/// Attribute names that CAS servers commonly release the user's last name under.
+const LAST_NAME_ATTRIBUTES: [&'static str; 5] =
+    ["lastName", "sn", "surname", "family_name", "last_name"];
+
+/// Attribute names that CAS servers commonly release the user's email under.
+const EMAIL_ATTRIBUTES: [&'static str; 3] = ["mail", "email", "emailAddress"];
+
+/// The failure codes defined by the CAS protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CasFailureCode {
+    /// Not all of the required parameters were present.
+    InvalidRequest,
+    /// The ticket does not meet the ticket specification.
+    InvalidTicketSpec,
+    /// The service is not authorized to perform proxy authentication.
+    UnauthorizedServiceProxy,
+    /// The proxy callback is invalid.
+    InvalidProxyCallback,
+    /// The ticket was not recognized, was already used, or has expired.
+    InvalidTicket,
+    /// The ticket was not issued for this service.
+    InvalidService,
+    /// An internal error occurred in the CAS server.
+    InternalError,
+    /// A code not defined by the protocol.
+    Other(String),
+}
+
+impl CasFailureCode {
+    /// Parse a failure code from the `code` attribute of an authentication failure.
+    fn parse(code: &str) -> Self {
+        match code {
+            "INVALID_REQUEST" => CasFailureCode::InvalidRequest,
+            "INVALID_TICKET_SPEC" => CasFailureCode::InvalidTicketSpec,
+            "UNAUTHORIZED_SERVICE_PROXY" => CasFailureCode::UnauthorizedServiceProxy,
+            "INVALID_PROXY_CALLBACK" => CasFailureCode::InvalidProxyCallback,
+            "INVALID_TICKET" => CasFailureCode::InvalidTicket,
+            "INVALID_SERVICE" => CasFailureCode::InvalidService,
+            "INTERNAL_ERROR" => CasFailureCode::InternalError,
+            other => CasFailureCode::Other(other.to_string()),
+        }
+    }
+
+    /// The code as it appears in CAS responses.
+    fn as_str(&self) -> &str {
+        match self {
+            CasFailureCode::InvalidRequest => "INVALID_REQUEST",
+            CasFailureCode::InvalidTicketSpec => "INVALID_TICKET_SPEC",
+            CasFailureCode::UnauthorizedServiceProxy => "UNAUTHORIZED_SERVICE_PROXY",
+            CasFailureCode::InvalidProxyCallback => "INVALID_PROXY_CALLBACK",
+            CasFailureCode::InvalidTicket => "INVALID_TICKET",
+            CasFailureCode::InvalidService => "INVALID_SERVICE",
+            CasFailureCode::InternalError => "INTERNAL_ERROR",
+            CasFailureCode::Other(code) => code.as_str(),
+        }
+    }
+}
+
+/// A CAS authentication failure.
+#[derive(Clone, Debug)]
+pub struct CasFailure {
+    /// The failure code.
+    pub code: CasFailureCode,
+    /// The human readable description sent by the CAS server.
+    pub description: String,
+}
+
+impl CasFailure {
+    /// Convert this failure into the error shown to the user. Rejected tickets are the user's
+    /// to retry, while rejected requests mean Telescope or the CAS server is misconfigured.
+    pub fn into_error(self) -> TelescopeError {
+        let code: String = self.code.as_str().to_string();
+        match self.code {
+            CasFailureCode::InvalidTicket
+            | CasFailureCode::InvalidTicketSpec
+            | CasFailureCode::InvalidService => TelescopeError::CasTicketRejected {
+                code,
+                description: self.description,
+            },
+
+            CasFailureCode::InternalError => {
+                TelescopeError::RpiCasError(format!("{}: {}", code, self.description))
+            }
+
+            _ => TelescopeError::CasRequestRejected {
+                code,
+                description: self.description,
+            },
+        }
+    }
+}
+
+/// The profile of a user released by the CAS server as attributes.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CasProfile {
+    /// The user's first name.
+    pub first_name: Option<String>,
+    /// The user's last name.
+    pub last_name: Option<String>,
+    /// The user's email.
+    pub email: Option<String>,
+}
+
+impl CasProfile {
+    /// Pick the profile fields out of the released attributes.
+    fn from_attributes(attributes: &HashMap<String, Vec<String>>) -> Option<Self> {
+        // Get the first value of the first attribute that was released under any of the names.
+        let first = |names: &[&str]| -> Option<String> {
+            names
+                .iter()
+                .filter_map(|name| attributes.get(*name))
+                .flat_map(|values| values.iter())
+                .map(|value| value.trim())
+                .find(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let profile = CasProfile {
+            first_name: first(&FIRST_NAME_ATTRIBUTES),
+            last_name: first(&LAST_NAME_ATTRIBUTES),
+            email: first(&EMAIL_ATTRIBUTES),
+        };
+
+        // Don't keep an empty profile.
+        if profile.first_name.is_none() && profile.last_name.is_none() && profile.email.is_none() {
+            return None;
+        }
+        return Some(profile);
+    }
+}
+
+/// A successful CAS authentication.
+#[derive(Clone, Debug)]
+pub struct CasSuccess {
+    /// The authenticated user.
+    pub user: String,
+    /// The attributes released about the user, if any. Attributes can have multiple values.
+    pub attributes: HashMap<String, Vec<String>>,
+    /// The proxies the ticket passed through, from the most recent. This is empty when the user
+    /// authenticated with Telescope directly.
+    pub proxies: Vec<String>,
+}
+
+impl CasSuccess {
+    /// Get the user's profile from the released attributes, if there are any.
+    pub fn profile(&self) -> Option<CasProfile> {
+        CasProfile::from_attributes(&self.attributes)
+    }
+}
+
+/// A parsed CAS `serviceValidate` response.
+#[derive(Clone, Debug)]
+pub enum CasResponse {
+    /// The ticket was validated.
+    Success(CasSuccess),
+    /// The ticket was not validated.
+    Failure(CasFailure),
+}
+
+/// Check if a node is a CAS element with the given local name.
+fn is_cas_element(node: &Node, name: &str) -> bool {
+    node.is_element()
+        && node.tag_name().name() == name
+        && node.tag_name().namespace() == Some(CAS_NAMESPACE)
+}
+
+/// Find the first CAS child element of a node with the given local name.
+fn cas_child<'a, 'input>(node: &Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| is_cas_element(child, name))
+}
+
+/// Get the trimmed text of a node.
+fn text_of(node: &Node) -> String {
+    node.text().unwrap_or("").trim().to_string()
+}
+
+/// Parse the attributes released in a successful response. Attributes are usually child
+/// elements of `<cas:attributes>` named after the attribute, but some servers send
+/// `<cas:attribute name="..." value="..."/>` elements instead. Both are supported.
+fn parse_attributes(success: &Node) -> HashMap<String, Vec<String>> {
+    let mut attributes: HashMap<String, Vec<String>> = HashMap::new();
+
+    let elements = cas_child(success, "attributes")
+        .into_iter()
+        .flat_map(|attributes| attributes.children())
+        .filter(|node| node.is_element());
+
+    for element in elements {
+        let (name, value) = match (element.attribute("name"), element.attribute("value")) {
+            (Some(name), Some(value)) if element.tag_name().name() == "attribute" => {
+                (name.to_string(), value.trim().to_string())
+            }
+            _ => (element.tag_name().name().to_string(), text_of(&element)),
+        };
+
+        attributes.entry(name).or_default().push(value);
+    }
+
+    return attributes;
+}
+
+/// Parse the XML of a CAS `serviceValidate` response. Error if the response is not valid XML
+/// or is not a CAS service response.
+pub fn parse(xml: &str) -> Result<CasResponse, TelescopeError> {
+    let malformed = |reason: String| {
+        TelescopeError::RpiCasError(format!(
+            "Malformed CAS response ({}). Response xml: {}",
+            reason, xml
+        ))
+    };
+
+    let document: Document = Document::parse(xml).map_err(|e| malformed(e.to_string()))?;
+    let root: Node = document.root_element();
+    if !is_cas_element(&root, "serviceResponse") {
+        return Err(malformed("no service response".into()));
+    }
+
+    if let Some(failure) = cas_child(&root, "authenticationFailure") {
+        return Ok(CasResponse::Failure(CasFailure {
+            code: CasFailureCode::parse(failure.attribute("code").unwrap_or("").trim()),
+            description: text_of(&failure),
+        }));
+    }
+
+    let success: Node = cas_child(&root, "authenticationSuccess")
+        .ok_or_else(|| malformed("no authentication success or failure".into()))?;
+
+    let user: String = cas_child(&success, "user")
+        .map(|user| text_of(&user))
+        .filter(|user| !user.is_empty())
+        .ok_or_else(|| malformed("no user".into()))?;
+
+    let proxies: Vec<String> = cas_child(&success, "proxies")
+        .into_iter()
+        .flat_map(|proxies| proxies.children())
+        .filter(|node| is_cas_element(node, "proxy"))
+        .map(|proxy| text_of(&proxy))
+        .collect();
+
+    return Ok(CasResponse::Success(CasSuccess {
+        user,
+        attributes: parse_attributes(&success),
+        proxies,
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Load an XML response from the CAS fixtures directory.
+    macro_rules! fixture {
+        ($name:literal) => {
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/cas/",
+                $name
+            ))
+        };
+    }
+
+    fn success(xml: &str) -> CasSuccess {
+        match parse(xml).expect("Could not parse CAS response") {
+            CasResponse::Success(success) => success,
+            CasResponse::Failure(failure) => panic!("Unexpected failure: {:?}", failure),
+        }
+    }
+
+    fn failure(xml: &str) -> CasFailure {
+        match parse(xml).expect("Could not parse CAS response") {
+            CasResponse::Failure(failure) => failure,
+            CasResponse::Success(success) => panic!("Unexpected success: {:?}", success),
+        }
+    }
+
+    #[test]
+    fn parses_cas2_success() {
+        let success = success(fixture!("cas2_success.xml"));
+        assert_eq!(success.user, "doej");
+        assert!(success.attributes.is_empty());
+        assert!(success.proxies.is_empty());
+        assert!(success.profile().is_none());
+    }
+
+    #[test]
+    fn parses_cas3_attributes() {
+        let success = success(fixture!("cas3_attributes.xml"));
+        assert_eq!(success.user, "doej");
+        assert_eq!(
+            success.attributes.get("memberOf"),
+            Some(&vec!["students".to_string(), "rcos".to_string()])
+        );
+
+        // Blank values are skipped when picking the profile.
+        let profile = success.profile().expect("No profile");
+        assert_eq!(profile.first_name.as_deref(), Some("Jane"));
+        assert_eq!(profile.last_name.as_deref(), Some("Doe"));
+        assert_eq!(profile.email.as_deref(), Some("doej@rpi.edu"));
+    }
+
+    #[test]
+    fn parses_name_value_attributes() {
+        let profile = success(fixture!("cas3_name_value_attributes.xml"))
+            .profile()
+            .expect("No profile");
+        assert_eq!(profile.first_name.as_deref(), Some("Jane"));
+        assert_eq!(profile.last_name.as_deref(), Some("Doe"));
+        assert_eq!(profile.email.as_deref(), Some("doej@rpi.edu"));
+    }
+
+    #[test]
+    fn parses_proxies() {
+        let success = success(fixture!("cas3_proxies.xml"));
+        assert_eq!(success.user, "doej");
+        assert_eq!(
+            success.proxies,
+            vec![
+                "https://proxy2.example.edu/pgtUrl".to_string(),
+                "https://proxy1.example.edu/pgtUrl".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_failure() {
+        let failure = failure(fixture!("failure_invalid_ticket.xml"));
+        assert_eq!(failure.code, CasFailureCode::InvalidTicket);
+        assert_eq!(
+            failure.description,
+            "Ticket ST-1856339-aA5Yuvrxzpv8Tau1cYQ7 not recognized"
+        );
+        assert!(matches!(
+            failure.into_error(),
+            TelescopeError::CasTicketRejected { .. }
+        ));
+    }
+
+    #[test]
+    fn parses_unknown_failure_code() {
+        let failure = failure(fixture!("failure_unknown_code.xml"));
+        assert_eq!(
+            failure.code,
+            CasFailureCode::Other("SERVICE_DISABLED".to_string())
+        );
+        assert!(matches!(
+            failure.into_error(),
+            TelescopeError::CasRequestRejected { .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_responses() {
+        assert!(parse("not xml").is_err());
+        assert!(parse(fixture!("wrong_namespace.xml")).is_err());
+        assert!(parse(fixture!("missing_user.xml")).is_err());
+    }
+}
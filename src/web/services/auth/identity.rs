@@ -2,14 +2,19 @@
 
 use crate::api::rcos::users::accounts::lookup::AccountLookup;
 use crate::api::rcos::users::UserAccountType;
+use crate::env::global_config;
 use crate::error::TelescopeError;
+use crate::web::csrf::extract_ip_addr;
 use crate::web::services::auth::oauth2_providers::{
-    discord::DiscordIdentity, github::GitHubIdentity,
+    bitbucket::BitBucketIdentity, discord::DiscordIdentity, github::GitHubIdentity,
+    gitlab::GitLabIdentity,
 };
 use crate::web::services::auth::rpi_cas::RpiCasIdentity;
+use crate::web::sessions::{session_store, Session, SessionStore};
 use actix_identity::Identity as ActixIdentity;
 use actix_web::dev::{Payload, PayloadStream};
 use actix_web::{FromRequest, HttpRequest};
+use chrono::Utc;
 use futures::future::{ready, LocalBoxFuture, Ready};
 use serde::Serialize;
 
@@ -24,17 +29,28 @@ pub enum RootIdentity {
 
     /// RCS ID.
     RpiCas(RpiCasIdentity),
+
+    /// GitLab access and refresh tokens.
+    GitLab(GitLabIdentity),
+
+    /// BitBucket access and refresh tokens.
+    BitBucket(BitBucketIdentity),
 }
 
 impl RootIdentity {
     /// Refresh this identity token if necessary.
     pub async fn refresh(self) -> Result<Self, TelescopeError> {
-        // If this is a discord-based identity, refresh it and construct the refreshed root identity.
-        if let RootIdentity::Discord(discord) = self {
-            return discord.refresh().await.map(RootIdentity::Discord);
+        // If this is an identity with a refresh token, refresh it and construct the refreshed
+        // root identity.
+        match self {
+            RootIdentity::Discord(discord) => discord.refresh().await.map(RootIdentity::Discord),
+            RootIdentity::GitLab(gitlab) => gitlab.refresh().await.map(RootIdentity::GitLab),
+            RootIdentity::BitBucket(bitbucket) => {
+                bitbucket.refresh().await.map(RootIdentity::BitBucket)
+            }
+            // Otherwise no-op.
+            _ => Ok(self),
         }
-        // Otherwise no-op.
-        return Ok(self);
     }
 
     /// Get the user account variant representing the authenticated platform.
@@ -43,6 +59,8 @@ impl RootIdentity {
             RootIdentity::GitHub(_) => UserAccountType::GitHub,
             RootIdentity::Discord(_) => UserAccountType::Discord,
             RootIdentity::RpiCas(_) => UserAccountType::Rpi,
+            RootIdentity::GitLab(_) => UserAccountType::GitLab,
+            RootIdentity::BitBucket(_) => UserAccountType::BitBucket,
         }
     }
 
@@ -51,7 +69,9 @@ impl RootIdentity {
         match self {
             RootIdentity::GitHub(gh) => gh.get_user_id().await,
             RootIdentity::Discord(d) => d.get_user_id().await,
-            RootIdentity::RpiCas(RpiCasIdentity { rcs_id }) => Ok(rcs_id.clone()),
+            RootIdentity::RpiCas(RpiCasIdentity { rcs_id, .. }) => Ok(rcs_id.clone()),
+            RootIdentity::GitLab(gl) => gl.get_user_id().await,
+            RootIdentity::BitBucket(bb) => bb.get_user_id().await,
         }
     }
 
@@ -62,6 +82,8 @@ impl RootIdentity {
             RootIdentity::GitHub(gh) => gh.get_rcos_username().await,
             RootIdentity::Discord(d) => d.get_rcos_username().await,
             RootIdentity::RpiCas(rpi) => rpi.get_rcos_username().await,
+            RootIdentity::GitLab(gl) => gl.get_rcos_username().await,
+            RootIdentity::BitBucket(bb) => bb.get_rcos_username().await,
         }
     }
 
@@ -78,6 +100,8 @@ impl RootIdentity {
             root: self,
             github: None,
             discord: None,
+            gitlab: None,
+            bitbucket: None,
         }
     }
 }
@@ -93,6 +117,15 @@ pub struct AuthenticationCookie {
 
     /// An optional Discord access and refresh token.
     pub discord: Option<DiscordIdentity>,
+
+    /// An optional GitLab access and refresh token. Defaulted so that cookies from before
+    /// GitLab support still deserialize.
+    #[serde(default)]
+    pub gitlab: Option<GitLabIdentity>,
+
+    /// An optional BitBucket access and refresh token. See [`Self::gitlab`].
+    #[serde(default)]
+    pub bitbucket: Option<BitBucketIdentity>,
     // We don't store an optional RCS ID because it can be queried from the
     // database.
 }
@@ -106,14 +139,24 @@ impl AuthenticationCookie {
 
         // When there is an additional discord identity.
         if let Some(discord_identity) = self.discord {
-            // Refresh the discord identity
-            let refreshed = discord_identity.refresh().await?;
-            // Store back and return self.
-            self.discord = Some(refreshed);
-            return Ok(self);
+            // Refresh the discord identity and store it back.
+            self.discord = Some(discord_identity.refresh().await?);
+        }
+
+        // Same for GitLab and BitBucket. These can be turned off after a user links them, in
+        // which case the linked identity is dropped rather than failing every request.
+        if let Some(gitlab_identity) = self.gitlab.take() {
+            if global_config().gitlab_credentials.is_some() {
+                self.gitlab = Some(gitlab_identity.refresh().await?);
+            }
+        }
+
+        if let Some(bitbucket_identity) = self.bitbucket.take() {
+            if global_config().bitbucket_credentials.is_some() {
+                self.bitbucket = Some(bitbucket_identity.refresh().await?);
+            }
         }
 
-        // Otherwise return self
         return Ok(self);
     }
 
@@ -176,6 +219,28 @@ impl AuthenticationCookie {
         return false;
     }
 
+    /// Try to replace the root identity with the GitLab token.
+    /// Return true on success.
+    /// See [`Self::replace_root_with_github`].
+    fn replace_root_with_gitlab(&mut self) -> bool {
+        if self.gitlab.is_some() {
+            self.root = RootIdentity::GitLab(self.gitlab.take().unwrap());
+            return true;
+        }
+        return false;
+    }
+
+    /// Try to replace the root identity with the BitBucket token.
+    /// Return true on success.
+    /// See [`Self::replace_root_with_github`].
+    fn replace_root_with_bitbucket(&mut self) -> bool {
+        if self.bitbucket.is_some() {
+            self.root = RootIdentity::BitBucket(self.bitbucket.take().unwrap());
+            return true;
+        }
+        return false;
+    }
+
     /// Try to get the user's RCS id from the RCOS database and replace the root
     /// identity with it.
     /// Return true on success.
@@ -187,7 +252,10 @@ impl AuthenticationCookie {
             AccountLookup::send(rcos_username, UserAccountType::Rpi).await?;
         // If there is an RCS id, replace the root.
         if let Some(rcs_id) = rcs_id {
-            self.root = RootIdentity::RpiCas(RpiCasIdentity { rcs_id });
+            self.root = RootIdentity::RpiCas(RpiCasIdentity {
+                rcs_id,
+                profile: None,
+            });
             return Ok(true);
         }
 
@@ -202,23 +270,24 @@ impl AuthenticationCookie {
     ///
     /// If the root can successfully be replaced, return `true`.
     async fn remove_root(&mut self) -> Result<bool, TelescopeError> {
-        match self.root {
-            // When the root identity is an RCS ID.
-            RootIdentity::RpiCas(_) => {
-                // Try with GitHub, then discord
-                Ok(self.replace_root_with_github() || self.replace_root_with_discord())
-            }
-            // When root identity is GitHub auth
-            RootIdentity::GitHub(_) => {
-                // Try with discord then RCS id.
-                Ok(self.replace_root_with_discord() || self.replace_root_with_rpi_cas().await?)
-            }
-            // When the root identity is Discord Auth
-            RootIdentity::Discord(_) => {
-                // Try with GitHub then with RPI CAS
-                Ok(self.replace_root_with_github() || self.replace_root_with_rpi_cas().await?)
-            }
+        let root: UserAccountType = self.root.get_user_account_type();
+
+        // Try each secondary identity of a different type than the root in turn:
+        // GitHub, Discord, GitLab, then BitBucket.
+        let replaced: bool = (root != UserAccountType::GitHub && self.replace_root_with_github())
+            || (root != UserAccountType::Discord && self.replace_root_with_discord())
+            || (root != UserAccountType::GitLab && self.replace_root_with_gitlab())
+            || (root != UserAccountType::BitBucket && self.replace_root_with_bitbucket());
+
+        if replaced {
+            return Ok(true);
+        }
+
+        // Fall back to the RCS ID, unless that is the root being removed.
+        if root == UserAccountType::Rpi {
+            return Ok(false);
         }
+        return self.replace_root_with_rpi_cas().await;
     }
 
     /// Try to remove a specific platform's identity and authentication from this cookie.
@@ -236,6 +305,8 @@ impl AuthenticationCookie {
         match platform {
             UserAccountType::GitHub => self.github = None,
             UserAccountType::Discord => self.discord = None,
+            UserAccountType::GitLab => self.gitlab = None,
+            UserAccountType::BitBucket => self.bitbucket = None,
             // If it isn't held in the authentication cookie this is a no-op
             _ => {}
         }
@@ -250,6 +321,9 @@ pub struct Identity {
     /// The actix identity of this request. This handles cookie and
     /// security stuff.
     inner: ActixIdentity,
+
+    /// The request, used to record where sessions are used from.
+    request: HttpRequest,
 }
 
 impl FromRequest for Identity {
@@ -272,7 +346,10 @@ impl FromRequest for Identity {
                     ))
                 })
                 // Wrap the extracted identity.
-                .map(|inner| Self { inner }),
+                .map(|inner| Self {
+                    inner,
+                    request: req.clone(),
+                }),
         )
     }
 }
@@ -300,25 +377,140 @@ impl FromRequest for AuthenticationCookie {
 }
 
 impl Identity {
-    /// Forget the user's identity if it exists.
-    pub fn forget(&self) {
+    /// Get the ID of the session in the identity cookie, if it holds one instead of a
+    /// serialized authentication cookie.
+    pub fn session_id(&self) -> Option<String> {
+        self.inner.identity().filter(|id| !id.starts_with('{'))
+    }
+
+    /// Forget the user's identity if it exists. If it is stored in a session, remove the
+    /// session too.
+    pub async fn forget(&self) {
+        if let (Some(store), Some(id)) = (session_store(), self.session_id()) {
+            if let Err(e) = store.remove(id.as_str()).await {
+                warn!("Could not remove session. Error: {}", e);
+            }
+        }
+
         self.inner.forget()
     }
 
-    /// Save an identity object to the client's cookies.
-    pub fn save(&self, identity: &AuthenticationCookie) {
+    /// Save an identity object to the client's cookies, or to the user's session if there
+    /// is a session store.
+    pub async fn save(&self, identity: &AuthenticationCookie) -> Result<(), TelescopeError> {
+        if let Some(store) = session_store() {
+            // Look up the user this identity belongs to, since it may have changed.
+            let username: Option<String> = identity.get_rcos_username().await?;
+
+            // Signing in gets a new session ID, so that a session ID known before signing in
+            // is never authenticated. The old session is removed.
+            if let Some(id) = self.session_id() {
+                if let Err(e) = store.remove(id.as_str()).await {
+                    warn!("Could not remove session. Error: {}", e);
+                }
+            }
+            return self.save_session(store, None, identity, username).await;
+        }
+
         // Serialize the cookie to JSON first. This serialization should not fail.
         let cookie: String =
             serde_json::to_string(identity).expect("Could not serialize identity cookie");
 
         // Remember cookie.
-        self.inner.remember(cookie)
+        self.inner.remember(cookie);
+        return Ok(());
+    }
+
+    /// Save an identity object to a session and put the session ID in the identity cookie.
+    /// The existing session, if any, is updated unless it belongs to a different user.
+    async fn save_session(
+        &self,
+        store: &dyn SessionStore,
+        existing: Option<Session>,
+        identity: &AuthenticationCookie,
+        username: Option<String>,
+    ) -> Result<(), TelescopeError> {
+        let ip_addr: Option<String> = extract_ip_addr(&self.request).ok();
+
+        let session: Session = match existing {
+            Some(mut session) if session.username.is_none() || session.username == username => {
+                session.identity = identity.clone();
+                session.username = username;
+                session.last_seen = Utc::now();
+                session.ip_addr = ip_addr;
+                session
+            }
+
+            _ => Session::new(identity.clone(), username, ip_addr),
+        };
+
+        let id: String = session.id.clone();
+        store.put(session).await?;
+        self.inner.remember(id);
+        return Ok(());
+    }
+
+    /// Get the identity held in a session. Refresh it if necessary.
+    async fn session_identity(
+        &self,
+        store: &dyn SessionStore,
+        id: String,
+    ) -> Option<AuthenticationCookie> {
+        let session: Session = match store.get(id.as_str()).await {
+            Ok(Some(session)) => session,
+
+            // The session expired or was revoked. Forget the cookie.
+            Ok(None) => {
+                self.inner.forget();
+                return None;
+            }
+
+            Err(e) => {
+                warn!("Could not get session. Error: {}", e);
+                return None;
+            }
+        };
+
+        let identity: AuthenticationCookie = match session.identity.clone().refresh().await {
+            Ok(identity) => identity,
+            Err(e) => {
+                warn!("Could not refresh identity token. Error: {}", e);
+                return None;
+            }
+        };
+
+        // Look up the username if the user was not registered when the session was saved.
+        let username: Option<String> = match session.username.clone() {
+            Some(username) => Some(username),
+            None => identity.get_rcos_username().await.ok().flatten(),
+        };
+
+        if let Err(e) = self
+            .save_session(store, Some(session), &identity, username)
+            .await
+        {
+            warn!("Could not save session. Error: {}", e);
+            return None;
+        }
+        return Some(identity);
     }
 
     /// Get the user's identity. Refresh it if necessary.
     pub async fn identity(&self) -> Option<AuthenticationCookie> {
         // Get the inner identity as a String.
         let id: String = self.inner.identity()?;
+
+        if let Some(store) = session_store() {
+            // Identity cookies from before the session store was enabled can't be revoked, so
+            // they are forgotten and the user has to sign in again.
+            if id.starts_with('{') {
+                info!("Identity cookie from before the session store forgotten.");
+                self.inner.forget();
+                return None;
+            }
+            return self.session_identity(store, id).await;
+        }
+
         // try to deserialize it
         match serde_json::from_str::<AuthenticationCookie>(id.as_str()) {
             // On okay, refresh the identity cookie if needed
@@ -326,7 +518,10 @@ impl Identity {
                 // If this succeeds
                 Ok(id) => {
                     // Save and return the authenticated identity
-                    self.save(&id);
+                    if let Err(e) = self.save(&id).await {
+                        warn!("Could not save identity. Error: {}", e);
+                        return None;
+                    }
                     return Some(id);
                 }
 
@@ -342,7 +537,7 @@ impl Identity {
             // Forget it, and log a warning. Return no identity.
             Err(err) => {
                 warn!("Bad identity forgotten. Error: {}", err);
-                self.forget();
+                self.inner.forget();
                 return None;
             }
         }
@@ -1,10 +1,14 @@
 use crate::api::rcos::users::accounts::for_user::UserAccounts;
 use crate::api::rcos::users::accounts::unlink::UnlinkUserAccount;
 use crate::api::rcos::users::UserAccountType;
+use crate::env::global_config;
 use crate::error::TelescopeError;
 use crate::web::profile_for;
 use crate::web::services::auth::identity::{AuthenticationCookie, Identity};
+use crate::web::services::auth::oauth2_providers::bitbucket::BitBucketOauth;
 use crate::web::services::auth::oauth2_providers::discord::DiscordOAuth;
+use crate::web::services::auth::oauth2_providers::gitlab::GitLabOauth;
+use crate::web::services::auth::oidc::Oidc;
 use crate::web::services::auth::rpi_cas::RpiCas;
 use actix_web::http::header::{HOST, LOCATION};
 use actix_web::web::ServiceConfig;
@@ -16,15 +20,19 @@ use oauth2_providers::github::GitHubOauth;
 use std::collections::HashMap;
 use std::future::Future;
 
+pub mod cas_response;
 pub mod identity;
 pub mod oauth2_providers;
+pub mod oidc;
 pub mod rpi_cas;
 
 /// The types of user accounts that provide authentication.
-const AUTHENTICATOR_ACCOUNT_TYPES: [UserAccountType; 3] = [
+const AUTHENTICATOR_ACCOUNT_TYPES: [UserAccountType; 5] = [
     UserAccountType::Rpi,
     UserAccountType::GitHub,
     UserAccountType::Discord,
+    UserAccountType::GitLab,
+    UserAccountType::BitBucket,
 ];
 
 /// Register auth services.
@@ -35,6 +43,20 @@ pub fn register(config: &mut ServiceConfig) {
     // Discord OAuth2 provider services.
     DiscordOAuth::register_services(config);
 
+    // GitLab and BitBucket OAuth2 provider services, if they are configured.
+    if global_config().gitlab_credentials.is_some() {
+        GitLabOauth::register_services(config);
+    }
+
+    if global_config().bitbucket_credentials.is_some() {
+        BitBucketOauth::register_services(config);
+    }
+
+    // OpenID Connect provider services, if configured.
+    if global_config().oidc.is_some() {
+        Oidc::register_services(config);
+    }
+
     // RPI CAS provider services.
     RpiCas::register_services(config);
 }
@@ -247,9 +269,9 @@ pub trait IdentityProvider: 'static {
             // the user to their profile.
             // If not, the user has been logged out. Redirect them to the homepage.
             if removed_auth {
-                id.save(&cookie);
+                id.save(&cookie).await?;
             } else {
-                id.forget();
+                id.forget().await;
             }
 
             // Get the path to redirect the user to.
@@ -0,0 +1,207 @@
+//! BitBucket OAuth2 flow.
+
+use crate::api::rcos::send_query;
+use crate::api::rcos::users::accounts::reverse_lookup::ReverseLookup;
+use crate::api::rcos::users::UserAccountType;
+use crate::env::global_config;
+use crate::error::TelescopeError;
+use crate::web::services::auth::identity::{AuthenticationCookie, RootIdentity};
+use crate::web::services::auth::oauth2_providers::{Oauth2Identity, Oauth2IdentityProvider};
+use actix_web::http::header::ACCEPT;
+use chrono::{DateTime, Duration, Utc};
+use futures::future::LocalBoxFuture;
+use oauth2::basic::{BasicClient, BasicTokenResponse};
+use oauth2::{AccessToken, AuthUrl, RefreshToken, Scope, TokenResponse, TokenUrl};
+use std::sync::Arc;
+
+/// The BitBucket API endpoint to query for user data.
+const BITBUCKET_API_ENDPOINT: &'static str = "https://api.bitbucket.org/2.0";
+
+/// Zero-sized type used to represent BitBucket based identity verification.
+pub struct BitBucketOauth;
+
+/// The object stored in a user's cookies when authenticated via BitBucket.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BitBucketIdentity {
+    /// The OAuth2 access token granted by BitBucket.
+    access_token: AccessToken,
+    /// When the access token expires.
+    expiration: Option<DateTime<Utc>>,
+    /// The token to use to refresh it.
+    refresh_token: Option<RefreshToken>,
+}
+
+/// A link in a BitBucket API response.
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitBucketLink {
+    pub href: String,
+}
+
+/// The links on a BitBucket user.
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitBucketUserLinks {
+    /// The user's avatar.
+    pub avatar: Option<BitBucketLink>,
+    /// The user's BitBucket profile.
+    pub html: Option<BitBucketLink>,
+}
+
+/// The fields of a BitBucket user that Telescope uses.
+/// See <https://developer.atlassian.com/cloud/bitbucket/rest/api-group-users/>.
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitBucketUser {
+    /// The user's UUID. This is the only identifier that BitBucket guarantees will not change.
+    pub uuid: String,
+    /// The user's BitBucket nickname.
+    pub nickname: String,
+    /// The user's display name.
+    pub display_name: String,
+    /// Links to the user's avatar and profile.
+    pub links: BitBucketUserLinks,
+}
+
+lazy_static! {
+    // Identities issued while BitBucket was configured can outlive the credentials, so
+    // there is no client rather than a panic when they are missing.
+    static ref BITBUCKET_CLIENT: Option<Arc<BasicClient>> = global_config()
+        .bitbucket_credentials
+        .as_ref()
+        .map(|config| {
+            // Create BitBucket OAuth2 client.
+            let client = BasicClient::new(
+                config.client_id.clone(),
+                Some(config.client_secret.clone()),
+                AuthUrl::new("https://bitbucket.org/site/oauth2/authorize".into())
+                    .expect("Invalid BitBucket Auth URL"),
+                Some(TokenUrl::new("https://bitbucket.org/site/oauth2/access_token".into())
+                    .expect("Invalid BitBucket Token URL")));
+
+            // Return the client config wrapped in an Arc.
+            Arc::new(client)
+        });
+}
+
+/// Get the BitBucket OAuth2 client, or an error if BitBucket credentials are not configured.
+fn bitbucket_client() -> Result<Arc<BasicClient>, TelescopeError> {
+    BITBUCKET_CLIENT
+        .clone()
+        .ok_or_else(|| TelescopeError::ise("BitBucket credentials are not configured."))
+}
+
+impl Oauth2IdentityProvider for BitBucketOauth {
+    type IdentityType = BitBucketIdentity;
+    const SERVICE_NAME: &'static str = "bitbucket";
+
+    fn get_client() -> Arc<BasicClient> {
+        // The BitBucket login services are only registered if the credentials are set.
+        bitbucket_client().expect("BitBucket login is registered without BitBucket credentials.")
+    }
+
+    fn scopes() -> Vec<Scope> {
+        vec![
+            // Scope to read the authenticated user's account.
+            Scope::new("account".into()),
+        ]
+    }
+}
+
+impl Oauth2Identity for BitBucketIdentity {
+    const USER_ACCOUNT_TY: UserAccountType = UserAccountType::BitBucket;
+
+    fn from_basic_token(token: &BasicTokenResponse) -> Self {
+        Self::from_response(token)
+    }
+
+    fn platform_user_id(&self) -> LocalBoxFuture<Result<String, TelescopeError>> {
+        Box::pin(async move { self.get_user_id().await })
+    }
+
+    fn into_root(self) -> RootIdentity {
+        RootIdentity::BitBucket(self)
+    }
+
+    fn add_to_cookie(self, cookie: &mut AuthenticationCookie) {
+        cookie.bitbucket = Some(self);
+    }
+}
+
+impl BitBucketIdentity {
+    fn from_response(token_response: &BasicTokenResponse) -> Self {
+        BitBucketIdentity {
+            access_token: token_response.access_token().clone(),
+            expiration: token_response
+                .expires_in()
+                .and_then(|duration| Duration::from_std(duration).ok())
+                .map(|duration| Utc::now() + duration),
+            refresh_token: token_response.refresh_token().cloned(),
+        }
+    }
+
+    /// Refresh this access token if necessary.
+    pub async fn refresh(self) -> Result<Self, TelescopeError> {
+        // Only expired tokens with a refresh token are refreshed.
+        let refresh_token: &RefreshToken = match (&self.expiration, &self.refresh_token) {
+            (Some(expiration), Some(refresh_token)) if *expiration < Utc::now() => refresh_token,
+            _ => return Ok(self),
+        };
+
+        let client: Arc<BasicClient> = bitbucket_client()?;
+        let response = client
+            .exchange_refresh_token(refresh_token)
+            .request(oauth2::reqwest::http_client)
+            .map_err(|err| {
+                TelescopeError::ise(format!(
+                    "Could not refresh BitBucket OAuth2 token. Error: {}",
+                    err
+                ))
+            })?;
+
+        let mut refreshed: Self = Self::from_response(&response);
+        // Keep the old refresh token if BitBucket did not issue a new one.
+        if refreshed.refresh_token.is_none() {
+            refreshed.refresh_token = self.refresh_token;
+        }
+        return Ok(refreshed);
+    }
+
+    /// Get the authenticated BitBucket account's UUID.
+    pub async fn get_user_id(&self) -> Result<String, TelescopeError> {
+        self.get_authenticated_user().await.map(|u| u.uuid)
+    }
+
+    /// Get the RCOS username of the account associated with the authenticated
+    /// BitBucket user if one exists.
+    pub async fn get_rcos_username(&self) -> Result<Option<String>, TelescopeError> {
+        let platform_id: String = self.get_user_id().await?;
+        let variables = ReverseLookup::make_vars(UserAccountType::BitBucket, platform_id);
+        return send_query::<ReverseLookup>(variables)
+            .await
+            .map(|response| response.username());
+    }
+
+    /// Get the currently authenticated BitBucket user associated with this access token.
+    pub async fn get_authenticated_user(&self) -> Result<BitBucketUser, TelescopeError> {
+        return reqwest::Client::new()
+            .get(format!("{}/user", BITBUCKET_API_ENDPOINT).as_str())
+            .bearer_auth(self.access_token.secret())
+            .header(ACCEPT, "application/json")
+            .send()
+            .await
+            .map_err(|e| {
+                TelescopeError::ise(format!(
+                    "Could not send identification query to BitBucket \
+            API. Internal error: {}",
+                    e
+                ))
+            })?
+            .json::<BitBucketUser>()
+            .await
+            .map_err(|e| {
+                TelescopeError::ise(format!(
+                    "Error with identification response from BitBucket \
+            API. Internal error: {}",
+                    e
+                ))
+            });
+    }
+}
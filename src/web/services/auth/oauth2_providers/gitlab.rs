@@ -0,0 +1,200 @@
+//! GitLab OAuth2 flow. This works with gitlab.com or a self-hosted GitLab instance.
+
+use crate::api::rcos::send_query;
+use crate::api::rcos::users::accounts::reverse_lookup::ReverseLookup;
+use crate::api::rcos::users::UserAccountType;
+use crate::env::{global_config, GitLabOauthConfig};
+use crate::error::TelescopeError;
+use crate::web::services::auth::identity::{AuthenticationCookie, RootIdentity};
+use crate::web::services::auth::oauth2_providers::{Oauth2Identity, Oauth2IdentityProvider};
+use crate::web::services::auth::IdentityProvider;
+use actix_web::http::header::ACCEPT;
+use chrono::{DateTime, Duration, Utc};
+use futures::future::LocalBoxFuture;
+use oauth2::basic::{BasicClient, BasicTokenResponse};
+use oauth2::{AccessToken, AuthUrl, RefreshToken, Scope, TokenResponse, TokenUrl};
+use std::sync::Arc;
+
+/// Zero-sized type used to represent GitLab based identity verification.
+pub struct GitLabOauth;
+
+/// The object stored in a user's cookies when authenticated via GitLab.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GitLabIdentity {
+    /// The OAuth2 access token granted by GitLab.
+    access_token: AccessToken,
+    /// When the access token expires. Older GitLab instances issue tokens that do not expire.
+    expiration: Option<DateTime<Utc>>,
+    /// The token to use to refresh it, if it expires.
+    refresh_token: Option<RefreshToken>,
+}
+
+/// The fields of a GitLab user that Telescope uses.
+/// See <https://docs.gitlab.com/ee/api/users.html#for-normal-users-1>.
+#[derive(Deserialize, Clone, Debug)]
+pub struct GitLabUser {
+    /// The user's ID on the GitLab instance.
+    pub id: u64,
+    /// The user's GitLab username.
+    pub username: String,
+    /// The URL of the user's avatar.
+    pub avatar_url: Option<String>,
+    /// The URL of the user's GitLab profile.
+    pub web_url: String,
+}
+
+/// Get the GitLab credentials from the global config. Identities issued while GitLab was
+/// configured can outlive the credentials, so this errors rather than panicking.
+fn gitlab_config() -> Result<&'static GitLabOauthConfig, TelescopeError> {
+    global_config()
+        .gitlab_credentials
+        .as_ref()
+        .ok_or_else(|| TelescopeError::ise("GitLab credentials are not configured."))
+}
+
+lazy_static! {
+    static ref GITLAB_CLIENT: Option<Arc<BasicClient>> = gitlab_config().ok().map(|config| {
+        let instance: &str = config.instance_url();
+
+        // Create GitLab OAuth2 client.
+        let client = BasicClient::new(
+            config.client_id.clone(),
+            Some(config.client_secret.clone()),
+            AuthUrl::new(format!("{}/oauth/authorize", instance))
+                .expect("Invalid GitLab Auth URL"),
+            Some(TokenUrl::new(format!("{}/oauth/token", instance))
+                .expect("Invalid GitLab Token URL")));
+
+        // Return the client config wrapped in an Arc.
+        Arc::new(client)
+    });
+}
+
+/// Get the GitLab OAuth2 client, or an error if GitLab credentials are not configured.
+fn gitlab_client() -> Result<Arc<BasicClient>, TelescopeError> {
+    GITLAB_CLIENT
+        .clone()
+        .ok_or_else(|| TelescopeError::ise("GitLab credentials are not configured."))
+}
+
+impl Oauth2IdentityProvider for GitLabOauth {
+    type IdentityType = GitLabIdentity;
+    const SERVICE_NAME: &'static str = "gitlab";
+
+    fn get_client() -> Arc<BasicClient> {
+        // The GitLab login services are only registered if the credentials are set.
+        gitlab_client().expect("GitLab login is registered without GitLab credentials.")
+    }
+
+    fn scopes() -> Vec<Scope> {
+        vec![
+            // Scope to read the authenticated user's profile.
+            Scope::new("read_user".into()),
+        ]
+    }
+}
+
+impl Oauth2Identity for GitLabIdentity {
+    const USER_ACCOUNT_TY: UserAccountType = UserAccountType::GitLab;
+
+    fn from_basic_token(token: &BasicTokenResponse) -> Self {
+        Self::from_response(token)
+    }
+
+    fn platform_user_id(&self) -> LocalBoxFuture<Result<String, TelescopeError>> {
+        Box::pin(async move { self.get_user_id().await })
+    }
+
+    fn into_root(self) -> RootIdentity {
+        RootIdentity::GitLab(self)
+    }
+
+    fn add_to_cookie(self, cookie: &mut AuthenticationCookie) {
+        cookie.gitlab = Some(self);
+    }
+}
+
+impl GitLabIdentity {
+    fn from_response(token_response: &BasicTokenResponse) -> Self {
+        GitLabIdentity {
+            access_token: token_response.access_token().clone(),
+            expiration: token_response
+                .expires_in()
+                .and_then(|duration| Duration::from_std(duration).ok())
+                .map(|duration| Utc::now() + duration),
+            refresh_token: token_response.refresh_token().cloned(),
+        }
+    }
+
+    /// Refresh this access token if necessary.
+    pub async fn refresh(self) -> Result<Self, TelescopeError> {
+        // Tokens without an expiration or refresh token are used as they are.
+        let refresh_token: &RefreshToken = match (&self.expiration, &self.refresh_token) {
+            (Some(expiration), Some(refresh_token)) if *expiration < Utc::now() => refresh_token,
+            _ => return Ok(self),
+        };
+
+        let client: Arc<BasicClient> = gitlab_client()?;
+        let response = client
+            .exchange_refresh_token(refresh_token)
+            .add_extra_param("redirect_uri", GitLabOauth::login_redirect_path().as_str())
+            .request(oauth2::reqwest::http_client)
+            .map_err(|err| {
+                TelescopeError::ise(format!(
+                    "Could not refresh GitLab OAuth2 token. Error: {}",
+                    err
+                ))
+            })?;
+
+        let mut refreshed: Self = Self::from_response(&response);
+        // Keep the old refresh token if GitLab did not issue a new one.
+        if refreshed.refresh_token.is_none() {
+            refreshed.refresh_token = self.refresh_token;
+        }
+        return Ok(refreshed);
+    }
+
+    /// Get the authenticated GitLab account's ID.
+    pub async fn get_user_id(&self) -> Result<String, TelescopeError> {
+        self.get_authenticated_user()
+            .await
+            .map(|u| u.id.to_string())
+    }
+
+    /// Get the RCOS username of the account associated with the authenticated
+    /// GitLab user if one exists.
+    pub async fn get_rcos_username(&self) -> Result<Option<String>, TelescopeError> {
+        let platform_id: String = self.get_user_id().await?;
+        let variables = ReverseLookup::make_vars(UserAccountType::GitLab, platform_id);
+        return send_query::<ReverseLookup>(variables)
+            .await
+            .map(|response| response.username());
+    }
+
+    /// Get the currently authenticated GitLab user associated with this access token.
+    pub async fn get_authenticated_user(&self) -> Result<GitLabUser, TelescopeError> {
+        let instance: &str = gitlab_config()?.instance_url();
+        return reqwest::Client::new()
+            .get(format!("{}/api/v4/user", instance).as_str())
+            .bearer_auth(self.access_token.secret())
+            .header(ACCEPT, "application/json")
+            .send()
+            .await
+            .map_err(|e| {
+                TelescopeError::ise(format!(
+                    "Could not send identification query to GitLab \
+            API. Internal error: {}",
+                    e
+                ))
+            })?
+            .json::<GitLabUser>()
+            .await
+            .map_err(|e| {
+                TelescopeError::ise(format!(
+                    "Error with identification response from GitLab \
+            API. Internal error: {}",
+                    e
+                ))
+            });
+    }
+}
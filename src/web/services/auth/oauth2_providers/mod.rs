@@ -15,8 +15,10 @@ use oauth2::{AuthorizationCode, AuthorizationRequest, CsrfToken, RedirectUrl, Sc
 use std::borrow::Cow;
 use std::sync::Arc;
 
+pub mod bitbucket;
 pub mod discord;
 pub mod github;
+pub mod gitlab;
 
 /// Data returned by GitHub OAuth2 Authorization request.
 #[derive(Deserialize)]
@@ -238,7 +240,7 @@ where
 
             // Otherwise, store the identity in the user's cookies and redirect to their profile.
             let identity: Identity = Identity::extract(&req).await?;
-            identity.save(&root.make_authenticated_cookie());
+            identity.save(&root.make_authenticated_cookie()).await?;
             Ok(HttpResponse::Found()
                 .header(LOCATION, profile_for(username.as_str()))
                 .finish())
@@ -259,7 +261,7 @@ where
 
             // Extract the identity object from the request and store the cookie in it.
             let identity: Identity = Identity::extract(&req).await?;
-            identity.save(&root.make_authenticated_cookie());
+            identity.save(&root.make_authenticated_cookie()).await?;
 
             // Success! Redirect the user to finish the registration process.
             Ok(HttpResponse::Found()
@@ -0,0 +1,729 @@
+//! Generic OpenID Connect identity provider.
+//!
+//! The provider's endpoints are discovered from the configured issuer URL. Authorization uses
+//! the code flow with PKCE, and the ID token returned by the token exchange is validated
+//! against the provider's published signing keys, the nonce sent with the authorization
+//! request, the issuer, and this client's ID. The configured claim of the ID token is the
+//! user's RCS ID, so users signed in this way are treated the same as users signed in through
+//! RPI CAS. The RCOS database has no account type for OpenID Connect, so the config has to opt
+//! in to this with `claim_is_rcs_id`.
+
+use crate::api::rcos::users::accounts::link::LinkUserAccount;
+use crate::api::rcos::users::accounts::lookup::AccountLookup;
+use crate::api::rcos::users::UserAccountType;
+use crate::env::{global_config, OidcConfig};
+use crate::error::TelescopeError;
+use crate::web::services::auth::cas_response::CasProfile;
+use crate::web::services::auth::identity::{AuthenticationCookie, Identity, RootIdentity};
+use crate::web::services::auth::rpi_cas::RpiCasIdentity;
+use crate::web::services::auth::{make_redirect_url, IdentityProvider};
+use crate::web::{csrf, profile_for};
+use actix_web::http::header::{ACCEPT, LOCATION};
+use actix_web::{web::Query, FromRequest};
+use actix_web::{HttpRequest, HttpResponse};
+use chrono::{DateTime, Duration, Utc};
+use dashmap::DashMap;
+use futures::future::LocalBoxFuture;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Header, Validation};
+use oauth2::basic::{
+    BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse,
+    BasicTokenType,
+};
+use oauth2::{
+    AuthUrl, AuthorizationCode, Client, CsrfToken, ExtraTokenFields, PkceCodeChallenge,
+    PkceCodeVerifier, Scope, StandardRevocableToken, StandardTokenResponse, TokenUrl,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::sync::RwLock;
+
+/// Seconds of clock skew allowed when checking ID token expiration.
+const CLOCK_SKEW_LEEWAY: u64 = 60;
+
+/// The most authorization requests waiting on the identity provider at once. Requests are
+/// kept per client IP address, so this is only reached if many clients start signing in
+/// within a few minutes of each other.
+const MAX_PENDING_AUTHORIZATIONS: usize = 10_000;
+
+/// The fields of the provider's discovery document that Telescope uses.
+/// See <https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata>.
+#[derive(Deserialize, Clone, Debug)]
+struct ProviderMetadata {
+    /// The issuer identifier. This must match the configured issuer.
+    issuer: String,
+    /// The URL to send users to for authorization.
+    authorization_endpoint: String,
+    /// The URL to exchange authorization codes at.
+    token_endpoint: String,
+    /// The URL of the provider's signing keys.
+    jwks_uri: String,
+}
+
+/// A JSON web key set.
+#[derive(Deserialize, Clone, Debug)]
+struct JsonWebKeySet {
+    keys: Vec<JsonWebKey>,
+}
+
+/// A JSON web key. Only RSA keys are supported.
+#[derive(Deserialize, Clone, Debug)]
+struct JsonWebKey {
+    /// The key type. Only `RSA` keys are used.
+    kty: String,
+    /// The key ID, matched against the `kid` of an ID token header.
+    #[serde(default)]
+    kid: Option<String>,
+    /// What the key is used for. Keys used for encryption are skipped.
+    #[serde(default, rename = "use")]
+    use_: Option<String>,
+    /// The RSA modulus, base64url encoded.
+    #[serde(default)]
+    n: Option<String>,
+    /// The RSA exponent, base64url encoded.
+    #[serde(default)]
+    e: Option<String>,
+}
+
+/// The ID token returned alongside the access token.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct IdTokenFields {
+    id_token: String,
+}
+
+impl ExtraTokenFields for IdTokenFields {}
+
+/// Token response with an ID token.
+type OidcTokenResponse = StandardTokenResponse<IdTokenFields, BasicTokenType>;
+
+/// OAuth2 client that expects an ID token in the token response.
+type OidcClient = Client<
+    BasicErrorResponse,
+    OidcTokenResponse,
+    BasicTokenType,
+    BasicTokenIntrospectionResponse,
+    StandardRevocableToken,
+    BasicRevocationErrorResponse,
+>;
+
+/// Data returned by the provider's authorization request.
+#[derive(Deserialize)]
+struct AuthResponse {
+    /// The auth code.
+    code: AuthorizationCode,
+    /// The CSRF token.
+    state: CsrfToken,
+}
+
+/// The PKCE verifier and nonce of an authorization request that has not completed yet.
+struct PendingAuthorization {
+    pkce_verifier: String,
+    nonce: String,
+    expiration: DateTime<Utc>,
+}
+
+lazy_static! {
+    /// The discovered provider metadata. This is fetched the first time it is needed.
+    static ref PROVIDER_METADATA: RwLock<Option<ProviderMetadata>> = RwLock::new(None);
+
+    /// Pending authorization requests, keyed by the IP address of the client that made them.
+    /// Like the CSRF token, a client only has one pending authorization at a time.
+    static ref PENDING_AUTHORIZATIONS: DashMap<String, PendingAuthorization> = DashMap::new();
+}
+
+/// Get the OpenID Connect config. The OpenID Connect services are only registered if it is set.
+fn oidc_config() -> &'static OidcConfig {
+    global_config()
+        .oidc
+        .as_ref()
+        .expect("OpenID Connect is not configured.")
+}
+
+/// The identity released by the OpenID Connect provider.
+#[derive(Clone, Debug)]
+struct OidcIdentity {
+    /// The value of the configured claim. This is the user's RCS ID.
+    subject: String,
+
+    /// The profile released in the ID token, if any. This is used to pre-fill the registration
+    /// form.
+    profile: Option<CasProfile>,
+}
+
+impl OidcIdentity {
+    /// Get the root identity for this account. This is always an RPI CAS identity, since the
+    /// claim is an RCS ID.
+    fn into_root(self) -> RootIdentity {
+        RootIdentity::RpiCas(RpiCasIdentity {
+            rcs_id: self.subject.to_lowercase(),
+            profile: self.profile,
+        })
+    }
+}
+
+/// Send a GET request for a JSON document from the identity provider.
+async fn get_json<T: DeserializeOwned>(url: &str) -> Result<T, TelescopeError> {
+    return reqwest::Client::new()
+        .get(url)
+        .header(ACCEPT, "application/json")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| {
+            TelescopeError::ise(format!(
+                "Could not reach the OpenID Connect provider at {}. Internal error: {}",
+                url, e
+            ))
+        })?
+        .json::<T>()
+        .await
+        .map_err(|e| {
+            TelescopeError::ise(format!(
+                "Malformed response from the OpenID Connect provider at {}. Internal error: {}",
+                url, e
+            ))
+        });
+}
+
+/// Get the provider metadata, discovering it from the issuer if it has not been yet.
+async fn provider_metadata() -> Result<ProviderMetadata, TelescopeError> {
+    if let Some(metadata) = PROVIDER_METADATA
+        .read()
+        .expect("Provider metadata lock poisoned")
+        .as_ref()
+    {
+        return Ok(metadata.clone());
+    }
+
+    let issuer: &str = oidc_config().issuer_url();
+    let metadata: ProviderMetadata =
+        get_json(format!("{}/.well-known/openid-configuration", issuer).as_str()).await?;
+
+    // The discovered issuer has to be the configured one.
+    if metadata.issuer.trim_end_matches('/') != issuer {
+        return Err(TelescopeError::ise(format!(
+            "OpenID Connect discovery returned issuer {} but {} is configured.",
+            metadata.issuer, issuer
+        )));
+    }
+
+    *PROVIDER_METADATA
+        .write()
+        .expect("Provider metadata lock poisoned") = Some(metadata.clone());
+    return Ok(metadata);
+}
+
+/// Make the OAuth2 client for the discovered endpoints.
+fn make_client(metadata: &ProviderMetadata) -> Result<OidcClient, TelescopeError> {
+    let config: &OidcConfig = oidc_config();
+    let auth_url = AuthUrl::new(metadata.authorization_endpoint.clone()).map_err(|e| {
+        TelescopeError::ise(format!("Invalid OpenID Connect authorization URL: {}", e))
+    })?;
+    let token_url = TokenUrl::new(metadata.token_endpoint.clone())
+        .map_err(|e| TelescopeError::ise(format!("Invalid OpenID Connect token URL: {}", e)))?;
+
+    return Ok(OidcClient::new(
+        config.client_id.clone(),
+        config.client_secret.clone(),
+        auth_url,
+        Some(token_url),
+    ));
+}
+
+/// Build the authorization URL and a response redirecting the user to it. The CSRF token,
+/// PKCE verifier, and nonce are saved until the user is sent back.
+async fn auth_response(
+    req: &HttpRequest,
+    redir_path: String,
+) -> Result<HttpResponse, TelescopeError> {
+    let client: OidcClient = make_client(&provider_metadata().await?)?;
+    let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+    let nonce: CsrfToken = CsrfToken::new_random();
+
+    let mut auth_req = client
+        .authorize_url(CsrfToken::new_random)
+        .set_redirect_uri(Cow::Owned(make_redirect_url(req, redir_path)))
+        .set_pkce_challenge(pkce_challenge)
+        .add_extra_param("nonce", nonce.secret().as_str());
+
+    for scope in oidc_config().scopes() {
+        auth_req = auth_req.add_scope(Scope::new(scope));
+    }
+
+    let (url, csrf_token) = auth_req.url();
+    let ip_addr: String = csrf::extract_ip_addr(req)?;
+
+    // Forget any pending authorizations that were never completed, then make sure there is
+    // room for this one.
+    PENDING_AUTHORIZATIONS.retain(|_, pending| pending.expiration > Utc::now());
+    if PENDING_AUTHORIZATIONS.len() >= MAX_PENDING_AUTHORIZATIONS
+        && !PENDING_AUTHORIZATIONS.contains_key(&ip_addr)
+    {
+        return Err(TelescopeError::BadRequest {
+            header: "Too Many Sign In Attempts".into(),
+            message: "Too many people are signing in right now. Please try again in a few \
+            minutes."
+                .into(),
+            show_status_code: true,
+        });
+    }
+
+    PENDING_AUTHORIZATIONS.insert(
+        ip_addr,
+        PendingAuthorization {
+            pkce_verifier: pkce_verifier.secret().clone(),
+            nonce: nonce.secret().clone(),
+            expiration: Utc::now() + Duration::minutes(10),
+        },
+    );
+    csrf::save(Oidc::SERVICE_NAME, req, csrf_token)?;
+
+    return Ok(HttpResponse::Found()
+        .header(LOCATION, url.as_str())
+        .finish());
+}
+
+/// Make the error for an ID token that does not validate.
+fn invalid_id_token(reason: impl std::fmt::Display) -> TelescopeError {
+    TelescopeError::BadRequest {
+        header: "Invalid ID Token".into(),
+        message: format!(
+            "The identity provider returned an ID token that could not be validated. \
+            Please try again. If this error persists, contact a coordinator and file an \
+            issue on Telescope's GitHub. Reason: {}",
+            reason
+        ),
+        show_status_code: true,
+    }
+}
+
+/// Validate an ID token against the provider's published keys and return its claims.
+async fn validate_id_token(
+    metadata: &ProviderMetadata,
+    id_token: &str,
+    nonce: &str,
+) -> Result<Map<String, Value>, TelescopeError> {
+    // The key set is fetched every time so that key rotation at the provider does not need a
+    // restart.
+    let key_set: JsonWebKeySet = get_json(metadata.jwks_uri.as_str()).await?;
+    return check_id_token(
+        &key_set,
+        metadata.issuer.as_str(),
+        oidc_config().client_id.as_str(),
+        id_token,
+        nonce,
+    );
+}
+
+/// Check an ID token and return its claims. This checks the signature against the key set, as
+/// well as the expiration, issuer, audience, and nonce.
+fn check_id_token(
+    key_set: &JsonWebKeySet,
+    issuer: &str,
+    client_id: &str,
+    id_token: &str,
+    nonce: &str,
+) -> Result<Map<String, Value>, TelescopeError> {
+    let header: Header = decode_header(id_token).map_err(invalid_id_token)?;
+
+    // Only RSA signatures are supported.
+    let algorithms: [Algorithm; 6] = [
+        Algorithm::RS256,
+        Algorithm::RS384,
+        Algorithm::RS512,
+        Algorithm::PS256,
+        Algorithm::PS384,
+        Algorithm::PS512,
+    ];
+    if !algorithms.contains(&header.alg) {
+        return Err(invalid_id_token(format!(
+            "unsupported signing algorithm {:?}",
+            header.alg
+        )));
+    }
+
+    // Find the signing key.
+    let key: &JsonWebKey = key_set
+        .keys
+        .iter()
+        .filter(|key| key.kty == "RSA")
+        .filter(|key| key.use_.as_deref().map(|u| u == "sig").unwrap_or(true))
+        .find(|key| header.kid.is_none() || key.kid == header.kid)
+        .ok_or_else(|| invalid_id_token("no matching signing key"))?;
+
+    let (n, e) = match (&key.n, &key.e) {
+        (Some(n), Some(e)) => (n, e),
+        _ => return Err(invalid_id_token("malformed signing key")),
+    };
+
+    let mut validation = Validation::new(header.alg);
+    validation.leeway = CLOCK_SKEW_LEEWAY;
+    validation.iss = Some(issuer.to_string());
+    validation.set_audience(&[client_id]);
+
+    let claims: Map<String, Value> = decode::<Map<String, Value>>(
+        id_token,
+        &DecodingKey::from_rsa_components(n.as_str(), e.as_str()),
+        &validation,
+    )
+    .map_err(invalid_id_token)?
+    .claims;
+
+    // Check the nonce to make sure this token was issued for this authorization request.
+    if claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+        return Err(invalid_id_token("nonce mismatch"));
+    }
+
+    return Ok(claims);
+}
+
+/// After the user has authenticated with the identity provider it sends them back to
+/// telescope with an authorization code. Exchange it for an ID token, validate the token,
+/// and return the identity in the configured claim.
+async fn oidc_authenticated(
+    req: &HttpRequest,
+    redir_path: String,
+) -> Result<OidcIdentity, TelescopeError> {
+    let Query(AuthResponse { code, state }): Query<AuthResponse> = Query::extract(req)
+        .await
+        .map_err(|err| TelescopeError::BadRequest {
+            header: "Bad Authentication Request".into(),
+            message: format!(
+                "Could not get authentication parameters from request URL. \
+                Actix-web error: {}",
+                err
+            ),
+            show_status_code: true,
+        })?;
+
+    // Take the pending authorization before verifying the CSRF token, so it is removed either
+    // way.
+    let pending: Option<PendingAuthorization> = PENDING_AUTHORIZATIONS
+        .remove(&csrf::extract_ip_addr(req)?)
+        .map(|(_, pending)| pending)
+        .filter(|pending| pending.expiration > Utc::now());
+    csrf::verify(Oidc::SERVICE_NAME, req, state)?;
+    let pending: PendingAuthorization = pending.ok_or(TelescopeError::CsrfTokenNotFound)?;
+
+    let metadata: ProviderMetadata = provider_metadata().await?;
+    let token_response: OidcTokenResponse = make_client(&metadata)?
+        .exchange_code(code)
+        .set_pkce_verifier(PkceCodeVerifier::new(pending.pkce_verifier))
+        .add_extra_param("redirect_uri", make_redirect_url(req, redir_path).as_str())
+        .request(oauth2::reqwest::http_client)
+        .map_err(|e| {
+            TelescopeError::ise(format!(
+                "OpenID Connect token exchange error. If this persists, please contact a \
+                coordinator and file a GitHub issue. Internal error description: {:?}",
+                e
+            ))
+        })?;
+
+    let claims: Map<String, Value> = validate_id_token(
+        &metadata,
+        token_response.extra_fields().id_token.as_str(),
+        pending.nonce.as_str(),
+    )
+    .await?;
+
+    // Get the configured claim. Allow numeric identifiers too.
+    let claim: &str = oidc_config().claim();
+    let subject: String = match claims.get(claim) {
+        Some(Value::String(value)) if !value.trim().is_empty() => value.trim().to_string(),
+        Some(Value::Number(value)) => value.to_string(),
+        _ => {
+            return Err(TelescopeError::BadRequest {
+                header: "Missing Account Identifier".into(),
+                message: format!(
+                    "The identity provider did not include the \"{}\" claim in the ID token. \
+                    Please contact a coordinator.",
+                    claim
+                ),
+                show_status_code: false,
+            })
+        }
+    };
+
+    // Pick the profile out of the standard claims.
+    let standard_claim = |name: &str| -> Option<String> {
+        claims.get(name).and_then(Value::as_str).map(String::from)
+    };
+    let profile = CasProfile {
+        first_name: standard_claim("given_name"),
+        last_name: standard_claim("family_name"),
+        email: standard_claim("email"),
+    };
+    let has_profile: bool =
+        profile.first_name.is_some() || profile.last_name.is_some() || profile.email.is_some();
+
+    return Ok(OidcIdentity {
+        subject,
+        profile: has_profile.then(|| profile),
+    });
+}
+
+/// Zero-sized struct representing the configured OpenID Connect identity provider.
+pub struct Oidc;
+
+impl IdentityProvider for Oidc {
+    const SERVICE_NAME: &'static str = "oidc";
+    // Users signed in this way are linked by their RCS ID.
+    const USER_ACCOUNT_TY: UserAccountType = UserAccountType::Rpi;
+
+    type LoginResponse = Result<HttpResponse, TelescopeError>;
+    type RegistrationResponse = Result<HttpResponse, TelescopeError>;
+    type LinkResponse = Result<HttpResponse, TelescopeError>;
+
+    type LoginFut = LocalBoxFuture<'static, Self::LoginResponse>;
+    type RegistrationFut = LocalBoxFuture<'static, Self::RegistrationResponse>;
+    type LinkFut = LocalBoxFuture<'static, Self::LinkResponse>;
+
+    type LoginAuthenticatedFut = LocalBoxFuture<'static, Result<HttpResponse, TelescopeError>>;
+    type RegistrationAuthenticatedFut =
+        LocalBoxFuture<'static, Result<HttpResponse, TelescopeError>>;
+    type LinkAuthenticatedFut = LocalBoxFuture<'static, Result<HttpResponse, TelescopeError>>;
+
+    fn login_handler(req: HttpRequest) -> Self::LoginFut {
+        return Box::pin(async move { auth_response(&req, Self::login_redirect_path()).await });
+    }
+
+    fn registration_handler(req: HttpRequest) -> Self::RegistrationFut {
+        return Box::pin(
+            async move { auth_response(&req, Self::registration_redirect_path()).await },
+        );
+    }
+
+    fn link_handler(req: HttpRequest, ident: Identity) -> Self::LinkFut {
+        return Box::pin(async move {
+            // The user must already be authenticated to link an existing account.
+            if let Some(authenticated_identity) = ident.identity().await {
+                // Make sure they are not already signed in with this type of account.
+                if authenticated_identity.root.get_user_account_type() == Self::USER_ACCOUNT_TY {
+                    return Err(TelescopeError::BadRequest {
+                        header: format!("{} account already linked", Self::USER_ACCOUNT_TY),
+                        message: format!(
+                            "You are already signed in with your {} account.",
+                            Self::USER_ACCOUNT_TY
+                        ),
+                        show_status_code: false,
+                    });
+                }
+
+                auth_response(&req, Self::link_redirect_path()).await
+            } else {
+                Err(TelescopeError::NotAuthenticated)
+            }
+        });
+    }
+
+    fn login_authenticated_handler(req: HttpRequest) -> Self::LoginAuthenticatedFut {
+        return Box::pin(async move {
+            let root: RootIdentity = oidc_authenticated(&req, Self::login_redirect_path())
+                .await?
+                .into_root();
+            let rcos_username: String =
+                root.get_rcos_username()
+                    .await?
+                    .ok_or(TelescopeError::resource_not_found(
+                        "Could not find associated user account",
+                        format!(
+                            "Could not find a Telescope account for this {} account. Please \
+                        create an account or login using another provider.",
+                            oidc_config().display_name()
+                        ),
+                    ))?;
+
+            // Set the user's identity cookie and redirect them to their profile.
+            let identity: Identity = Identity::extract(&req).await?;
+            identity.save(&root.make_authenticated_cookie()).await?;
+            Ok(HttpResponse::Found()
+                .header(LOCATION, profile_for(rcos_username.as_str()))
+                .finish())
+        });
+    }
+
+    fn registration_authenticated_handler(req: HttpRequest) -> Self::RegistrationAuthenticatedFut {
+        return Box::pin(async move {
+            let cookie: RootIdentity = oidc_authenticated(&req, Self::registration_redirect_path())
+                .await?
+                .into_root();
+            let identity: Identity = Identity::extract(&req).await?;
+            identity.save(&cookie.make_authenticated_cookie()).await?;
+
+            // Redirect the user to complete registration.
+            Ok(HttpResponse::Found()
+                .header(LOCATION, "/register/finish")
+                .finish())
+        });
+    }
+
+    fn linking_authenticated_handler(
+        req: HttpRequest,
+        ident: Identity,
+    ) -> Self::LinkAuthenticatedFut {
+        return Box::pin(async move {
+            let authenticated: AuthenticationCookie = ident
+                .identity()
+                .await
+                .ok_or(TelescopeError::NotAuthenticated)?;
+            let rcos_username: String = authenticated.get_rcos_username_or_error().await?;
+
+            // Get the account of this type already linked to this user (if one exists).
+            let existing_id: Option<String> =
+                AccountLookup::send(rcos_username.clone(), Self::USER_ACCOUNT_TY).await?;
+            let new_id: String = oidc_authenticated(&req, Self::link_redirect_path())
+                .await?
+                .into_root()
+                .get_platform_id()
+                .await?;
+
+            match existing_id {
+                // Link the account if there isn't one yet.
+                None => {
+                    LinkUserAccount::send(rcos_username.clone(), Self::USER_ACCOUNT_TY, new_id)
+                        .await?;
+                }
+
+                // Otherwise it has to be the same one.
+                Some(existing) if existing != new_id => {
+                    return Err(TelescopeError::BadRequest {
+                        header: format!(
+                            "Different {} account already linked",
+                            Self::USER_ACCOUNT_TY
+                        ),
+                        message: format!(
+                            "This account is already linked to the {} account {}. Please \
+                            unlink it before linking a different one. If you did not link this \
+                            account please contact a coordinator.",
+                            Self::USER_ACCOUNT_TY,
+                            existing
+                        ),
+                        show_status_code: false,
+                    });
+                }
+
+                Some(_) => {}
+            }
+
+            Ok(HttpResponse::Found()
+                .header(LOCATION, profile_for(rcos_username.as_str()))
+                .finish())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey};
+    use serde_json::json;
+
+    const ISSUER: &'static str = "https://sso.example.edu";
+    const CLIENT_ID: &'static str = "telescope";
+    const NONCE: &'static str = "test-nonce";
+
+    /// The key the test identity provider signs ID tokens with. Its public half is in the
+    /// key set.
+    const SIGNING_KEY: &'static str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/oidc/signing_key.pem"
+    ));
+
+    /// A key that is not in the key set.
+    const OTHER_KEY: &'static str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/oidc/other_key.pem"
+    ));
+
+    fn key_set() -> JsonWebKeySet {
+        serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/oidc/jwks.json"
+        )))
+        .expect("Invalid test key set")
+    }
+
+    /// Claims of a valid ID token, expiring in ten minutes.
+    fn claims() -> Value {
+        json!({
+            "iss": ISSUER,
+            "aud": CLIENT_ID,
+            "sub": "12345",
+            "nonce": NONCE,
+            "iat": Utc::now().timestamp(),
+            "exp": (Utc::now() + Duration::minutes(10)).timestamp(),
+        })
+    }
+
+    /// Sign an ID token with an RSA key.
+    fn sign(claims: &Value, key: &str) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some("test-key".into());
+        let key = EncodingKey::from_rsa_pem(key.as_bytes()).expect("Invalid test key");
+        encode(&header, claims, &key).expect("Could not sign test ID token")
+    }
+
+    fn check(id_token: &str) -> Result<Map<String, Value>, TelescopeError> {
+        check_id_token(&key_set(), ISSUER, CLIENT_ID, id_token, NONCE)
+    }
+
+    #[test]
+    fn accepts_valid_token() {
+        let claims = check(sign(&claims(), SIGNING_KEY).as_str()).expect("Valid token rejected");
+        assert_eq!(claims.get("sub").and_then(Value::as_str), Some("12345"));
+    }
+
+    #[test]
+    fn rejects_bad_signature() {
+        assert!(check(sign(&claims(), OTHER_KEY).as_str()).is_err());
+    }
+
+    #[test]
+    fn rejects_symmetric_signature() {
+        let key = EncodingKey::from_secret(b"secret");
+        let token = encode(&Header::new(Algorithm::HS256), &claims(), &key).unwrap();
+        assert!(check(token.as_str()).is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_nonce() {
+        let mut claims = claims();
+        claims["nonce"] = json!("other-nonce");
+        assert!(check(sign(&claims, SIGNING_KEY).as_str()).is_err());
+    }
+
+    #[test]
+    fn rejects_missing_nonce() {
+        let mut claims = claims();
+        claims.as_object_mut().unwrap().remove("nonce");
+        assert!(check(sign(&claims, SIGNING_KEY).as_str()).is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_audience() {
+        let mut claims = claims();
+        claims["aud"] = json!("other-client");
+        assert!(check(sign(&claims, SIGNING_KEY).as_str()).is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_issuer() {
+        let mut claims = claims();
+        claims["iss"] = json!("https://attacker.example.com");
+        assert!(check(sign(&claims, SIGNING_KEY).as_str()).is_err());
+    }
+
+    #[test]
+    fn rejects_expired_token() {
+        let mut claims = claims();
+        claims["exp"] = json!((Utc::now() - Duration::minutes(10)).timestamp());
+        assert!(check(sign(&claims, SIGNING_KEY).as_str()).is_err());
+    }
+
+    #[test]
+    fn accepts_token_within_clock_skew() {
+        let mut claims = claims();
+        claims["exp"] = json!((Utc::now() - Duration::seconds(30)).timestamp());
+        assert!(check(sign(&claims, SIGNING_KEY).as_str()).is_ok());
+    }
+}
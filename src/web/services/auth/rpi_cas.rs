@@ -7,8 +7,10 @@ use crate::api::rcos::users::accounts::link::LinkUserAccount;
 use crate::api::rcos::users::accounts::lookup::AccountLookup;
 use crate::api::rcos::users::accounts::reverse_lookup::ReverseLookup;
 use crate::api::rcos::users::UserAccountType;
+use crate::env::global_config;
 use crate::error::TelescopeError;
 use crate::web::profile_for;
+use crate::web::services::auth::cas_response::{self, CasProfile, CasResponse};
 use crate::web::services::auth::identity::{AuthenticationCookie, RootIdentity};
 use crate::web::services::auth::{identity::Identity, make_redirect_url, IdentityProvider};
 use actix_web::http::header::LOCATION;
@@ -16,10 +18,6 @@ use actix_web::{web::Query, FromRequest};
 use actix_web::{HttpRequest, HttpResponse};
 use futures::future::LocalBoxFuture;
 use futures::future::{ready, Ready};
-use regex::Regex;
-
-/// The URL of the RPI CAS server.
-const RPI_CAS_ENDPOINT: &'static str = "https://cas.auth.rpi.edu/cas";
 
 /// Query parameters of the request sent to Telescope after
 /// the user is authenticated with RPI CAS.
@@ -36,39 +34,17 @@ struct CasIdentificationParameters {
     service: String,
 }
 
-/// Function to make CAS user regular expression.
-fn make_cas_user_regex() -> Regex {
-    // Don't validate the RPI CAS XML too much. Just look for the user tag
-    // in the cas namespace and capture its value.
-    Regex::new(r"<cas:user>([[:alnum:]]+)</cas:user>").expect("Could not create CAS RCS ID Regex")
-}
-
-lazy_static! {
-    static ref CAS_USER_REGEX: Regex = make_cas_user_regex();
-}
-
-/// Use the CAS user id regular expression to extract the RCS ID of a user from
-/// the XML returned by the CAS service.
-fn extract_rcs_id(xml: &str) -> Option<String> {
-    Some(
-        CAS_USER_REGEX
-            .captures(xml)?
-            // The first capture should be the RCS ID.
-            .get(1)?
-            // Get the string
-            .as_str()
-            // Convert to lowercase.
-            .to_lowercase(),
-    )
-}
-
-/// The RPI CAS based identity object just stores the user's RCS id directly.
-/// We do not get any other information from the RPI CAS service and the user's
+/// The RPI CAS based identity object stores the user's RCS id directly, since the user's
 /// RCS id should never change.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RpiCasIdentity {
     /// The authenticated RCS ID of the user with this cookie.
     pub rcs_id: String,
+
+    /// The profile released by the CAS server when the user signed in, if any. This is used
+    /// to pre-fill the registration form.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<CasProfile>,
 }
 
 impl RpiCasIdentity {
@@ -85,12 +61,12 @@ impl RpiCasIdentity {
 
 /// After the user has authenticated with CAS it will send them back to telescope
 /// with a service ticket. This function will extract the service ticket and
-/// use it to access the user's information via CAS. On success, this function return's the
-/// user's RCS ID as a string (in lowercase).
+/// use it to access the user's information via CAS. On success, this function returns the
+/// user's RCS ID (in lowercase) along with any profile attributes the CAS server released.
 async fn cas_authenticated(
     req: &HttpRequest,
     redir_path: String,
-) -> Result<String, TelescopeError> {
+) -> Result<RpiCasIdentity, TelescopeError> {
     // Extract the CAS parameters from the query
     let Query(params): Query<CasAuthenticatedParameters> =
         Query::<CasAuthenticatedParameters>::extract(req)
@@ -127,8 +103,11 @@ async fn cas_authenticated(
     let validation_query: String = serde_urlencoded::to_string(validation_params)
         .expect("Could not URL-encode CAS validation parameters");
     // Build the endpoint to query for user info.
-    let validation_url: String =
-        format!("{}/serviceValidate?{}", RPI_CAS_ENDPOINT, validation_query);
+    let validation_url: String = format!(
+        "{}/serviceValidate?{}",
+        global_config().cas_url,
+        validation_query
+    );
 
     // Send request to CAS service and wait for response.
     let cas_xml: String = reqwest::get(validation_url.as_str())
@@ -138,11 +117,34 @@ async fn cas_authenticated(
         .await
         .map_err(TelescopeError::rpi_cas_error)?;
 
-    // Extract and return the RCS id.
-    return extract_rcs_id(cas_xml.as_str()).ok_or(TelescopeError::RpiCasError(format!(
-        "Could not extract RCS ID from RPI CAS response. Response xml: {}",
-        cas_xml
-    )));
+    // Parse the response and map failures to errors.
+    let success = match cas_response::parse(cas_xml.as_str())? {
+        CasResponse::Success(success) => success,
+        CasResponse::Failure(failure) => {
+            warn!(
+                "RPI CAS rejected service ticket ({:?}): {}",
+                failure.code, failure.description
+            );
+            return Err(failure.into_error());
+        }
+    };
+
+    // We never request proxy granting tickets, so a proxied ticket means someone else
+    // authenticated this user.
+    if !success.proxies.is_empty() {
+        return Err(TelescopeError::CasTicketRejected {
+            code: "PROXIED_TICKET".into(),
+            description: format!(
+                "The ticket was issued through the proxies {}.",
+                success.proxies.join(", ")
+            ),
+        });
+    }
+
+    return Ok(RpiCasIdentity {
+        rcs_id: success.user.to_lowercase(),
+        profile: success.profile(),
+    });
 }
 
 /// Make the url to redirect users to when authenticating.
@@ -155,7 +157,7 @@ fn make_authentication_url(req: &HttpRequest, redir_path: String) -> String {
         .expect("Could not URL-encode CAS parameters.");
 
     // Build the CAS URL.
-    return format!("{}/login?{}", RPI_CAS_ENDPOINT, encoded);
+    return format!("{}/login?{}", global_config().cas_url, encoded);
 }
 
 /// Zero-Sized struct representing the RPI CAS identity provider
@@ -228,9 +230,9 @@ impl IdentityProvider for RpiCas {
     fn login_authenticated_handler(req: HttpRequest) -> Self::LoginAuthenticatedFut {
         return Box::pin(async move {
             // Get the RCS ID of the user logging in.
-            let rcs_id: String = cas_authenticated(&req, Self::login_redirect_path()).await?;
+            let token: RpiCasIdentity =
+                cas_authenticated(&req, Self::login_redirect_path()).await?;
             // Get the RCOS username of the account linked to this RCS id.
-            let token = RpiCasIdentity { rcs_id };
             let rcos_username: String = token
                 .get_rcos_username()
                 .await?
@@ -246,7 +248,9 @@ impl IdentityProvider for RpiCas {
 
             // Set the user's identity cookie
             let identity: Identity = Identity::extract(&req).await?;
-            identity.save(&RootIdentity::RpiCas(token).make_authenticated_cookie());
+            identity
+                .save(&RootIdentity::RpiCas(token).make_authenticated_cookie())
+                .await?;
             // Redirect the user to their profile.
             Ok(HttpResponse::Found()
                 .header(LOCATION, profile_for(rcos_username.as_str()))
@@ -256,14 +260,14 @@ impl IdentityProvider for RpiCas {
 
     fn registration_authenticated_handler(req: HttpRequest) -> Self::RegistrationAuthenticatedFut {
         return Box::pin(async move {
-            // Authenticate with the RPI CAS service and extract the user's RCS ID.
-            let rcs_id: String =
+            // Authenticate with the RPI CAS service and extract the user's RCS ID and profile.
+            let token: RpiCasIdentity =
                 cas_authenticated(&req, Self::registration_redirect_path()).await?;
             // Put the RCS ID in an identity cookie.
-            let cookie: RootIdentity = RootIdentity::RpiCas(RpiCasIdentity { rcs_id });
+            let cookie: RootIdentity = RootIdentity::RpiCas(token);
             // Give the cookie to the user
             let identity: Identity = Identity::extract(&req).await?;
-            identity.save(&cookie.make_authenticated_cookie());
+            identity.save(&cookie.make_authenticated_cookie()).await?;
             // Redirect the user to complete registration
             Ok(HttpResponse::Found()
                 .header(LOCATION, "/register/finish")
@@ -290,7 +294,9 @@ impl IdentityProvider for RpiCas {
                 AccountLookup::send(rcos_username.clone(), Self::USER_ACCOUNT_TY).await?;
 
             // Get the RCS ID from the authenticated RPI CAS response.
-            let new_rcs_id: String = cas_authenticated(&req, Self::link_redirect_path()).await?;
+            let new_rcs_id: String = cas_authenticated(&req, Self::link_redirect_path())
+                .await?
+                .rcs_id;
 
             // We add the new RCS ID to the database for any user who doesn't have one.
             let add_new_to_db: bool = existing_rcs_id.is_none();
@@ -1,5 +1,6 @@
 //! Module for serving the RCOS homepage.
 
+use crate::api::rcos::announcements::ActiveAnnouncements;
 use crate::api::rcos::landing_page_stats::LandingPageStatistics;
 use crate::error::TelescopeError;
 use crate::templates::Template;
@@ -13,10 +14,13 @@ const TEMPLATE_PATH: &'static str = "index";
 pub async fn index(req: HttpRequest) -> Result<Template, TelescopeError> {
     // Get the statistics.
     let stats = LandingPageStatistics::get().await?;
+    // Get the announcements that have not expired.
+    let announcements = ActiveAnnouncements::get().await?;
 
-    // Make and return a template with the statistics.
+    // Make and return a template with the statistics and announcements.
     Template::new(TEMPLATE_PATH)
         .field("stats", stats)
+        .field("announcements", announcements)
         .render_into_page(&req, "RCOS")
         .await
 }
@@ -0,0 +1,238 @@
+//! Meeting check-in services.
+//!
+//! Hosts (and anyone else who can edit a meeting) generate an attendance code from the
+//! meeting's attendance page and share it with the people at the meeting. While the meeting
+//! is running, attendees submit the code at `/meeting/{id}/attend` to record their attendance.
+
+use crate::api::rcos::meetings::attendance::{
+    meeting_attendance::MeetingAttendanceMeeting, MeetingAttendance, RecordAttendance,
+    SetAttendanceCode,
+};
+use crate::api::rcos::meetings::authorization_for::{AuthorizationFor, UserMeetingAuthorization};
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::templates::Template;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+use chrono::Utc;
+use rand::Rng;
+
+/// The handlebars template for the host's view of meeting attendance.
+const HOST_TEMPLATE: &'static str = "meetings/attendance/host";
+
+/// The handlebars template for the check-in form.
+const CHECK_IN_FORM: &'static str = "meetings/attendance/attend";
+
+/// Characters used in generated attendance codes. Characters that are easy to mix up
+/// when read off of a projector (`0`/`O`, `1`/`I`/`L`) are left out.
+const CODE_CHARSET: &'static [u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+
+/// The length of generated attendance codes.
+const CODE_LENGTH: usize = 6;
+
+/// Register meeting attendance services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(attendance_page)
+        .service(rotate_code)
+        .service(close_check_in)
+        .service(check_in_page)
+        .service(submit_check_in);
+}
+
+/// Generate a new random attendance code.
+fn generate_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..CODE_LENGTH)
+        .map(|_| CODE_CHARSET[rng.gen_range(0..CODE_CHARSET.len())] as char)
+        .collect()
+}
+
+/// Normalize a submitted attendance code for comparison.
+fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+/// Get a meeting's attendance data or return a resource not found error.
+async fn get_attendance_data(meeting_id: i64) -> Result<MeetingAttendanceMeeting, TelescopeError> {
+    MeetingAttendance::get(meeting_id)
+        .await?
+        .ok_or(TelescopeError::resource_not_found(
+            "Meeting Not Found",
+            "Could not find a meeting for this ID.",
+        ))
+}
+
+/// Error if the authenticated user cannot edit the meeting.
+async fn require_edit_access(
+    auth: &AuthenticationCookie,
+    meeting_id: i64,
+) -> Result<(), TelescopeError> {
+    let viewer: String = auth.get_rcos_username_or_error().await?;
+    AuthorizationFor::get(Some(viewer))
+        .await?
+        .can_edit_by_id(meeting_id)
+        .await?
+        .then(|| ())
+        .ok_or(TelescopeError::Forbidden)
+}
+
+/// Is this meeting currently running?
+fn is_ongoing(meeting: &MeetingAttendanceMeeting) -> bool {
+    let now = Utc::now();
+    meeting.start_date_time <= now && now <= meeting.end_date_time
+}
+
+/// Page for meeting hosts to manage the attendance code and see who has checked in.
+#[get("/meeting/{meeting_id}/attendance")]
+async fn attendance_page(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+    Path(meeting_id): Path<i64>,
+) -> Result<Template, TelescopeError> {
+    // Only hosts, coordinators and faculty advisors can see the attendance code.
+    require_edit_access(&auth, meeting_id).await?;
+    let meeting: MeetingAttendanceMeeting = get_attendance_data(meeting_id).await?;
+
+    return Template::new(HOST_TEMPLATE)
+        .field("is_ongoing", is_ongoing(&meeting))
+        .field("meeting", meeting)
+        .render_into_page(&req, "Meeting Attendance")
+        .await;
+}
+
+/// Generate a new attendance code for a meeting, replacing any existing one.
+#[post("/meeting/{meeting_id}/attendance/code")]
+async fn rotate_code(
+    auth: AuthenticationCookie,
+    Path(meeting_id): Path<i64>,
+) -> Result<HttpResponse, TelescopeError> {
+    require_edit_access(&auth, meeting_id).await?;
+    SetAttendanceCode::execute(meeting_id, Some(generate_code()))
+        .await?
+        .ok_or(TelescopeError::resource_not_found(
+            "Meeting Not Found",
+            "Could not find a meeting for this ID.",
+        ))?;
+
+    // Send the host back to the attendance page to see the new code.
+    Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/meeting/{}/attendance", meeting_id))
+        .finish())
+}
+
+/// Clear a meeting's attendance code, preventing any further check-ins.
+#[post("/meeting/{meeting_id}/attendance/close")]
+async fn close_check_in(
+    auth: AuthenticationCookie,
+    Path(meeting_id): Path<i64>,
+) -> Result<HttpResponse, TelescopeError> {
+    require_edit_access(&auth, meeting_id).await?;
+    SetAttendanceCode::execute(meeting_id, None).await?.ok_or(
+        TelescopeError::resource_not_found(
+            "Meeting Not Found",
+            "Could not find a meeting for this ID.",
+        ),
+    )?;
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/meeting/{}/attendance", meeting_id))
+        .finish())
+}
+
+/// Get a meeting's attendance data and check that the viewer can see the meeting.
+async fn visible_meeting(
+    auth: &AuthenticationCookie,
+    meeting_id: i64,
+) -> Result<MeetingAttendanceMeeting, TelescopeError> {
+    let viewer: String = auth.get_rcos_username_or_error().await?;
+    let authorization: UserMeetingAuthorization = AuthorizationFor::get(Some(viewer)).await?;
+    let meeting: MeetingAttendanceMeeting = get_attendance_data(meeting_id).await?;
+
+    // Users cannot check in to meetings they cannot see.
+    if (meeting.is_draft && !authorization.can_view_drafts())
+        || !authorization.can_view(meeting.type_)
+    {
+        return Err(TelescopeError::Forbidden);
+    }
+
+    return Ok(meeting);
+}
+
+/// Make the check-in form for a meeting. This intentionally leaves out the attendance code.
+fn make_check_in_form(meeting: &MeetingAttendanceMeeting) -> FormTemplate {
+    let mut form = FormTemplate::new(CHECK_IN_FORM, "Meeting Check-In");
+    form.template = json!({
+        "meeting": {
+            "meeting_id": meeting.meeting_id,
+            "title": &meeting.title,
+            "type": meeting.type_,
+            "is_draft": meeting.is_draft,
+            "start_date_time": meeting.start_date_time,
+            "end_date_time": meeting.end_date_time,
+        },
+        "is_ongoing": is_ongoing(meeting),
+        "is_open": meeting.attendance_code.is_some()
+    });
+    return form;
+}
+
+/// Check-in form for meeting attendees.
+#[get("/meeting/{meeting_id}/attend")]
+async fn check_in_page(
+    auth: AuthenticationCookie,
+    Path(meeting_id): Path<i64>,
+) -> Result<FormTemplate, TelescopeError> {
+    let meeting = visible_meeting(&auth, meeting_id).await?;
+    return Ok(make_check_in_form(&meeting));
+}
+
+/// Form submitted by attendees to check in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CheckInForm {
+    /// The attendance code given out by the host.
+    code: String,
+}
+
+/// Endpoint that attendees submit the check-in form to.
+#[post("/meeting/{meeting_id}/attend")]
+async fn submit_check_in(
+    auth: AuthenticationCookie,
+    Path(meeting_id): Path<i64>,
+    Form(CheckInForm { code }): Form<CheckInForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let meeting = visible_meeting(&auth, meeting_id).await?;
+
+    // Create a form to send back to the user if there is an issue.
+    let mut form: FormTemplate = make_check_in_form(&meeting);
+    form.template["code"] = json!(&code);
+
+    // Check-in is only allowed while the meeting is running.
+    if !is_ongoing(&meeting) {
+        form.template["issues"]["code"] = json!("This meeting is not currently running.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    // Check the submitted code against the meeting's code.
+    let matches: bool = meeting
+        .attendance_code
+        .as_ref()
+        .map(|expected| normalize_code(expected) == normalize_code(code.as_str()))
+        .unwrap_or(false);
+
+    if !matches {
+        form.template["issues"]["code"] = json!("Incorrect attendance code.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    // Record attendance. Checking in more than once is not an error.
+    RecordAttendance::execute(meeting_id, username).await?;
+
+    // Send the user back to the meeting page.
+    return Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/meeting/{}", meeting_id))
+        .finish());
+}
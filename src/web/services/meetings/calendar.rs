@@ -0,0 +1,348 @@
+//! iCalendar (.ics) feed of the meetings visible to a user.
+//!
+//! Calendar clients poll subscriptions without any cookies, so every user gets a personal
+//! subscription URL with a signed token in it. The token only identifies the user to this
+//! feed -- it cannot be used to sign in or to query the RCOS API. Tokens are signed with their
+//! own secret, expire, and carry the user's token version, which the user can increment to
+//! revoke every link issued before.
+
+use crate::api::rcos::meetings::authorization_for::{AuthorizationFor, UserMeetingAuthorization};
+use crate::api::rcos::meetings::get::{meetings::MeetingsMeetings, Meetings};
+use crate::api::rcos::meetings::get_by_id::meeting_title;
+use crate::env::global_config;
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::{AuthenticationCookie, Identity};
+use crate::web::services::meetings::calendar_resets;
+use actix_web::http::header::{CACHE_CONTROL, CONTENT_TYPE, LOCATION};
+use actix_web::web::{Query, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rand::distributions::Alphanumeric;
+use rand::rngs::OsRng;
+use rand::Rng;
+use url::Url;
+
+/// The handlebars template for the calendar subscription page.
+const TEMPLATE_PATH: &'static str = "meetings/calendar";
+
+/// The issuer claim on calendar subscription tokens.
+const TOKEN_ISSUER: &'static str = "telescope";
+
+/// The audience claim on calendar subscription tokens. This keeps them from being
+/// confused with any other JWT that telescope signs.
+const TOKEN_AUDIENCE: &'static str = "telescope-calendar";
+
+/// How long calendar subscription tokens are valid for. Visiting the subscription page issues
+/// a new one.
+const TOKEN_LIFETIME_DAYS: i64 = 365;
+
+/// How far back the feed includes meetings.
+const FEED_PAST_WEEKS: i64 = 4;
+
+/// How far ahead the feed includes meetings.
+const FEED_FUTURE_WEEKS: i64 = 26;
+
+/// Register calendar feed services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(subscription_page)
+        .service(reset_subscription)
+        .service(feed);
+}
+
+lazy_static! {
+    /// The secret calendar tokens are signed with. This is never the JWT secret used for
+    /// the RCOS API.
+    static ref CALENDAR_SECRET: String = global_config()
+        .calendar_secret
+        .clone()
+        .unwrap_or_else(|| {
+            warn!("No calendar secret configured. Using a random secret. Calendar subscription links will stop working when Telescope restarts.");
+            OsRng::default()
+                .sample_iter(&Alphanumeric)
+                .take(48)
+                .map(char::from)
+                .collect()
+        });
+}
+
+/// Claims in a calendar subscription token.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CalendarTokenClaims {
+    /// Always [`TOKEN_ISSUER`].
+    iss: String,
+    /// Always [`TOKEN_AUDIENCE`].
+    aud: String,
+    /// The username of the user this feed is for.
+    sub: String,
+    /// When this token was issued (UNIX time).
+    iat: i64,
+    /// When this token expires (UNIX time).
+    exp: i64,
+    /// The user's calendar token version when this token was issued.
+    ver: i64,
+}
+
+impl CalendarTokenClaims {
+    /// Create and sign a subscription token for a user.
+    async fn issue(username: String) -> Result<(String, DateTime<Utc>), TelescopeError> {
+        let version: i64 = calendar_resets::token_version(username.as_str()).await?;
+
+        let now: DateTime<Utc> = Utc::now();
+        let expires: DateTime<Utc> = now + Duration::days(TOKEN_LIFETIME_DAYS);
+        let claims = CalendarTokenClaims {
+            iss: TOKEN_ISSUER.to_string(),
+            aud: TOKEN_AUDIENCE.to_string(),
+            sub: username,
+            iat: now.timestamp(),
+            exp: expires.timestamp(),
+            ver: version,
+        };
+
+        let token: String = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(CALENDAR_SECRET.as_bytes()),
+        )
+        .map_err(|e| TelescopeError::ise(format!("Could not encode calendar token: {}", e)))?;
+        return Ok((token, expires));
+    }
+
+    /// Verify a subscription token and return the username it was issued for. Tokens from
+    /// before the user's last reset are rejected.
+    async fn verify(token: &str) -> Result<String, TelescopeError> {
+        let invalid = || TelescopeError::BadRequest {
+            header: "Invalid Calendar Token".into(),
+            message: "This calendar subscription link is not valid. It may have expired or \
+            been reset. Please get a new one from the meetings page."
+                .into(),
+            show_status_code: true,
+        };
+
+        let mut validation = Validation::default();
+        validation.iss = Some(TOKEN_ISSUER.to_string());
+        validation.set_audience(&[TOKEN_AUDIENCE]);
+
+        let claims: CalendarTokenClaims = decode::<CalendarTokenClaims>(
+            token,
+            &DecodingKey::from_secret(CALENDAR_SECRET.as_bytes()),
+            &validation,
+        )
+        .map_err(|_| invalid())?
+        .claims;
+
+        let version: i64 = calendar_resets::token_version(claims.sub.as_str()).await?;
+        if version != claims.ver {
+            return Err(invalid());
+        }
+        return Ok(claims.sub);
+    }
+}
+
+/// Page that shows the authenticated user their personal subscription URL.
+#[get("/meetings/calendar")]
+async fn subscription_page(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+) -> Result<Template, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let (token, expires) = CalendarTokenClaims::issue(username).await?;
+
+    // Build the subscription URL from the public URL of this Telescope instance.
+    let base_url: String = global_config()
+        .discord_config
+        .telescope_url
+        .trim_end_matches('/')
+        .to_string();
+    let feed_url: String = format!("{}/meetings/calendar.ics?token={}", base_url, token);
+    // Most calendar clients treat webcal:// links as subscriptions.
+    let webcal_url: String =
+        feed_url
+            .replacen("https://", "webcal://", 1)
+            .replacen("http://", "webcal://", 1);
+
+    return Template::new(TEMPLATE_PATH)
+        .field("feed_url", feed_url)
+        .field("webcal_url", webcal_url)
+        .field("expires", expires)
+        .render_into_page(&req, "Meeting Calendar")
+        .await;
+}
+
+/// Revoke all of the authenticated user's subscription links and show them a new one.
+#[post("/meetings/calendar/reset")]
+async fn reset_subscription(auth: AuthenticationCookie) -> Result<HttpResponse, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    calendar_resets::reset(username.as_str()).await?;
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/meetings/calendar")
+        .finish())
+}
+
+/// Query parameters on the calendar feed.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct FeedQuery {
+    /// The subscription token. If this is missing, the identity cookie is used instead.
+    token: Option<String>,
+}
+
+/// The iCalendar feed of meetings.
+#[get("/meetings/calendar.ics")]
+async fn feed(
+    identity: Identity,
+    Query(FeedQuery { token }): Query<FeedQuery>,
+) -> Result<HttpResponse, TelescopeError> {
+    // Resolve the user this feed is for.
+    let username: Option<String> = match token {
+        Some(token) => Some(CalendarTokenClaims::verify(token.as_str()).await?),
+        None => identity.get_rcos_username().await?,
+    };
+
+    // Filter meetings the same way as the meetings page.
+    let authorization: UserMeetingAuthorization = AuthorizationFor::get(username).await?;
+    let start: DateTime<Utc> = Utc::now() - Duration::weeks(FEED_PAST_WEEKS);
+    let end: DateTime<Utc> = Utc::now() + Duration::weeks(FEED_FUTURE_WEEKS);
+    let meetings: Vec<MeetingsMeetings> = Meetings::get(
+        start,
+        end,
+        authorization.can_view_drafts(),
+        authorization.viewable_types(),
+    )
+    .await?;
+
+    let body: String = make_calendar(meetings.as_slice());
+    return Ok(HttpResponse::Ok()
+        .header(CONTENT_TYPE, "text/calendar;charset=UTF-8")
+        .header(CACHE_CONTROL, "no-cache")
+        .body(body));
+}
+
+/// Escape a text value as described in RFC 5545 section 3.3.11.
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Format a timestamp as an iCalendar UTC date-time.
+fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
+    timestamp.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Append a content line to the calendar, folding it to 75 octets per line as required
+/// by RFC 5545 section 3.1.
+fn push_line(calendar: &mut String, line: &str) {
+    let mut line_length: usize = 0;
+    for c in line.chars() {
+        if line_length + c.len_utf8() > 75 {
+            // Continuation lines start with a single space.
+            calendar.push_str("\r\n ");
+            line_length = 1;
+        }
+        calendar.push(c);
+        line_length += c.len_utf8();
+    }
+    calendar.push_str("\r\n");
+}
+
+/// Build an iCalendar document containing a VEVENT for each meeting.
+fn make_calendar(meetings: &[MeetingsMeetings]) -> String {
+    let base_url: String = global_config()
+        .discord_config
+        .telescope_url
+        .trim_end_matches('/')
+        .to_string();
+    let now: String = format_timestamp(&Utc::now());
+    // Event UIDs are scoped to the host of this Telescope instance.
+    let uid_host: String = Url::parse(base_url.as_str())
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "telescope".to_string());
+
+    let mut calendar = String::new();
+    push_line(&mut calendar, "BEGIN:VCALENDAR");
+    push_line(&mut calendar, "VERSION:2.0");
+    push_line(&mut calendar, "PRODID:-//RCOS//Telescope//EN");
+    push_line(&mut calendar, "CALSCALE:GREGORIAN");
+    push_line(&mut calendar, "METHOD:PUBLISH");
+    push_line(&mut calendar, "X-WR-CALNAME:RCOS Meetings");
+
+    for meeting in meetings {
+        let meeting_page: String = format!("{}/meeting/{}", base_url, meeting.meeting_id);
+
+        // Build the description from the meeting description, host, and links.
+        let mut description: Vec<String> = Vec::new();
+        if let Some(host) = meeting.host.as_ref() {
+            description.push(format!("Hosted by {} {}", host.first_name, host.last_name));
+        }
+        if let Some(url) = meeting.meeting_url.as_ref() {
+            description.push(format!("Join: {}", url));
+        }
+        if !meeting.description.trim().is_empty() {
+            description.push(meeting.description.trim().to_string());
+        }
+        description.push(meeting_page.clone());
+
+        // Use the physical location if there is one, otherwise the meeting URL.
+        let location: Option<&String> = meeting.location.as_ref().or(meeting.meeting_url.as_ref());
+
+        push_line(&mut calendar, "BEGIN:VEVENT");
+        push_line(
+            &mut calendar,
+            format!("UID:meeting-{}@{}", meeting.meeting_id, uid_host).as_str(),
+        );
+        push_line(&mut calendar, format!("DTSTAMP:{}", now).as_str());
+        push_line(
+            &mut calendar,
+            format!("DTSTART:{}", format_timestamp(&meeting.start_date_time)).as_str(),
+        );
+        push_line(
+            &mut calendar,
+            format!("DTEND:{}", format_timestamp(&meeting.end_date_time)).as_str(),
+        );
+        push_line(
+            &mut calendar,
+            format!("SUMMARY:{}", escape_text(meeting.title().as_str())).as_str(),
+        );
+        if let Some(location) = location {
+            push_line(
+                &mut calendar,
+                format!("LOCATION:{}", escape_text(location.as_str())).as_str(),
+            );
+        }
+        push_line(
+            &mut calendar,
+            format!(
+                "DESCRIPTION:{}",
+                escape_text(description.join("\n\n").as_str())
+            )
+            .as_str(),
+        );
+        push_line(&mut calendar, format!("URL:{}", meeting_page).as_str());
+        push_line(
+            &mut calendar,
+            format!(
+                "CATEGORIES:{}",
+                escape_text(meeting.type_.to_string().as_str())
+            )
+            .as_str(),
+        );
+        // Drafts are only visible to coordinators, but mark them as tentative anyways.
+        push_line(
+            &mut calendar,
+            if meeting.is_draft {
+                "STATUS:TENTATIVE"
+            } else {
+                "STATUS:CONFIRMED"
+            },
+        );
+        push_line(&mut calendar, "END:VEVENT");
+    }
+
+    push_line(&mut calendar, "END:VCALENDAR");
+    return calendar;
+}
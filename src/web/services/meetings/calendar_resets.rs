@@ -0,0 +1,79 @@
+//! Resets of calendar subscription links.
+//!
+//! The RCOS database does not have a column for this, so every reset is appended to a JSON
+//! lines file (one JSON object per line) at the path given by the `calendar_resets_path`
+//! config option, like the audit log. A user's calendar token version is the number of times
+//! they have reset their links.
+
+use crate::env::global_config;
+use crate::error::TelescopeError;
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::sync::Mutex;
+
+lazy_static! {
+    /// Lock held while writing to the resets file, so that concurrent resets do not interleave.
+    static ref CALENDAR_RESETS_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// A single reset of a user's calendar subscription links.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CalendarReset {
+    /// The username of the user who reset their links.
+    username: String,
+    /// When the links were reset.
+    timestamp: DateTime<Utc>,
+}
+
+/// Get a user's calendar token version. The file is read on the blocking thread pool.
+pub async fn token_version(username: &str) -> Result<i64, TelescopeError> {
+    let username: String = username.to_string();
+    web::block(move || count_resets(username.as_str()))
+        .await
+        .map_err(|e| TelescopeError::ise(format!("Could not read calendar resets: {}", e)))
+}
+
+/// Count the resets of a user's calendar links. This blocks.
+fn count_resets(username: &str) -> Result<i64, io::Error> {
+    let file: File = match File::open(&global_config().calendar_resets_path) {
+        Ok(file) => file,
+        // Nobody has reset their links yet.
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+
+    let count: usize = BufReader::new(file)
+        .lines()
+        .filter_map(|line| line.ok())
+        .filter_map(|line| serde_json::from_str::<CalendarReset>(line.as_str()).ok())
+        .filter(|reset| reset.username == username)
+        .count();
+    return Ok(count as i64);
+}
+
+/// Reset a user's calendar links, incrementing their token version. The file is written on
+/// the blocking thread pool.
+pub async fn reset(username: &str) -> Result<(), TelescopeError> {
+    let line: String = serde_json::to_string(&CalendarReset {
+        username: username.to_string(),
+        timestamp: Utc::now(),
+    })
+    .map_err(|e| TelescopeError::ise(format!("Could not serialize calendar reset: {}", e)))?;
+
+    web::block(move || {
+        // Hold the lock while writing. A poisoned lock only means another write panicked.
+        let _guard = CALENDAR_RESETS_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&global_config().calendar_resets_path)
+            .and_then(|mut file| writeln!(file, "{}", line))
+    })
+    .await
+    .map_err(|e| TelescopeError::ise(format!("Could not reset calendar links: {}", e)))
+}
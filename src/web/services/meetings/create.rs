@@ -7,7 +7,7 @@
 
 use crate::api::rcos::meetings::authorization_for::UserMeetingAuthorization;
 use crate::api::rcos::meetings::creation;
-use crate::api::rcos::meetings::creation::create::CreateMeeting;
+use crate::api::rcos::meetings::creation::create::{create_meeting, create_meetings};
 use crate::api::rcos::meetings::creation::host_selection::HostSelection;
 use crate::api::rcos::meetings::{MeetingType, ALL_MEETING_TYPES};
 use crate::error::TelescopeError;
@@ -19,7 +19,7 @@ use actix_web::web as aweb;
 use actix_web::web::{Form, Query, ServiceConfig};
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
-use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use serde_json::Value;
 
 /// The handlebars template for the user to select a host.
@@ -28,6 +28,9 @@ const HOST_SELECTION_TEMPLATE: &'static str = "meetings/creation/host_selection"
 /// The handlebars template to finish the meeting creation process.
 const FINISH_CREATION_TEMPLATE: &'static str = "meetings/creation/finish";
 
+/// The most meetings that can be created by a single recurring meeting submission.
+const MAX_OCCURRENCES: usize = 52;
+
 /// Register meeting creation services.
 pub fn register(config: &mut ServiceConfig) {
     // Create meeting creation auth middleware.
@@ -146,6 +149,88 @@ pub struct FinishForm {
 
     #[serde(default)]
     pub is_draft: Option<bool>,
+
+    /// How often this meeting repeats. Only used on creation.
+    #[serde(default)]
+    pub recurrence: Recurrence,
+
+    /// The last date that a recurring meeting can occur on. Cannot be a [`NaiveDate`]
+    /// since the field is submitted empty for meetings that do not repeat.
+    #[serde(default)]
+    pub recurrence_end: Option<String>,
+
+    /// Dates (one per line or comma separated) to skip when creating a recurring meeting.
+    #[serde(default)]
+    pub skip_dates: Option<String>,
+
+    /// Should edits also apply to the following occurrences of this meeting?
+    /// Only used when editing.
+    #[serde(default)]
+    pub apply_to_following: Option<bool>,
+}
+
+/// How often a meeting repeats.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    /// The meeting happens once.
+    Once,
+    /// The meeting happens every week.
+    Weekly,
+    /// The meeting happens every other week.
+    Biweekly,
+}
+
+impl Default for Recurrence {
+    fn default() -> Self {
+        Recurrence::Once
+    }
+}
+
+impl Recurrence {
+    /// The time between occurrences of the meeting, if it repeats.
+    pub fn interval(self) -> Option<Duration> {
+        match self {
+            Recurrence::Once => None,
+            Recurrence::Weekly => Some(Duration::weeks(1)),
+            Recurrence::Biweekly => Some(Duration::weeks(2)),
+        }
+    }
+}
+
+/// Get the start dates of every occurrence of a meeting. Skipped dates are left out.
+fn occurrence_dates(
+    first: NaiveDate,
+    recurrence: Recurrence,
+    last: NaiveDate,
+    skipped: &[NaiveDate],
+) -> Vec<NaiveDate> {
+    let mut dates: Vec<NaiveDate> = Vec::new();
+    let mut date: NaiveDate = first;
+    loop {
+        if !skipped.contains(&date) {
+            dates.push(date);
+        }
+
+        // Step to the next occurrence if there is one.
+        match recurrence.interval() {
+            Some(interval) if date + interval <= last => date = date + interval,
+            _ => break,
+        }
+    }
+    return dates;
+}
+
+/// Parse a list of dates separated by commas or whitespace.
+fn parse_skip_dates(input: &str) -> Result<Vec<NaiveDate>, String> {
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            part.parse::<NaiveDate>()
+                .map_err(|_| format!("Could not parse \"{}\" as a date (YYYY-MM-DD).", part))
+        })
+        .collect()
 }
 
 /// Endpoint that users submit meeting creation forms to.
@@ -179,6 +264,10 @@ async fn submit_meeting(
         recording_url,
         external_slides_url,
         is_draft,
+        recurrence,
+        recurrence_end,
+        skip_dates,
+        ..
     } = form;
 
     // We assume that semester_id is valid, since it includes only options from the creation
@@ -276,52 +365,144 @@ async fn submit_meeting(
         return Err(TelescopeError::invalid_form(&return_form));
     }
 
-    // Ascribe local timezone.
-    let start: DateTime<Local> = Local
-        .from_local_datetime(&start)
-        // Expect that there is only one valid local time for this.
-        .single()
-        .ok_or(TelescopeError::BadRequest {
-            header: "Malformed Meeting Creation Form".into(),
-            message: "Could not ascribe local timezone to start timestamp.".into(),
-            show_status_code: false,
-        })?;
+    // Resolve the last date the meeting can repeat on. Meetings that happen once end
+    // on their first date.
+    let recurrence_end: NaiveDate = match recurrence {
+        Recurrence::Once => start_date,
+        _ => {
+            let parsed: Option<NaiveDate> = recurrence_end
+                .as_ref()
+                .and_then(|string| string.trim().parse::<NaiveDate>().ok());
+
+            match parsed {
+                Some(date) if date >= start_date => date,
+                Some(_) => {
+                    return_form.template["issues"]["recurrence_end"] =
+                        json!("Repeat until date is before the first meeting.");
+                    return Err(TelescopeError::invalid_form(&return_form));
+                }
+                None => {
+                    return_form.template["issues"]["recurrence_end"] =
+                        json!("Recurring meetings need a date to repeat until.");
+                    return Err(TelescopeError::invalid_form(&return_form));
+                }
+            }
+        }
+    };
+
+    // Parse the dates to skip.
+    let skip_dates: Vec<NaiveDate> = match parse_skip_dates(skip_dates.as_deref().unwrap_or("")) {
+        Ok(dates) => dates,
+        Err(issue) => {
+            return_form.template["issues"]["skip_dates"] = json!(issue);
+            return Err(TelescopeError::invalid_form(&return_form));
+        }
+    };
+
+    // Get the dates of every occurrence.
+    let occurrences: Vec<NaiveDate> = occurrence_dates(
+        start_date,
+        recurrence,
+        recurrence_end,
+        skip_dates.as_slice(),
+    );
 
-    let end: DateTime<Local> = Local
-        .from_local_datetime(&end)
-        // Expect that there is only one valid local time for this.
-        .single()
-        .ok_or(TelescopeError::BadRequest {
-            header: "Malformed Meeting Creation Form".into(),
-            message: "Could not ascribe local timezone to end timestamp.".into(),
-            show_status_code: false,
-        })?;
+    if occurrences.is_empty() {
+        return_form.template["issues"]["skip_dates"] = json!("Every occurrence is skipped.");
+        return Err(TelescopeError::invalid_form(&return_form));
+    }
 
-    // The rest of the fields are managed pretty tersely in the API call and do not need validation
-    // or feedback.
-    let created_meeting_id: i64 = CreateMeeting::execute(
-        host,
-        title,
-        start.with_timezone(&Utc),
-        end.with_timezone(&Utc),
-        description.trim().to_string(),
-        is_draft.unwrap_or(false),
-        is_remote.unwrap_or(false),
-        location.and_then(|string| (!string.trim().is_empty()).then(|| string.trim().to_string())),
-        meeting_url,
-        recording_url,
-        external_slides_url,
-        semester,
-        kind,
-    )
-    .await?
-    .ok_or(TelescopeError::ise(
-        "Meeting creation call did not return ID.",
-    ))?;
+    if occurrences.len() > MAX_OCCURRENCES {
+        return_form.template["issues"]["recurrence_end"] = json!(format!(
+            "This would create {} meetings. At most {} can be created at once.",
+            occurrences.len(),
+            MAX_OCCURRENCES
+        ));
+        return Err(TelescopeError::invalid_form(&return_form));
+    }
+
+    // Build the timestamps of every occurrence, checking that each one is inside the semester.
+    let mut timestamps: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::with_capacity(occurrences.len());
+    for date in occurrences {
+        // Shift the first occurrence by whole days. This keeps the local time of day
+        // the same across daylight savings changes.
+        let offset: Duration = date - start_date;
+        let occurrence_start: NaiveDateTime = start + offset;
+        let occurrence_end: NaiveDateTime = end + offset;
+
+        if occurrence_start.date() < semester_start || occurrence_end.date() > semester_end {
+            return_form.template["issues"]["recurrence_end"] = json!(format!(
+                "The meeting on {} is outside of the semester.",
+                date.format("%B %_d, %Y")
+            ));
+            return Err(TelescopeError::invalid_form(&return_form));
+        }
+
+        // Ascribe local timezone.
+        let occurrence_start: DateTime<Local> = Local
+            .from_local_datetime(&occurrence_start)
+            // Expect that there is only one valid local time for this.
+            .single()
+            .ok_or(TelescopeError::BadRequest {
+                header: "Malformed Meeting Creation Form".into(),
+                message: "Could not ascribe local timezone to start timestamp.".into(),
+                show_status_code: false,
+            })?;
+
+        let occurrence_end: DateTime<Local> = Local
+            .from_local_datetime(&occurrence_end)
+            // Expect that there is only one valid local time for this.
+            .single()
+            .ok_or(TelescopeError::BadRequest {
+                header: "Malformed Meeting Creation Form".into(),
+                message: "Could not ascribe local timezone to end timestamp.".into(),
+                show_status_code: false,
+            })?;
+
+        timestamps.push((
+            occurrence_start.with_timezone(&Utc),
+            occurrence_end.with_timezone(&Utc),
+        ));
+    }
 
-    // Redirect the user to the page for the meeting they created.
+    // Normalize the remaining fields once for all occurrences.
+    let description: String = description.trim().to_string();
+    let location: Option<String> =
+        location.and_then(|string| (!string.trim().is_empty()).then(|| string.trim().to_string()));
+
+    // The rest of the fields are managed pretty tersely in the API call and do not need validation
+    // or feedback. If any occurrence fails, the ones already created are removed again.
+    let meetings: Vec<create_meeting::Variables> = timestamps
+        .into_iter()
+        .map(|(start, end)| create_meeting::Variables {
+            host_username: host.clone(),
+            title: title.clone(),
+            start,
+            end,
+            description: description.clone(),
+            is_draft: is_draft.unwrap_or(false),
+            is_remote: is_remote.unwrap_or(false),
+            location: location.clone(),
+            meeting_url: meeting_url.clone(),
+            recording_url: recording_url.clone(),
+            external_slides_url: external_slides_url.clone(),
+            semester_id: semester.clone(),
+            kind,
+        })
+        .collect();
+
+    let first_meeting_id: i64 =
+        create_meetings(meetings)
+            .await?
+            .into_iter()
+            .min()
+            .ok_or(TelescopeError::ise(
+                "Meeting creation call did not return ID.",
+            ))?;
+
+    // Redirect the user to the page for the (first) meeting they created.
     return Ok(HttpResponse::Found()
-        .header(LOCATION, format!("/meeting/{}", created_meeting_id))
+        .header(LOCATION, format!("/meeting/{}", first_meeting_id))
         .finish());
 }
 
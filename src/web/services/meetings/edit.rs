@@ -1,7 +1,9 @@
 //! Services to support meeting edits.
 
 use crate::api::rcos::meetings::creation::create::normalize_url;
-use crate::api::rcos::meetings::edit::EditHostSelection;
+use crate::api::rcos::meetings::edit::{
+    following_meetings::FollowingMeetingsMeetings, EditHostSelection, FollowingMeetings,
+};
 use crate::api::rcos::meetings::ALL_MEETING_TYPES;
 use crate::api::rcos::meetings::{
     authorization_for::{AuthorizationFor, UserMeetingAuthorization},
@@ -20,7 +22,9 @@ use actix_web::{
     web::{Path, Query, ServiceConfig},
     HttpRequest, HttpResponse,
 };
-use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
 use serde_json::Value;
 
 /// The Handlebars file for the meeting edit form.
@@ -119,6 +123,37 @@ fn resolve_meeting_title(meeting_data: &MeetingMeeting) -> String {
     }
 }
 
+/// Find the later occurrences of a recurring meeting. Since the database does not track
+/// meeting series, these are the later meetings of the same type in the same semester with the
+/// same host and title, that start on the same weekday at the same time of day.
+async fn following_occurrences(
+    meeting_data: &MeetingMeeting,
+) -> Result<Vec<FollowingMeetingsMeetings>, TelescopeError> {
+    let original_start: DateTime<Local> = meeting_data.start_date_time.with_timezone(&Local);
+    let original_host: Option<&str> = meeting_data
+        .host
+        .as_ref()
+        .map(|host| host.username.as_str());
+
+    let candidates = FollowingMeetings::get(
+        meeting_data.semester.semester_id.clone(),
+        meeting_data.type_,
+        meeting_data.start_date_time,
+    )
+    .await?;
+
+    return Ok(candidates
+        .into_iter()
+        .filter(|candidate| {
+            let start: DateTime<Local> = candidate.start_date_time.with_timezone(&Local);
+            candidate.title == meeting_data.title
+                && candidate.host.as_ref().map(|host| host.username.as_str()) == original_host
+                && start.weekday() == original_start.weekday()
+                && start.time() == original_start.time()
+        })
+        .collect());
+}
+
 /// Create the form template for meeting edits.
 fn make_form(meeting_data: &MeetingMeeting) -> FormTemplate {
     // Resolve the meeting title.
@@ -207,6 +242,8 @@ async fn submit_meeting_edits(
         location,
         kind,
         title,
+        apply_to_following,
+        ..
     } = form_data;
 
     // Like the creation system, semester ID, meeting kind, and host username are not validated.
@@ -244,6 +281,7 @@ async fn submit_meeting_edits(
     let is_draft: bool = is_draft.unwrap_or(false);
     form.template["data"]["is_remote"] = json!(is_remote);
     form.template["data"]["is_draft"] = json!(is_draft);
+    form.template["data"]["apply_to_following"] = json!(apply_to_following);
 
     // Validate dates and set an issue in the form if there is one.
     // Get the selected semester info from the context object.
@@ -318,6 +356,102 @@ async fn submit_meeting_edits(
         show_status_code: false,
     })?;
 
+    // Extract the host from context object.
+    let host: Option<String> = form.template["context"]
+        .get("host")
+        .and_then(|host| host[0]["username"].as_str())
+        .map(|host| host.to_string());
+
+    // If these edits also apply to the following occurrences of the meeting, build those
+    // edits first, so that nothing is changed if any of them are invalid. Each edit is paired
+    // with one that restores the occurrence, in case a later edit fails.
+    let mut following_edits: Vec<(edit::edit_meeting::Variables, edit::edit_meeting::Variables)> =
+        Vec::new();
+    if apply_to_following.unwrap_or(false) {
+        // Move every occurrence by as many days as this one moved.
+        let day_shift: Duration = start_date
+            - meeting_data
+                .start_date_time
+                .with_timezone(&Local)
+                .naive_local()
+                .date();
+        let length_in_days: Duration = end_date - start_date;
+
+        for occurrence in following_occurrences(&meeting_data).await? {
+            let occurrence_start_date: NaiveDate = occurrence
+                .start_date_time
+                .with_timezone(&Local)
+                .naive_local()
+                .date()
+                + day_shift;
+            let occurrence_end_date: NaiveDate = occurrence_start_date + length_in_days;
+
+            // Every occurrence must stay inside the semester.
+            if occurrence_start_date < semester_start || occurrence_end_date > semester_end {
+                form.template["issues"]["start_date"] = json!(format!(
+                    "The occurrence on {} would move outside of the semester.",
+                    occurrence_start_date.format("%B %_d, %Y")
+                ));
+                return Err(TelescopeError::invalid_form(&form));
+            }
+
+            let occurrence_start: DateTime<Local> = timezone_adder(
+                &occurrence_start_date.and_time(start_time),
+            )
+            .ok_or(TelescopeError::BadRequest {
+                header: "Malformed Start Time".into(),
+                message: "Could not ascribe local timezone to start timestamp.".into(),
+                show_status_code: false,
+            })?;
+
+            let occurrence_end: DateTime<Local> = timezone_adder(
+                &occurrence_end_date.and_time(end_time),
+            )
+            .ok_or(TelescopeError::BadRequest {
+                header: "Malformed End Time".into(),
+                message: "Could not ascribe local timezone to end timestamp.".into(),
+                show_status_code: false,
+            })?;
+
+            let undo = edit::edit_meeting::Variables {
+                meeting_id: occurrence.meeting_id,
+                title: occurrence.title.clone(),
+                start: occurrence.start_date_time,
+                end: occurrence.end_date_time,
+                semester_id: meeting_data.semester.semester_id.clone(),
+                kind: meeting_data.type_,
+                description: occurrence.description,
+                is_remote: occurrence.is_remote,
+                is_draft: occurrence.is_draft,
+                meeting_url: occurrence.meeting_url,
+                location: occurrence.location,
+                external_slides_url: occurrence.external_presentation_url.clone(),
+                recording_url: occurrence.recording_url.clone(),
+                host: occurrence.host.map(|host| host.username),
+            };
+
+            let edit = edit::edit_meeting::Variables {
+                meeting_id: occurrence.meeting_id,
+                title: title.clone(),
+                start: occurrence_start.with_timezone(&Utc),
+                end: occurrence_end.with_timezone(&Utc),
+                semester_id: semester.clone(),
+                kind,
+                description: description.clone(),
+                is_remote,
+                is_draft,
+                meeting_url: normalize_url(meeting_url.clone()),
+                location: location.clone(),
+                // Recordings and slides belong to each occurrence, so keep the existing ones.
+                external_slides_url: normalize_url(occurrence.external_presentation_url),
+                recording_url: normalize_url(occurrence.recording_url),
+                host: host.clone(),
+            };
+
+            following_edits.push((edit, undo));
+        }
+    }
+
     // Create variables for mutation.
     let edit_mutation_variables = edit::edit_meeting::Variables {
         meeting_id,
@@ -333,17 +467,34 @@ async fn submit_meeting_edits(
         location,
         external_slides_url: normalize_url(external_slides_url),
         recording_url: normalize_url(recording_url),
-        // Extract the host from context object.
-        host: form.template["context"]
-            .get("host")
-            .and_then(|host| host[0]["username"].as_str())
-            .map(|host| host.to_string()),
+        host,
     };
 
-    // The returned meeting ID should match the existing one but we don't check.
-    let meeting_id: i64 = edit::EditMeeting::execute(edit_mutation_variables)
-        .await?
-        .unwrap_or(meeting_id);
+    if following_edits.is_empty() {
+        // The returned meeting ID should match the existing one but we don't check.
+        edit::EditMeeting::execute(edit_mutation_variables).await?;
+    } else {
+        // Edit this meeting first, then the following occurrences. If any of them fail, the
+        // ones already edited are restored from their existing values.
+        let undo = edit::edit_meeting::Variables {
+            meeting_id,
+            title: meeting_data.title.clone(),
+            start: meeting_data.start_date_time,
+            end: meeting_data.end_date_time,
+            semester_id: meeting_data.semester.semester_id.clone(),
+            kind: meeting_data.type_,
+            description: meeting_data.description.clone(),
+            is_remote: meeting_data.is_remote,
+            is_draft: meeting_data.is_draft,
+            meeting_url: meeting_data.meeting_url.clone(),
+            location: meeting_data.location.clone(),
+            external_slides_url: meeting_data.external_presentation_url.clone(),
+            recording_url: meeting_data.recording_url.clone(),
+            host: meeting_data.host.as_ref().map(|host| host.username.clone()),
+        };
+        following_edits.insert(0, (edit_mutation_variables, undo));
+        edit::edit_meetings(following_edits).await?;
+    }
 
     // Redirect the user back to the meeting they edited.
     return Ok(HttpResponse::Found()
@@ -5,17 +5,24 @@ use crate::error::TelescopeError;
 use crate::web::middlewares::authorization::Authorization;
 use actix_web::web::ServiceConfig;
 
+mod attendance;
+mod calendar;
+mod calendar_resets;
 mod create;
 mod delete;
 mod edit;
 mod list;
 mod view;
+mod workshop_proposal;
 
 /// Register calendar related services.
 pub fn register(config: &mut ServiceConfig) {
     // Meetings list page
     list::register(config);
 
+    // Calendar feed of meetings.
+    calendar::register(config);
+
     // Meeting creation services
     create::register(config);
 
@@ -25,6 +32,12 @@ pub fn register(config: &mut ServiceConfig) {
     // Meeting destruction services.
     delete::register(config);
 
+    // Meeting check-in services.
+    attendance::register(config);
+
+    // Workshop proposal services.
+    workshop_proposal::register(config);
+
     config
         // The meeting viewing endpoint must be registered after the meeting creation endpoint,
         // so that the ID path doesn't match the create path.
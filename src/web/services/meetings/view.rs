@@ -7,6 +7,7 @@ use crate::templates::Template;
 use crate::web::services::auth::identity::Identity;
 use actix_web::web::Path;
 use actix_web::HttpRequest;
+use chrono::Utc;
 
 /// The path from the templates directory to this template.
 const TEMPLATE_PATH: &'static str = "meetings/page";
@@ -62,9 +63,14 @@ pub async fn meeting(
 
     info!("{:#?}", &authorization);
 
+    // Check if the meeting is running, so that the check-in button can be shown.
+    let now = Utc::now();
+    let is_ongoing: bool = meeting.start_date_time <= now && now <= meeting.end_date_time;
+
     // If the meeting is visible to the viewer, make and return the template.
     return Template::new(TEMPLATE_PATH)
         .field("meeting", &meeting)
+        .field("is_ongoing", is_ongoing)
         .field("auth", authorization)
         // Rendered inside a page
         .render_into_page(&req, meeting.title())
@@ -0,0 +1,185 @@
+//! Workshop proposal form.
+//!
+//! Enrolled users propose a bonus workshop for the ongoing semester with three ranked time
+//! slots. Coordinators review proposals from the admin panel and schedule approved
+//! workshops at one of the slots.
+
+use crate::api::rcos::workshop_proposals::context::{
+    workshop_proposal_context::ResponseData, WorkshopProposalContext,
+};
+use crate::api::rcos::workshop_proposals::submit::{
+    submit_workshop_proposal, SubmitWorkshopProposal,
+};
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::web::parse_datetime_local;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, ServiceConfig};
+use actix_web::HttpResponse;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+/// The handlebars template for the workshop proposal form.
+const PROPOSAL_FORM: &'static str = "meetings/workshop_proposal";
+
+/// The names of the time slot fields on the form, in order of preference.
+const TIME_SLOT_FIELDS: [&'static str; 3] =
+    ["first_choice_at", "second_choice_at", "third_choice_at"];
+
+/// Register workshop proposal services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(proposal_page).service(submit_proposal);
+}
+
+/// Get the workshop proposal context for a user and make the form for it.
+async fn get_context_and_make_form(
+    username: String,
+) -> Result<(FormTemplate, ResponseData), TelescopeError> {
+    let context: ResponseData = WorkshopProposalContext::get(username).await?;
+
+    // There has to be an ongoing semester to hold a workshop in.
+    let semester = context
+        .current_semester
+        .first()
+        .ok_or(TelescopeError::BadRequest {
+            header: "No Ongoing Semester".into(),
+            message: "There is no ongoing semester to propose a workshop for.".into(),
+            show_status_code: false,
+        })?;
+
+    let mut form = FormTemplate::new(PROPOSAL_FORM, "Propose a Workshop");
+    form.template = json!({
+        "semester": semester,
+        "is_enrolled": !semester.enrollments.is_empty(),
+        "proposals": &context.workshop_proposals,
+        "data": {}
+    });
+
+    return Ok((form, context));
+}
+
+/// Is a timestamp within a semester's dates (in local time)?
+fn in_semester(timestamp: DateTime<Utc>, start_date: NaiveDate, end_date: NaiveDate) -> bool {
+    let date: NaiveDate = timestamp.with_timezone(&Local).date().naive_local();
+    start_date <= date && date <= end_date
+}
+
+/// Workshop proposal form page, with the user's previous proposals.
+#[get("/workshop_proposals")]
+async fn proposal_page(auth: AuthenticationCookie) -> Result<FormTemplate, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let (form, _) = get_context_and_make_form(username).await?;
+    return Ok(form);
+}
+
+/// Form submitted to propose a workshop.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ProposalForm {
+    /// The title of the workshop.
+    title: String,
+    /// What the workshop covers.
+    topic: String,
+    /// Why the proposer is qualified to run the workshop.
+    qualifications: String,
+    /// The preferred time slot (from a `datetime-local` input).
+    first_choice_at: String,
+    /// The second choice time slot.
+    second_choice_at: String,
+    /// The third choice time slot.
+    third_choice_at: String,
+}
+
+/// Endpoint to submit a workshop proposal.
+#[post("/workshop_proposals")]
+async fn submit_proposal(
+    auth: AuthenticationCookie,
+    Form(input): Form<ProposalForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let (mut form, context) = get_context_and_make_form(username.clone()).await?;
+    // The semester exists since the form was created.
+    let semester = &context.current_semester[0];
+
+    // Add the submitted data back to the form.
+    form.template["data"] = json!(&input);
+
+    // Proposals are tied to an enrollment.
+    if semester.enrollments.is_empty() {
+        form.template["issues"]["enrollment"] =
+            json!("You must enroll in this semester before proposing a workshop.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    let mut valid: bool = true;
+
+    if input.title.trim().is_empty() {
+        form.template["issues"]["title"] = json!("Workshops must have a title.");
+        valid = false;
+    }
+
+    if input.topic.trim().is_empty() {
+        form.template["issues"]["topic"] = json!("Please describe what the workshop covers.");
+        valid = false;
+    }
+
+    if input.qualifications.trim().is_empty() {
+        form.template["issues"]["qualifications"] =
+            json!("Please describe why you are qualified to run this workshop.");
+        valid = false;
+    }
+
+    // Workshops have to be scheduled in the future, during the semester.
+    let now: DateTime<Utc> = Utc::now();
+
+    let values: [&String; 3] = [
+        &input.first_choice_at,
+        &input.second_choice_at,
+        &input.third_choice_at,
+    ];
+    let mut slots: Vec<DateTime<Utc>> = Vec::with_capacity(3);
+    for (field, value) in TIME_SLOT_FIELDS.iter().zip(values.iter()) {
+        match parse_datetime_local(value.as_str()) {
+            None => {
+                form.template["issues"][*field] = json!("Invalid date and time.");
+                valid = false;
+            }
+            Some(slot) if slot <= now => {
+                form.template["issues"][*field] = json!("This time has already passed.");
+                valid = false;
+            }
+            Some(slot) if !in_semester(slot, semester.start_date, semester.end_date) => {
+                form.template["issues"][*field] = json!("This time is outside of the semester.");
+                valid = false;
+            }
+            Some(slot) if slots.contains(&slot) => {
+                form.template["issues"][*field] = json!("Each time slot must be different.");
+                valid = false;
+            }
+            Some(slot) => slots.push(slot),
+        }
+    }
+
+    if !valid {
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    SubmitWorkshopProposal::execute(submit_workshop_proposal::Variables {
+        username,
+        semester_id: semester.semester_id.clone(),
+        title: input.title.trim().to_string(),
+        topic: input.topic,
+        qualifications: input.qualifications,
+        first_choice_at: slots[0],
+        second_choice_at: slots[1],
+        third_choice_at: slots[2],
+    })
+    .await?
+    .ok_or(TelescopeError::ise(
+        "Workshop proposal creation did not return ID.",
+    ))?;
+
+    // Send the user back to the proposals page to see the new proposal.
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/workshop_proposals")
+        .finish())
+}
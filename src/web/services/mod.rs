@@ -3,11 +3,15 @@
 use actix_web::web::ServiceConfig;
 
 mod admin;
+mod announcements;
 pub mod auth;
 mod index;
 pub mod meetings;
 pub mod not_found;
+mod organizations;
 mod projects;
+mod small_groups;
+mod status_updates;
 pub mod user;
 
 /// Register all of the routs to the actix app.
@@ -24,6 +28,18 @@ pub fn register(config: &mut ServiceConfig) {
     // Project related services.
     projects::register(config);
 
+    // Small group pages and management.
+    small_groups::register(config);
+
+    // Status update submission and grading services.
+    status_updates::register(config);
+
+    // Sponsors and external organization pages.
+    organizations::register(config);
+
+    // Announcement feed.
+    announcements::register(config);
+
     // Admin panel services.
     admin::register(config);
 
@@ -0,0 +1,44 @@
+//! Sponsors page and external organization pages.
+
+use crate::api::rcos::external_organizations::list::ExternalOrganizations;
+use crate::api::rcos::external_organizations::organization::Organization;
+use crate::error::TelescopeError;
+use crate::templates::static_pages::{sponsors::SponsorsPage, StaticPage};
+use crate::templates::Template;
+use actix_web::web::{Path, ServiceConfig};
+use actix_web::HttpRequest;
+
+/// Register sponsor and organization services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(sponsors).service(organization_page);
+}
+
+/// Sponsors page. This is the static sponsor content followed by the external organizations
+/// that have sponsored projects.
+#[get("/sponsors")]
+async fn sponsors(req: HttpRequest) -> Result<Template, TelescopeError> {
+    SponsorsPage::template()
+        .field("organizations", ExternalOrganizations::get().await?)
+        .render_into_page(&req, SponsorsPage::PAGE_TITLE)
+        .await
+}
+
+/// Page for an external organization listing the projects it has sponsored.
+#[get("/organizations/{external_organization_id}")]
+async fn organization_page(
+    req: HttpRequest,
+    Path(external_organization_id): Path<i64>,
+) -> Result<Template, TelescopeError> {
+    let organization = Organization::get(external_organization_id).await?.ok_or(
+        TelescopeError::resource_not_found(
+            "Organization Not Found",
+            "Could not find an external organization for this ID.",
+        ),
+    )?;
+
+    let title: String = organization.title.clone();
+    Template::new("organizations/page")
+        .field("organization", organization)
+        .render_into_page(&req, title)
+        .await
+}
@@ -0,0 +1,191 @@
+//! Project creation form and services.
+
+use crate::api::rcos::external_organizations::list::ExternalOrganizations;
+use crate::api::rcos::projects::authorization_for::UserProjectAuthorization;
+use crate::api::rcos::projects::mutations::create::CreateProject;
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::web::services::projects::make_project_auth_middleware;
+use actix_web::http::header::LOCATION;
+use actix_web::web as aweb;
+use actix_web::web::{Form, ServiceConfig};
+use actix_web::HttpResponse;
+use url::Url;
+
+/// The handlebars template for the project creation and edit form.
+pub const PROJECT_FORM_TEMPLATE: &'static str = "projects/forms/form";
+
+/// Register project creation services.
+pub fn register(config: &mut ServiceConfig) {
+    // Create project creation auth middleware.
+    let authorization =
+        make_project_auth_middleware(&UserProjectAuthorization::can_create_projects);
+
+    config.service(
+        aweb::scope("/project/create")
+            .wrap(authorization)
+            .service(creation_page)
+            .service(submit_creation),
+    );
+}
+
+/// Form submitted to create or edit a project.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProjectForm {
+    /// The project's title.
+    pub title: String,
+    /// Markdown description of the project.
+    pub description: String,
+    /// Comma separated list of technologies the project uses.
+    pub stack: String,
+    /// Repository URLs, one per line.
+    pub repository_urls: String,
+    /// The project's homepage, if it has one.
+    pub homepage_url: Option<String>,
+    /// A cover image for the project, if it has one.
+    pub cover_image_url: Option<String>,
+    /// The ID of the external organization that owns this project.
+    /// Empty string for no external organization.
+    pub external_organization_id: String,
+}
+
+/// The validated values of a project form, ready to send to the API.
+pub struct ValidatedProject {
+    pub title: String,
+    pub description: String,
+    pub stack: Vec<String>,
+    pub repository_urls: Vec<Url>,
+    pub homepage_url: Option<String>,
+    pub cover_image_url: Option<String>,
+    pub external_organization_id: Option<i64>,
+}
+
+/// Treat empty or whitespace-only strings as missing.
+pub fn non_empty(string: Option<String>) -> Option<String> {
+    string
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Check that an optional URL field parses. Set a form issue and return false if it does not.
+pub fn check_url(form: &mut FormTemplate, field: &str, value: &Option<String>) -> bool {
+    match value.as_ref().map(|s| Url::parse(s.as_str())) {
+        Some(Err(_)) => {
+            form.template["issues"][field] = json!("Could not parse this URL.");
+            false
+        }
+        _ => true,
+    }
+}
+
+impl ProjectForm {
+    /// Validate this form, adding the submitted values and any issues to the form template.
+    /// Return the validated project if there were no issues.
+    pub fn validate(self, form: &mut FormTemplate) -> Option<ValidatedProject> {
+        // Add the submitted values to the form so the user does not lose them.
+        form.template["data"]["title"] = json!(&self.title);
+        form.template["data"]["description"] = json!(&self.description);
+        form.template["data"]["stack_input"] = json!(&self.stack);
+        form.template["data"]["repository_urls_input"] = json!(&self.repository_urls);
+        form.template["data"]["homepage_url"] = json!(&self.homepage_url);
+        form.template["data"]["cover_image_url"] = json!(&self.cover_image_url);
+
+        let mut valid: bool = true;
+
+        let title: String = self.title.trim().to_string();
+        if title.is_empty() {
+            form.template["issues"]["title"] = json!("Projects must have a title.");
+            valid = false;
+        }
+
+        // Split the stack on commas, ignoring empty entries.
+        let stack: Vec<String> = self
+            .stack
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect();
+
+        // Parse each repository URL.
+        let mut repository_urls: Vec<Url> = Vec::new();
+        for line in self.repository_urls.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+            match Url::parse(line) {
+                Ok(url) => repository_urls.push(url),
+                Err(_) => {
+                    form.template["issues"]["repository_urls"] =
+                        json!(format!("Could not parse \"{}\" as a URL.", line));
+                    valid = false;
+                }
+            }
+        }
+
+        let homepage_url: Option<String> = non_empty(self.homepage_url);
+        valid &= check_url(form, "homepage_url", &homepage_url);
+        let cover_image_url: Option<String> = non_empty(self.cover_image_url);
+        valid &= check_url(form, "cover_image_url", &cover_image_url);
+
+        // External organization IDs come from a dropdown. The empty option means none.
+        let external_organization_id: Option<i64> = self.external_organization_id.parse().ok();
+        form.template["data"]["external_organization"] =
+            json!({ "external_organization_id": external_organization_id });
+
+        return valid.then(|| ValidatedProject {
+            title,
+            description: self.description,
+            stack,
+            repository_urls,
+            homepage_url,
+            cover_image_url,
+            external_organization_id,
+        });
+    }
+}
+
+/// Create an empty project form with the list of external organizations to choose from.
+pub async fn make_form(page_title: impl Into<String>) -> Result<FormTemplate, TelescopeError> {
+    let mut form = FormTemplate::new(PROJECT_FORM_TEMPLATE, page_title);
+    form.template = json!({
+        "external_organizations": ExternalOrganizations::get().await?,
+        "data": {}
+    });
+    return Ok(form);
+}
+
+/// Page to create a project. Authorized to project creation perms.
+#[get("")]
+async fn creation_page() -> Result<FormTemplate, TelescopeError> {
+    make_form("Create Project").await
+}
+
+/// Endpoint to submit project creation.
+#[post("")]
+async fn submit_creation(
+    Form(form_data): Form<ProjectForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let mut form: FormTemplate = make_form("Create Project").await?;
+    let project: ValidatedProject = form_data
+        .validate(&mut form)
+        .ok_or(TelescopeError::invalid_form(&form))?;
+
+    let project_id: i64 = CreateProject::execute(
+        project.title,
+        project.description,
+        project.stack,
+        project.repository_urls,
+        project.homepage_url,
+        project.cover_image_url,
+        project.external_organization_id,
+    )
+    .await?
+    .ok_or(TelescopeError::ise(
+        "Project creation call did not return ID.",
+    ))?;
+
+    // Redirect the user to the created project.
+    Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/project/{}", project_id))
+        .finish())
+}
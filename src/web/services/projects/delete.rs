@@ -0,0 +1,66 @@
+//! Services for deleting projects.
+
+use crate::api::rcos::projects::authorization_for::ProjectAuthorizationFor;
+use crate::api::rcos::projects::mutations::delete::DeleteProject;
+use crate::api::rcos::projects::project::Project;
+use crate::error::TelescopeError;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Path, ServiceConfig};
+use actix_web::HttpResponse;
+
+/// Register project deletion services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(delete_project);
+}
+
+/// Project deletion endpoint. Uses post to prevent inadvertent deletion.
+#[post("/project/{project_id}/delete")]
+async fn delete_project(
+    auth: AuthenticationCookie,
+    Path(project_id): Path<i64>,
+) -> Result<HttpResponse, TelescopeError> {
+    // Require that there is a user authenticated and that they can delete projects.
+    let username: String = auth.get_rcos_username_or_error().await?;
+    if !ProjectAuthorizationFor::get(Some(username))
+        .await?
+        .can_delete_projects()
+    {
+        return Err(TelescopeError::Forbidden);
+    }
+
+    let project =
+        Project::get_by_id(project_id)
+            .await?
+            .ok_or(TelescopeError::resource_not_found(
+                "Project Not Found",
+                "Could not find a project for this ID.",
+            ))?;
+
+    // Projects that anyone has ever enrolled in are part of the historical record.
+    let enrollment_count: i64 = project
+        .enrollment_count
+        .aggregate
+        .and_then(|aggregate| aggregate.count)
+        .unwrap_or(0);
+
+    if enrollment_count > 0 {
+        return Err(TelescopeError::BadRequest {
+            header: "Cannot Delete Project".into(),
+            message: format!(
+                "This project has {} enrollment(s) and cannot be deleted.",
+                enrollment_count
+            ),
+            show_status_code: false,
+        });
+    }
+
+    DeleteProject::execute(project_id)
+        .await?
+        .ok_or(TelescopeError::ise(
+            "Project deletion did not return project ID.",
+        ))?;
+
+    // Project deleted successfully. Redirect user back to projects page.
+    Ok(HttpResponse::Found().header(LOCATION, "/projects").finish())
+}
@@ -0,0 +1,100 @@
+//! Project edit form and services.
+
+use crate::api::rcos::projects::authorization_for::ProjectAuthorizationFor;
+use crate::api::rcos::projects::mutations::edit::{edit_project, EditProject};
+use crate::api::rcos::projects::project::{project::ProjectProject, Project};
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use crate::web::services::projects::create::{make_form, ProjectForm, ValidatedProject};
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::HttpResponse;
+
+/// Register project edit services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(edit_page).service(submit_edits);
+}
+
+/// Get project data and error if the authenticated user cannot edit the project.
+async fn project_data_checked(
+    auth: &AuthenticationCookie,
+    project_id: i64,
+) -> Result<ProjectProject, TelescopeError> {
+    let viewer: String = auth.get_rcos_username_or_error().await?;
+    let authorization = ProjectAuthorizationFor::get(Some(viewer)).await?;
+    if !authorization.can_edit(project_id) {
+        return Err(TelescopeError::Forbidden);
+    }
+
+    Project::get_by_id(project_id)
+        .await?
+        .ok_or(TelescopeError::resource_not_found(
+            "Project Not Found",
+            "Could not find a project for this ID.",
+        ))
+}
+
+/// Create the edit form for a project.
+async fn make_edit_form(project: &ProjectProject) -> Result<FormTemplate, TelescopeError> {
+    let mut form: FormTemplate = make_form(format!("Edit {}", project.title)).await?;
+    form.template["data"] = json!(project);
+    form.template["editing"] = json!(true);
+    return Ok(form);
+}
+
+/// Service to display the project edit form to users who can edit the project.
+#[get("/project/{project_id}/edit")]
+async fn edit_page(
+    auth: AuthenticationCookie,
+    Path(project_id): Path<i64>,
+) -> Result<FormTemplate, TelescopeError> {
+    let project: ProjectProject = project_data_checked(&auth, project_id).await?;
+    let mut form: FormTemplate = make_edit_form(&project).await?;
+
+    // Convert the list fields to the text the inputs expect.
+    form.template["data"]["stack_input"] = json!(project.stack.join(", "));
+    form.template["data"]["repository_urls_input"] = json!(project
+        .repository_urls
+        .iter()
+        .map(|url| url.as_str())
+        .collect::<Vec<_>>()
+        .join("\n"));
+
+    return Ok(form);
+}
+
+/// Endpoint to submit project edits.
+#[post("/project/{project_id}/edit")]
+async fn submit_edits(
+    auth: AuthenticationCookie,
+    Path(project_id): Path<i64>,
+    Form(form_data): Form<ProjectForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let project: ProjectProject = project_data_checked(&auth, project_id).await?;
+    let mut form: FormTemplate = make_edit_form(&project).await?;
+    let edits: ValidatedProject = form_data
+        .validate(&mut form)
+        .ok_or(TelescopeError::invalid_form(&form))?;
+
+    EditProject::execute(edit_project::Variables {
+        project_id,
+        title: edits.title,
+        description: edits.description,
+        stack: edits.stack,
+        repository_urls: edits.repository_urls,
+        homepage_url: edits.homepage_url,
+        cover_image_url: edits.cover_image_url,
+        external_organization_id: edits.external_organization_id,
+    })
+    .await?
+    .ok_or(TelescopeError::resource_not_found(
+        "Project Not Found",
+        "Could not find a project for this ID.",
+    ))?;
+
+    // Redirect the user back to the project page.
+    Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/project/{}", project_id))
+        .finish())
+}
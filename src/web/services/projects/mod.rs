@@ -1,10 +1,58 @@
 //! Services related to project management.
 
-use actix_web::web::ServiceConfig;
+use crate::api::rcos::projects::authorization_for::{
+    ProjectAuthorizationFor, UserProjectAuthorization,
+};
+use crate::error::TelescopeError;
+use crate::web::middlewares::authorization::Authorization;
+use actix_web::web::{self as aweb, ServiceConfig};
 
+mod create;
+mod delete;
+mod edit;
+mod pitch;
+mod presentations;
+mod project;
 mod projects_page;
 
 /// Register project services.
 pub fn register(conf: &mut ServiceConfig) {
-    conf.service(projects_page::get);
+    // Route with or without the page number to the projects page handler.
+    conf.route("/projects", aweb::get().to(projects_page::get))
+        .route("/projects/{page}", aweb::get().to(projects_page::get));
+
+    // Project creation services.
+    create::register(conf);
+
+    // Project edit services.
+    edit::register(conf);
+
+    // Project deletion services.
+    delete::register(conf);
+
+    // Project pitch form.
+    pitch::register(conf);
+
+    // Presentation submission and grading.
+    presentations::register(conf);
+
+    // The project viewing endpoint must be registered after the project creation endpoint,
+    // so that the ID path doesn't match the create path.
+    conf.service(project::project);
+}
+
+/// Create an authorization middleware based on a project authorization function.
+fn make_project_auth_middleware<F: 'static + Fn(&UserProjectAuthorization) -> bool>(
+    f: &'static F,
+) -> Authorization {
+    Authorization::new(move |username: String| {
+        Box::pin(async move {
+            // Get the user project access authorization object.
+            let auth: UserProjectAuthorization =
+                ProjectAuthorizationFor::get(Some(username)).await?;
+
+            // Call the verification function on the access authorization object.
+            (f)(&auth).then(|| ()).ok_or(TelescopeError::Forbidden)
+        })
+    })
 }
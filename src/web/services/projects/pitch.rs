@@ -0,0 +1,177 @@
+//! Project pitch form.
+//!
+//! Enrolled users pitch a project for the ongoing semester, either a new project or one that
+//! already exists. Coordinators review pitches from the admin panel. Pitches can be revised
+//! until they are approved.
+
+use crate::api::rcos::project_pitches::context::{pitch_context::ResponseData, PitchContext};
+use crate::api::rcos::project_pitches::submit::{submit_pitch, SubmitPitch};
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use crate::web::services::projects::create::{check_url, non_empty};
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, ServiceConfig};
+use actix_web::HttpResponse;
+
+/// The handlebars template for the project pitch form.
+const PITCH_FORM: &'static str = "projects/pitch";
+
+/// Register project pitch services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(pitch_page).service(submit);
+}
+
+/// Get the pitch context for a user and make the form for it.
+async fn get_context_and_make_form(
+    username: String,
+) -> Result<(FormTemplate, ResponseData), TelescopeError> {
+    let context: ResponseData = PitchContext::get(username).await?;
+
+    // There has to be an ongoing semester to pitch a project for.
+    let semester = context
+        .current_semester
+        .first()
+        .ok_or(TelescopeError::BadRequest {
+            header: "No Ongoing Semester".into(),
+            message: "There is no ongoing semester to pitch a project for.".into(),
+            show_status_code: false,
+        })?;
+
+    let mut form = FormTemplate::new(
+        PITCH_FORM,
+        format!("Pitch a Project for {}", semester.title),
+    );
+    form.template = json!({
+        "semester": semester,
+        "is_enrolled": !semester.enrollments.is_empty(),
+        "projects": &context.projects,
+        // The existing pitch, if any. This is also used to fill the form.
+        "pitch": semester.project_pitches.first(),
+        "data": semester.project_pitches.first()
+    });
+
+    return Ok((form, context));
+}
+
+/// Project pitch form page.
+#[get("/pitch")]
+async fn pitch_page(auth: AuthenticationCookie) -> Result<FormTemplate, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let (form, _) = get_context_and_make_form(username).await?;
+    return Ok(form);
+}
+
+/// Form submitted to pitch a project.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct PitchForm {
+    /// The ID of the existing project being pitched. Empty string for a new project.
+    existing_project_id: String,
+    /// The title of the proposed project.
+    #[serde(default)]
+    proposed_title: Option<String>,
+    /// Markdown description of the proposed project.
+    #[serde(default)]
+    proposed_description: Option<String>,
+    /// Comma separated list of technologies the project will use.
+    #[serde(default)]
+    proposed_stack: Option<String>,
+    /// Link to a written proposal.
+    #[serde(default)]
+    proposal_url: Option<String>,
+    /// Link to the pitch slides.
+    #[serde(default)]
+    pitch_slide_url: Option<String>,
+    /// Checkbox for whether the project is looking for members.
+    #[serde(default)]
+    is_looking_for_members: Option<bool>,
+}
+
+/// Endpoint to submit a project pitch.
+#[post("/pitch")]
+async fn submit(
+    auth: AuthenticationCookie,
+    Form(input): Form<PitchForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let (mut form, context) = get_context_and_make_form(username.clone()).await?;
+    // The semester exists since the form was created.
+    let semester = &context.current_semester[0];
+
+    let existing_project_id: Option<i64> = input.existing_project_id.parse().ok();
+    let proposed_title: Option<String> = non_empty(input.proposed_title);
+    let proposed_description: Option<String> = non_empty(input.proposed_description);
+    let proposed_stack: Option<String> = non_empty(input.proposed_stack);
+    let proposal_url: Option<String> = non_empty(input.proposal_url);
+    let pitch_slide_url: Option<String> = non_empty(input.pitch_slide_url);
+    let is_looking_for_members: bool = input.is_looking_for_members.unwrap_or(false);
+
+    // Add the submitted data back to the form.
+    form.template["data"] = json!({
+        "existing_project_id": existing_project_id,
+        "proposed_title": &proposed_title,
+        "proposed_description": &proposed_description,
+        "proposed_stack": &proposed_stack,
+        "proposal_url": &proposal_url,
+        "pitch_slide_url": &pitch_slide_url,
+        "is_looking_for_members": is_looking_for_members,
+    });
+
+    // Pitches are tied to an enrollment.
+    if semester.enrollments.is_empty() {
+        form.template["issues"]["enrollment"] =
+            json!("You must enroll in this semester before pitching a project.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    // Approved pitches are final.
+    if semester
+        .project_pitches
+        .first()
+        .map(|pitch| pitch.is_approved)
+        .unwrap_or(false)
+    {
+        form.template["issues"]["approved"] =
+            json!("Your pitch has already been approved and can no longer be changed.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    let mut valid: bool = true;
+
+    // New projects need a title and description to create the project from.
+    if existing_project_id.is_none() {
+        if proposed_title.is_none() {
+            form.template["issues"]["proposed_title"] = json!("New projects must have a title.");
+            valid = false;
+        }
+
+        if proposed_description.is_none() {
+            form.template["issues"]["proposed_description"] =
+                json!("New projects must have a description.");
+            valid = false;
+        }
+    }
+
+    valid &= check_url(&mut form, "proposal_url", &proposal_url);
+    valid &= check_url(&mut form, "pitch_slide_url", &pitch_slide_url);
+
+    if !valid {
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    SubmitPitch::execute(submit_pitch::Variables {
+        username,
+        semester_id: semester.semester_id.clone(),
+        existing_project_id,
+        proposed_title,
+        proposed_description,
+        proposed_stack,
+        proposal_url,
+        pitch_slide_url,
+        is_looking_for_members,
+    })
+    .await?;
+
+    // Send the user back to the pitch page to see its status.
+    Ok(HttpResponse::Found().header(LOCATION, "/pitch").finish())
+}
@@ -0,0 +1,303 @@
+//! Project presentation submission and grading.
+//!
+//! Project leads submit a link to their project's end-of-semester presentation at
+//! `/project/{id}/presentation`. Mentors grade the presentations of the projects in their
+//! small groups at `/presentations` using a fixed rubric. Coordinators and admins can see and
+//! grade every project.
+
+use crate::api::rcos::projects::authorization_for::ProjectAuthorizationFor;
+use crate::api::rcos::projects::presentations::grading::{
+    presentation_grading::PresentationGradingCurrentSemester, PresentationGrading,
+    UpsertPresentationGrade,
+};
+use crate::api::rcos::projects::presentations::submit::{
+    presentation_context::ResponseData as PresentationContextData, PresentationContext,
+    UpsertPresentation,
+};
+use crate::api::rcos::small_groups::authorization_for::{
+    SmallGroupAuthorizationFor, UserSmallGroupAuthorization,
+};
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::templates::Template;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use crate::web::services::projects::create::{check_url, non_empty};
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+use std::collections::HashMap;
+
+/// The path from the templates directory to the presentation form template.
+const PRESENTATION_FORM: &'static str = "projects/presentation";
+
+/// One criterion of the presentation grading rubric.
+#[derive(Serialize, Copy, Clone, Debug)]
+struct RubricCriterion {
+    /// The form field for this criterion.
+    key: &'static str,
+    /// Short name shown next to the score input.
+    label: &'static str,
+    /// What graders should look for.
+    description: &'static str,
+    /// The most points a presentation can get for this criterion.
+    max_points: f64,
+}
+
+/// The presentation grading rubric. A presentation's grade is the sum of its points for each
+/// criterion, out of 100.
+const RUBRIC: [RubricCriterion; 4] = [
+    RubricCriterion {
+        key: "progress",
+        label: "Progress",
+        description: "Progress made over the semester",
+        max_points: 40.0,
+    },
+    RubricCriterion {
+        key: "demo",
+        label: "Demo",
+        description: "Demonstration of working features",
+        max_points: 30.0,
+    },
+    RubricCriterion {
+        key: "delivery",
+        label: "Delivery",
+        description: "Clarity and delivery of the presentation",
+        max_points: 20.0,
+    },
+    RubricCriterion {
+        key: "plans",
+        label: "Plans",
+        description: "Plans for the project going forward",
+        max_points: 10.0,
+    },
+];
+
+/// Register presentation services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(presentation_page)
+        .service(submit_presentation)
+        .service(grading_page)
+        .service(submit_grade);
+}
+
+/// Get the presentation context for a project and make the form for it. Error if the
+/// authenticated user cannot edit the project.
+async fn get_context_and_make_form(
+    auth: &AuthenticationCookie,
+    project_id: i64,
+) -> Result<(FormTemplate, PresentationContextData), TelescopeError> {
+    let viewer: String = auth.get_rcos_username_or_error().await?;
+    if !ProjectAuthorizationFor::get(Some(viewer))
+        .await?
+        .can_edit(project_id)
+    {
+        return Err(TelescopeError::Forbidden);
+    }
+
+    let context: PresentationContextData = PresentationContext::get(project_id).await?;
+    let project = context
+        .project
+        .as_ref()
+        .ok_or(TelescopeError::resource_not_found(
+            "Project Not Found",
+            "Could not find a project for this ID.",
+        ))?;
+
+    let semester = context
+        .current_semester
+        .first()
+        .ok_or(TelescopeError::BadRequest {
+            header: "No Ongoing Semester".into(),
+            message: "There is no ongoing semester to present in.".into(),
+            show_status_code: false,
+        })?;
+
+    let mut form = FormTemplate::new(PRESENTATION_FORM, format!("{} Presentation", project.title));
+    form.template = json!({
+        "project": project,
+        "semester": semester,
+        // The existing presentation, if any.
+        "data": semester.presentation.first(),
+    });
+
+    return Ok((form, context));
+}
+
+/// Presentation form page.
+#[get("/project/{project_id}/presentation")]
+async fn presentation_page(
+    auth: AuthenticationCookie,
+    Path(project_id): Path<i64>,
+) -> Result<FormTemplate, TelescopeError> {
+    let (form, _) = get_context_and_make_form(&auth, project_id).await?;
+    return Ok(form);
+}
+
+/// Presentation form submission.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct PresentationForm {
+    /// Link to the presentation slides or recording.
+    presentation_url: String,
+    /// Checkbox to save the presentation as a draft, hidden from graders.
+    #[serde(default)]
+    is_draft: Option<bool>,
+}
+
+/// Endpoint to submit a presentation.
+#[post("/project/{project_id}/presentation")]
+async fn submit_presentation(
+    auth: AuthenticationCookie,
+    Path(project_id): Path<i64>,
+    Form(PresentationForm {
+        presentation_url,
+        is_draft,
+    }): Form<PresentationForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let (mut form, context) = get_context_and_make_form(&auth, project_id).await?;
+
+    let presentation_url: Option<String> = non_empty(Some(presentation_url));
+    let is_draft: bool = is_draft.unwrap_or(false);
+
+    // Add the submitted data back to the form.
+    form.template["data"] = json!({
+        "presentation_url": &presentation_url,
+        "is_draft": is_draft,
+    });
+
+    if presentation_url.is_none() {
+        form.template["issues"]["presentation_url"] = json!("Enter a link to the presentation.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    if !check_url(&mut form, "presentation_url", &presentation_url) {
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    // The semester exists since the form was created.
+    let semester_id: String = context.current_semester[0].semester_id.clone();
+    UpsertPresentation::execute(project_id, semester_id, presentation_url.unwrap(), is_draft)
+        .await?;
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/project/{}", project_id))
+        .finish())
+}
+
+/// Get the ongoing semester's grading data, keeping only the small groups the authenticated
+/// user can grade. Return the user's username along with it.
+async fn grading_data_checked(
+    auth: &AuthenticationCookie,
+) -> Result<(String, PresentationGradingCurrentSemester), TelescopeError> {
+    let viewer: String = auth.get_rcos_username_or_error().await?;
+    let authorization: UserSmallGroupAuthorization =
+        SmallGroupAuthorizationFor::get(Some(viewer.clone())).await?;
+
+    let mut semester =
+        PresentationGrading::get(viewer.clone())
+            .await?
+            .ok_or(TelescopeError::BadRequest {
+                header: "No Ongoing Semester".into(),
+                message: "There is no ongoing semester to grade presentations for.".into(),
+                show_status_code: false,
+            })?;
+
+    // Graders only see the projects in the small groups they mentor.
+    semester
+        .small_groups
+        .retain(|group| authorization.can_view_dashboard(group.small_group_id));
+
+    if semester.small_groups.is_empty() && !authorization.can_manage() {
+        return Err(TelescopeError::Forbidden);
+    }
+
+    return Ok((viewer, semester));
+}
+
+/// Page listing the presentations the authenticated user can grade.
+#[get("/presentations")]
+async fn grading_page(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+) -> Result<Template, TelescopeError> {
+    let (_, semester) = grading_data_checked(&auth).await?;
+    let is_enrolled: bool = !semester.viewer_enrollment.is_empty();
+
+    Template::new("projects/presentations")
+        .field("semester", semester)
+        .field("is_enrolled", is_enrolled)
+        .field("rubric", RUBRIC)
+        .render_into_page(&req, "Presentation Grading")
+        .await
+}
+
+/// Endpoint to grade a project's presentation. The form has a score for each rubric
+/// criterion, keyed by the criterion's key.
+#[post("/presentations/{project_id}")]
+async fn submit_grade(
+    auth: AuthenticationCookie,
+    Path(project_id): Path<i64>,
+    Form(scores): Form<HashMap<String, String>>,
+) -> Result<HttpResponse, TelescopeError> {
+    let (grader, semester) = grading_data_checked(&auth).await?;
+
+    // The grader has to be able to see the project and it has to have a presentation.
+    let project = semester
+        .small_groups
+        .iter()
+        .flat_map(|group| group.small_group_projects.iter())
+        .map(|group_project| &group_project.project)
+        .find(|project| project.project_id == project_id)
+        .ok_or(TelescopeError::Forbidden)?;
+
+    if project
+        .presentation
+        .first()
+        .map(|presentation| presentation.is_draft)
+        .unwrap_or(true)
+    {
+        return Err(TelescopeError::BadRequest {
+            header: "No Presentation".into(),
+            message: "This project has not submitted a presentation to grade yet.".into(),
+            show_status_code: false,
+        });
+    }
+
+    // Grades are recorded against the grader's enrollment.
+    if semester.viewer_enrollment.is_empty() {
+        return Err(TelescopeError::BadRequest {
+            header: "Not Enrolled".into(),
+            message: format!(
+                "You must be enrolled in {} to grade presentations.",
+                semester.title
+            ),
+            show_status_code: false,
+        });
+    }
+
+    // Add up the score for each criterion.
+    let mut grade: f64 = 0.0;
+    for criterion in RUBRIC.iter() {
+        let points: f64 = scores
+            .get(criterion.key)
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|points| points.is_finite() && *points >= 0.0)
+            .filter(|points| *points <= criterion.max_points)
+            .ok_or(TelescopeError::BadRequest {
+                header: "Invalid Score".into(),
+                message: format!(
+                    "The score for \"{}\" must be between 0 and {}.",
+                    criterion.description, criterion.max_points
+                ),
+                show_status_code: false,
+            })?;
+
+        grade += points;
+    }
+
+    UpsertPresentationGrade::execute(project_id, semester.semester_id, grader, grade).await?;
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/presentations")
+        .finish())
+}
@@ -0,0 +1,84 @@
+//! Project details page.
+
+use crate::api::rcos::projects::authorization_for::{
+    ProjectAuthorizationFor, UserProjectAuthorization,
+};
+use crate::api::rcos::projects::project::{project::ProjectProject, Project};
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::Identity;
+use actix_web::web::Path;
+use actix_web::HttpRequest;
+use serde_json::Value;
+
+/// The path to the project page template from the templates directory.
+const TEMPLATE_PATH: &'static str = "projects/page";
+
+/// Page to view a project's details.
+#[get("/project/{project_id}")]
+pub async fn project(
+    req: HttpRequest,
+    identity: Identity,
+    Path(project_id): Path<i64>,
+) -> Result<Template, TelescopeError> {
+    let project =
+        Project::get_by_id(project_id)
+            .await?
+            .ok_or(TelescopeError::resource_not_found(
+                "Project Not Found",
+                "Could not find a project for this ID.",
+            ))?;
+
+    // Get the viewer's project permissions to decide which buttons to show.
+    let viewer: Option<String> = identity.get_rcos_username().await?;
+    let authorization: UserProjectAuthorization = ProjectAuthorizationFor::get(viewer).await?;
+
+    let show_grades: bool = can_see_grades(&project, &authorization);
+    let title: String = project.title.clone();
+
+    // Remove presentation grades before rendering if the viewer cannot see them.
+    let mut project: Value = json!(project);
+    if !show_grades {
+        if let Some(presentations) = project["presentations"].as_array_mut() {
+            for presentation in presentations {
+                presentation["semester"]["grades"] = Value::Null;
+            }
+        }
+    }
+
+    return Template::new(TEMPLATE_PATH)
+        .field("can_edit", authorization.can_edit(project_id))
+        .field("can_delete", authorization.can_delete_projects())
+        .field("show_grades", show_grades)
+        .field("project", project)
+        .render_into_page(&req, title)
+        .await;
+}
+
+/// Can the viewer see the presentation grades of a project? Grades are visible to the
+/// project's members, the mentors of its small groups, coordinators, and admins.
+fn can_see_grades(project: &ProjectProject, authorization: &UserProjectAuthorization) -> bool {
+    if authorization.can_manage_all() {
+        return true;
+    }
+
+    let viewer: &str = match authorization.username.as_deref() {
+        Some(viewer) => viewer,
+        None => return false,
+    };
+
+    let is_member: bool = project
+        .members
+        .iter()
+        .any(|member| member.username == viewer);
+
+    let is_mentor: bool = project.small_groups.iter().any(|group| {
+        group
+            .small_group
+            .mentors
+            .iter()
+            .any(|mentor| mentor.user.username == viewer)
+    });
+
+    return is_member || is_mentor;
+}
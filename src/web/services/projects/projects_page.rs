@@ -1,9 +1,82 @@
-//! Project page.
+//! Projects page services.
 
+use actix_web::web::{Path, Query};
+use actix_web::HttpRequest;
+use serde_json::Value;
+
+use crate::api::rcos::projects::authorization_for::ProjectAuthorizationFor;
+use crate::api::rcos::projects::projects_page::{AllProjects, CurrentProjects, PER_PAGE};
 use crate::error::TelescopeError;
-use actix_web::HttpResponse;
+use crate::templates::pagination::PaginationInfo;
+use crate::templates::Template;
+use crate::web::services::auth::identity::Identity;
+
+/// The path to the projects page template from the templates directory.
+const TEMPLATE_PATH: &'static str = "projects/list";
+
+/// The query parameters passed to the projects page indicating pagination
+/// data and any filters.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ProjectsPageQuery {
+    /// Filter for projects if their title or description contains this
+    /// string case independently (via ILIKE).
+    pub search: Option<String>,
+
+    /// Should the results include projects without enrollments this semester?
+    #[serde(default)]
+    pub include_old: bool,
+}
+
+/// Try to get the pagination bar to use based on the api data.
+/// Panics if `current_page` is 0.
+fn get_page_numbers(api_response: &Value, current_page: u64) -> Option<PaginationInfo> {
+    api_response
+        .get("projects_aggregate")?
+        .get("aggregate")?
+        .get("count")?
+        .as_u64()
+        .and_then(|count| PaginationInfo::new(count, PER_PAGE as u64, current_page))
+}
+
+/// The project catalogue. This page displays the projects in the RCOS database.
+pub async fn get(
+    req: HttpRequest,
+    identity: Identity,
+    page: Option<Path<u32>>,
+    Query(query): Query<ProjectsPageQuery>,
+) -> Result<Template, TelescopeError> {
+    // Resolve the zero-indexed page number. Page numbers in the UI index from 1.
+    let page_num: u32 = page
+        .map(|page_path| page_path.0)
+        .filter(|p| *p >= 1)
+        .map(|p| p - 1)
+        .unwrap_or(0);
+
+    // Get the API data by sending one of the projects page queries.
+    let api_data: Value = if query.include_old {
+        serde_json::to_value(AllProjects::get(page_num, query.search.clone()).await?).unwrap()
+    } else {
+        serde_json::to_value(CurrentProjects::get(page_num, query.search.clone()).await?).unwrap()
+    };
+
+    // Check if the viewer can create projects to show the creation button.
+    let viewer: Option<String> = identity.get_rcos_username().await?;
+    // Anyone who is signed in can pitch a project.
+    let can_pitch: bool = viewer.is_some();
+    let can_create: bool = ProjectAuthorizationFor::get(viewer)
+        .await?
+        .can_create_projects();
 
-#[get("/projects")]
-pub async fn get() -> Result<HttpResponse, TelescopeError> {
-    return Err(TelescopeError::NotImplemented);
+    Template::new(TEMPLATE_PATH)
+        .field("can_create", can_create)
+        .field("can_pitch", can_pitch)
+        .field(
+            "pagination",
+            get_page_numbers(&api_data, page_num as u64 + 1),
+        )
+        .field("data", api_data)
+        .field("query", query)
+        .field("preserved_query_string", req.query_string())
+        .render_into_page(&req, "Projects")
+        .await
 }
@@ -0,0 +1,86 @@
+//! Small group creation form and services.
+
+use crate::api::rcos::semesters::list::SemesterList;
+use crate::api::rcos::small_groups::mutations::CreateSmallGroup;
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::web::services::small_groups::make_management_middleware;
+use actix_web::http::header::LOCATION;
+use actix_web::web as aweb;
+use actix_web::web::{Form, ServiceConfig};
+use actix_web::HttpResponse;
+
+/// The handlebars template for the small group creation form.
+const CREATION_FORM: &'static str = "small_groups/create";
+
+/// Register small group creation services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(
+        aweb::scope("/small_group/create")
+            .wrap(make_management_middleware())
+            .service(creation_page)
+            .service(submit_creation),
+    );
+}
+
+/// Make the small group creation form with the list of semesters.
+async fn make_form() -> Result<FormTemplate, TelescopeError> {
+    let mut form = FormTemplate::new(CREATION_FORM, "Create Small Group");
+    form.template = json!({
+        "semesters": SemesterList::get().await?
+    });
+    return Ok(form);
+}
+
+/// Page to create a small group.
+#[get("")]
+async fn creation_page() -> Result<FormTemplate, TelescopeError> {
+    make_form().await
+}
+
+/// Form submitted to create a small group.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SmallGroupForm {
+    /// The semester of the small group. This is not used for edits.
+    #[serde(default)]
+    pub semester_id: String,
+    /// The small group's title.
+    pub title: String,
+    /// Where the small group meets.
+    #[serde(default)]
+    pub location: String,
+}
+
+/// Endpoint to submit small group creation.
+#[post("")]
+async fn submit_creation(
+    Form(SmallGroupForm {
+        semester_id,
+        title,
+        location,
+    }): Form<SmallGroupForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let mut form: FormTemplate = make_form().await?;
+    form.template["data"] = json!({
+        "semester_id": &semester_id,
+        "title": &title,
+        "location": &location,
+    });
+
+    if title.trim().is_empty() {
+        form.template["issues"]["title"] = json!("Small groups must have a title.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    // Empty locations are stored as null.
+    let location: Option<String> = Some(location).filter(|l| !l.trim().is_empty());
+    let small_group_id: i64 = CreateSmallGroup::execute(semester_id, title, location)
+        .await?
+        .ok_or(TelescopeError::ise(
+            "Small group creation did not return ID.",
+        ))?;
+
+    Ok(HttpResponse::Found()
+        .header(LOCATION, format!("/small_group/{}", small_group_id))
+        .finish())
+}
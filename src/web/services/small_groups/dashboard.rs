@@ -0,0 +1,47 @@
+//! Mentor dashboard for a small group.
+
+use crate::api::rcos::small_groups::authorization_for::SmallGroupAuthorizationFor;
+use crate::api::rcos::small_groups::dashboard::SmallGroupDashboard;
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::web::{Path, ServiceConfig};
+use actix_web::HttpRequest;
+
+/// The handlebars template for the mentor dashboard.
+const TEMPLATE_PATH: &'static str = "small_groups/dashboard";
+
+/// Register the mentor dashboard.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(dashboard);
+}
+
+/// Dashboard of a small group's members with their attendance and status update completion.
+/// Visible to the group's mentors and to coordinators.
+#[get("/small_group/{small_group_id}/dashboard")]
+async fn dashboard(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+    Path(small_group_id): Path<i64>,
+) -> Result<Template, TelescopeError> {
+    let viewer: String = auth.get_rcos_username_or_error().await?;
+    if !SmallGroupAuthorizationFor::get(Some(viewer))
+        .await?
+        .can_view_dashboard(small_group_id)
+    {
+        return Err(TelescopeError::Forbidden);
+    }
+
+    let small_group = SmallGroupDashboard::get(small_group_id).await?.ok_or(
+        TelescopeError::resource_not_found(
+            "Small Group Not Found",
+            "Could not find a small group for this ID.",
+        ),
+    )?;
+
+    let title: String = format!("{} Dashboard", small_group.title);
+    return Template::new(TEMPLATE_PATH)
+        .field("small_group", small_group)
+        .render_into_page(&req, title)
+        .await;
+}
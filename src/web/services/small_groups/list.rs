@@ -0,0 +1,68 @@
+//! Small groups list page.
+
+use crate::api::rcos::semesters::list::{semester_list::SemesterListSemesters, SemesterList};
+use crate::api::rcos::small_groups::authorization_for::SmallGroupAuthorizationFor;
+use crate::api::rcos::small_groups::list::SmallGroups;
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::Identity;
+use actix_web::web::{Query, ServiceConfig};
+use actix_web::HttpRequest;
+use chrono::Local;
+
+/// The handlebars template for the small groups list.
+const TEMPLATE_PATH: &'static str = "small_groups/list";
+
+/// Register the small groups list page.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(small_groups);
+}
+
+/// Query parameters on the small groups page.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct SmallGroupsQuery {
+    /// The semester to show small groups for. Defaults to the ongoing semester.
+    semester: Option<String>,
+}
+
+/// Page listing the small groups of a semester.
+#[get("/small_groups")]
+async fn small_groups(
+    req: HttpRequest,
+    identity: Identity,
+    Query(SmallGroupsQuery { semester }): Query<SmallGroupsQuery>,
+) -> Result<Template, TelescopeError> {
+    let semesters: Vec<SemesterListSemesters> = SemesterList::get().await?;
+
+    // Resolve the semester to show. Use the requested semester, the ongoing semester,
+    // or the most recent semester in that order.
+    let today = Local::today().naive_local();
+    let selected: Option<&SemesterListSemesters> = semester
+        .as_ref()
+        .and_then(|id| semesters.iter().find(|s| &s.semester_id == id))
+        .or_else(|| {
+            semesters
+                .iter()
+                .find(|s| s.start_date <= today && today <= s.end_date)
+        })
+        .or_else(|| semesters.first());
+
+    let groups = match selected {
+        Some(selected) => SmallGroups::get(selected.semester_id.clone()).await?,
+        None => Vec::new(),
+    };
+
+    let viewer: Option<String> = identity.get_rcos_username().await?;
+    // Anyone who is signed in can apply to mentor.
+    let can_apply: bool = viewer.is_some();
+    let can_manage: bool = SmallGroupAuthorizationFor::get(viewer).await?.can_manage();
+
+    return Template::new(TEMPLATE_PATH)
+        .field("selected", selected)
+        .field("semesters", &semesters)
+        .field("small_groups", groups)
+        .field("can_manage", can_manage)
+        .field("can_apply", can_apply)
+        .render_into_page(&req, "Small Groups")
+        .await;
+}
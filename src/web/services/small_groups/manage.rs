@@ -0,0 +1,155 @@
+//! Coordinator services to edit small groups and assign mentors and projects.
+//!
+//! These are all form submissions from the small group page, which redirect back to it.
+
+use crate::api::rcos::small_groups::authorization_for::SmallGroupAuthorizationFor;
+use crate::api::rcos::small_groups::get::SmallGroup;
+use crate::api::rcos::small_groups::mutations::{
+    AddSmallGroupMentor, AddSmallGroupProject, EditSmallGroup, RemoveSmallGroupMentor,
+    RemoveSmallGroupProject,
+};
+use crate::error::TelescopeError;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use crate::web::services::small_groups::create::SmallGroupForm;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::HttpResponse;
+
+/// Register small group management services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(edit)
+        .service(add_mentor)
+        .service(remove_mentor)
+        .service(add_project)
+        .service(remove_project);
+}
+
+/// Error if the authenticated user cannot manage small groups.
+async fn require_manager(auth: &AuthenticationCookie) -> Result<(), TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    SmallGroupAuthorizationFor::get(Some(username))
+        .await?
+        .can_manage()
+        .then(|| ())
+        .ok_or(TelescopeError::Forbidden)
+}
+
+/// Redirect back to a small group's page.
+fn back_to_group(small_group_id: i64) -> HttpResponse {
+    HttpResponse::Found()
+        .header(LOCATION, format!("/small_group/{}", small_group_id))
+        .finish()
+}
+
+/// Edit a small group's title and location.
+#[post("/small_group/{small_group_id}/edit")]
+async fn edit(
+    auth: AuthenticationCookie,
+    Path(small_group_id): Path<i64>,
+    Form(SmallGroupForm {
+        title, location, ..
+    }): Form<SmallGroupForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    require_manager(&auth).await?;
+
+    if title.trim().is_empty() {
+        return Err(TelescopeError::BadRequest {
+            header: "Missing Title".into(),
+            message: "Small groups must have a title.".into(),
+            show_status_code: false,
+        });
+    }
+
+    let location: Option<String> = Some(location).filter(|l| !l.trim().is_empty());
+    EditSmallGroup::execute(small_group_id, title, location)
+        .await?
+        .ok_or(TelescopeError::resource_not_found(
+            "Small Group Not Found",
+            "Could not find a small group for this ID.",
+        ))?;
+
+    Ok(back_to_group(small_group_id))
+}
+
+/// Form to add or remove a mentor.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct MentorForm {
+    /// The username of the mentor.
+    username: String,
+}
+
+/// Assign a mentor to a small group. Only users with an approved mentor proposal for the
+/// small group's semester can be assigned.
+#[post("/small_group/{small_group_id}/mentors/add")]
+async fn add_mentor(
+    auth: AuthenticationCookie,
+    Path(small_group_id): Path<i64>,
+    Form(MentorForm { username }): Form<MentorForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    require_manager(&auth).await?;
+    let username: String = username.trim().to_string();
+
+    let is_eligible: bool = SmallGroup::get(small_group_id)
+        .await?
+        .eligible_mentors
+        .iter()
+        .any(|mentor| mentor.username == username);
+
+    if !is_eligible {
+        return Err(TelescopeError::BadRequest {
+            header: "Not an Approved Mentor".into(),
+            message: format!(
+                "{} does not have an approved mentor proposal for this semester.",
+                username
+            ),
+            show_status_code: false,
+        });
+    }
+
+    AddSmallGroupMentor::execute(small_group_id, username).await?;
+    Ok(back_to_group(small_group_id))
+}
+
+/// Remove a mentor from a small group.
+#[post("/small_group/{small_group_id}/mentors/remove")]
+async fn remove_mentor(
+    auth: AuthenticationCookie,
+    Path(small_group_id): Path<i64>,
+    Form(MentorForm { username }): Form<MentorForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    require_manager(&auth).await?;
+    RemoveSmallGroupMentor::execute(small_group_id, username).await?;
+    Ok(back_to_group(small_group_id))
+}
+
+/// Form to attach or detach a project.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ProjectForm {
+    /// The ID of the project.
+    project_id: i64,
+}
+
+/// Attach a project to a small group.
+#[post("/small_group/{small_group_id}/projects/add")]
+async fn add_project(
+    auth: AuthenticationCookie,
+    Path(small_group_id): Path<i64>,
+    Form(ProjectForm { project_id }): Form<ProjectForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    require_manager(&auth).await?;
+    AddSmallGroupProject::execute(small_group_id, project_id).await?;
+    Ok(back_to_group(small_group_id))
+}
+
+/// Detach a project from a small group.
+#[post("/small_group/{small_group_id}/projects/remove")]
+async fn remove_project(
+    auth: AuthenticationCookie,
+    Path(small_group_id): Path<i64>,
+    Form(ProjectForm { project_id }): Form<ProjectForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    require_manager(&auth).await?;
+    RemoveSmallGroupProject::execute(small_group_id, project_id).await?;
+    Ok(back_to_group(small_group_id))
+}
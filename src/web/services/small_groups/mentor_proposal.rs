@@ -0,0 +1,139 @@
+//! Mentor application form.
+//!
+//! Enrolled users apply to mentor a small group in the ongoing semester. Coordinators review
+//! applications from the admin panel. Applications can be revised until they are approved.
+
+use crate::api::rcos::mentor_proposals::context::{
+    mentor_proposal_context::ResponseData, MentorProposalContext,
+};
+use crate::api::rcos::mentor_proposals::submit::SubmitMentorProposal;
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, ServiceConfig};
+use actix_web::HttpResponse;
+
+/// The handlebars template for the mentor application form.
+const PROPOSAL_FORM: &'static str = "small_groups/mentor_proposal";
+
+/// Register mentor application services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(proposal_page).service(submit_proposal);
+}
+
+/// Get the mentor application context for a user and make the form for it.
+async fn get_context_and_make_form(
+    username: String,
+) -> Result<(FormTemplate, ResponseData), TelescopeError> {
+    let context: ResponseData = MentorProposalContext::get(username).await?;
+
+    // There has to be an ongoing semester to mentor in.
+    let semester = context
+        .current_semester
+        .first()
+        .ok_or(TelescopeError::BadRequest {
+            header: "No Ongoing Semester".into(),
+            message: "There is no ongoing semester to apply to mentor in.".into(),
+            show_status_code: false,
+        })?;
+
+    // The existing proposal, if any.
+    let proposal = semester
+        .enrollments
+        .first()
+        .and_then(|enrollment| enrollment.mentor_proposals.first());
+
+    let mut form = FormTemplate::new(
+        PROPOSAL_FORM,
+        format!("Apply to Mentor in {}", semester.title),
+    );
+    form.template = json!({
+        "semester": semester,
+        "is_enrolled": !semester.enrollments.is_empty(),
+        "proposal": proposal,
+        "data": proposal
+    });
+
+    return Ok((form, context));
+}
+
+/// Mentor application form page.
+#[get("/mentor_proposal")]
+async fn proposal_page(auth: AuthenticationCookie) -> Result<FormTemplate, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let (form, _) = get_context_and_make_form(username).await?;
+    return Ok(form);
+}
+
+/// Form submitted to apply to mentor.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ProposalForm {
+    /// Why the user wants to mentor.
+    reason: String,
+    /// The skills and experience the user would bring to their small group.
+    skillset: String,
+}
+
+/// Endpoint to submit a mentor application.
+#[post("/mentor_proposal")]
+async fn submit_proposal(
+    auth: AuthenticationCookie,
+    Form(ProposalForm { reason, skillset }): Form<ProposalForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let (mut form, context) = get_context_and_make_form(username.clone()).await?;
+    // The semester exists since the form was created.
+    let semester = &context.current_semester[0];
+
+    // Add the submitted data back to the form.
+    form.template["data"] = json!({
+        "reason": &reason,
+        "skillset": &skillset,
+    });
+
+    // Proposals are tied to an enrollment.
+    let enrollment = match semester.enrollments.first() {
+        Some(enrollment) => enrollment,
+        None => {
+            form.template["issues"]["enrollment"] =
+                json!("You must enroll in this semester before applying to mentor.");
+            return Err(TelescopeError::invalid_form(&form));
+        }
+    };
+
+    // Approved proposals are final.
+    if enrollment
+        .mentor_proposals
+        .first()
+        .and_then(|proposal| proposal.is_approved)
+        .unwrap_or(false)
+    {
+        form.template["issues"]["approved"] =
+            json!("Your application has already been approved and can no longer be changed.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    let mut valid: bool = true;
+
+    if reason.trim().is_empty() {
+        form.template["issues"]["reason"] = json!("Please explain why you want to mentor.");
+        valid = false;
+    }
+
+    if skillset.trim().is_empty() {
+        form.template["issues"]["skillset"] = json!("Please describe your skills and experience.");
+        valid = false;
+    }
+
+    if !valid {
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    SubmitMentorProposal::execute(username, semester.semester_id.clone(), reason, skillset).await?;
+
+    // Send the user back to the form to see the status of their application.
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/mentor_proposal")
+        .finish())
+}
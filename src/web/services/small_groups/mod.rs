@@ -0,0 +1,50 @@
+//! Small group pages and management services.
+
+use crate::api::rcos::small_groups::authorization_for::{
+    SmallGroupAuthorizationFor, UserSmallGroupAuthorization,
+};
+use crate::error::TelescopeError;
+use crate::web::middlewares::authorization::Authorization;
+use actix_web::web::ServiceConfig;
+
+mod create;
+mod dashboard;
+mod list;
+mod manage;
+mod mentor_proposal;
+mod view;
+
+/// Register small group services.
+pub fn register(config: &mut ServiceConfig) {
+    // Small groups list page.
+    list::register(config);
+
+    // Small group creation services.
+    create::register(config);
+
+    // Coordinator management of mentors and projects.
+    manage::register(config);
+
+    // Mentor dashboard.
+    dashboard::register(config);
+
+    // Mentor applications.
+    mentor_proposal::register(config);
+
+    // The small group viewing endpoint must be registered after the creation endpoint,
+    // so that the ID path doesn't match the create path.
+    config.service(view::small_group);
+}
+
+/// Create an authorization middleware that requires small group management permissions.
+fn make_management_middleware() -> Authorization {
+    Authorization::new(|username: String| {
+        Box::pin(async move {
+            let auth: UserSmallGroupAuthorization =
+                SmallGroupAuthorizationFor::get(Some(username)).await?;
+            auth.can_manage()
+                .then(|| ())
+                .ok_or(TelescopeError::Forbidden)
+        })
+    })
+}
@@ -0,0 +1,49 @@
+//! Small group details page.
+
+use crate::api::rcos::small_groups::authorization_for::{
+    SmallGroupAuthorizationFor, UserSmallGroupAuthorization,
+};
+use crate::api::rcos::small_groups::get::SmallGroup;
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::Identity;
+use actix_web::web::Path;
+use actix_web::HttpRequest;
+
+/// The handlebars template for the small group page.
+const TEMPLATE_PATH: &'static str = "small_groups/page";
+
+/// Page to view a small group's mentors, projects, and members.
+#[get("/small_group/{small_group_id}")]
+pub async fn small_group(
+    req: HttpRequest,
+    identity: Identity,
+    Path(small_group_id): Path<i64>,
+) -> Result<Template, TelescopeError> {
+    let data = SmallGroup::get(small_group_id).await?;
+    let small_group = data.small_group.ok_or(TelescopeError::resource_not_found(
+        "Small Group Not Found",
+        "Could not find a small group for this ID.",
+    ))?;
+
+    let auth: UserSmallGroupAuthorization =
+        SmallGroupAuthorizationFor::get(identity.get_rcos_username().await?).await?;
+
+    let title: String = small_group.title.clone();
+    let mut template = Template::new(TEMPLATE_PATH)
+        .field("can_manage", auth.can_manage())
+        .field(
+            "can_view_dashboard",
+            auth.can_view_dashboard(small_group_id),
+        )
+        .field("small_group", small_group);
+
+    // Only send the lists of all projects and eligible mentors when they will be used.
+    if auth.can_manage() {
+        template = template
+            .field("all_projects", data.all_projects)
+            .field("eligible_mentors", data.eligible_mentors);
+    }
+
+    return template.render_into_page(&req, title).await;
+}
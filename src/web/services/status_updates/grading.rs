@@ -0,0 +1,183 @@
+//! Status update grading services for mentors and coordinators.
+//!
+//! Each small group has a grading queue per status update at
+//! `/small_group/{id}/grading/{status_update_id}`, listing every student enrolled in the
+//! small group's projects with their submission.
+
+use crate::api::rcos::status_updates::grading::{
+    grading_context::ResponseData as GradingContextData, GradeSubmission, GradingContext,
+    GradingQueue,
+};
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+/// The handlebars template for the grading queue.
+const QUEUE_TEMPLATE: &'static str = "status_updates/grading";
+
+/// Register status update grading services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(grading_index)
+        .service(grading_queue)
+        .service(submit_grade);
+}
+
+/// Get the grading context for a small group and error if the viewer cannot grade for it.
+async fn grading_context_checked(
+    auth: &AuthenticationCookie,
+    small_group_id: i64,
+) -> Result<(String, GradingContextData), TelescopeError> {
+    let viewer: String = auth.get_rcos_username_or_error().await?;
+    let context: GradingContextData = GradingContext::get(small_group_id, viewer.clone()).await?;
+
+    if context.small_group.is_none() {
+        return Err(TelescopeError::resource_not_found(
+            "Small Group Not Found",
+            "Could not find a small group for this ID.",
+        ));
+    }
+
+    if !context.viewer_can_grade() {
+        return Err(TelescopeError::Forbidden);
+    }
+
+    return Ok((viewer, context));
+}
+
+/// Redirect to the grading queue for the most recent status update of a small group's semester.
+#[get("/small_group/{small_group_id}/grading")]
+async fn grading_index(
+    auth: AuthenticationCookie,
+    Path(small_group_id): Path<i64>,
+) -> Result<HttpResponse, TelescopeError> {
+    let (_, context) = grading_context_checked(&auth, small_group_id).await?;
+
+    // Status updates are ordered most recent first.
+    let status_update_id: i64 = context
+        .small_group
+        .as_ref()
+        .and_then(|group| group.semester.status_updates.first())
+        .map(|status_update| status_update.status_update_id)
+        .ok_or(TelescopeError::resource_not_found(
+            "No Status Updates",
+            "There are no status updates for this small group's semester yet.",
+        ))?;
+
+    Ok(HttpResponse::Found()
+        .header(
+            LOCATION,
+            format!(
+                "/small_group/{}/grading/{}",
+                small_group_id, status_update_id
+            ),
+        )
+        .finish())
+}
+
+/// Grading queue for a small group and status update.
+#[get("/small_group/{small_group_id}/grading/{status_update_id}")]
+async fn grading_queue(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+    Path((small_group_id, status_update_id)): Path<(i64, i64)>,
+) -> Result<Template, TelescopeError> {
+    let (_, context) = grading_context_checked(&auth, small_group_id).await?;
+    let small_group = context.small_group.unwrap();
+
+    // Make sure the status update is from the small group's semester.
+    let status_update = small_group
+        .semester
+        .status_updates
+        .iter()
+        .find(|status_update| status_update.status_update_id == status_update_id)
+        .cloned()
+        .ok_or(TelescopeError::resource_not_found(
+            "Status Update Not Found",
+            "Could not find this status update in the small group's semester.",
+        ))?;
+
+    let projects = GradingQueue::get(small_group_id, status_update_id).await?;
+
+    return Template::new(QUEUE_TEMPLATE)
+        .field("status_update", status_update)
+        .field("small_group", small_group)
+        .field("projects", projects)
+        .render_into_page(&req, "Status Update Grading")
+        .await;
+}
+
+/// Grade submitted for a student's submission.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct GradeForm {
+    /// The student whose submission is graded.
+    username: String,
+    /// The grade.
+    grade: f64,
+    /// Optional comments for the student.
+    #[serde(default)]
+    grader_comments: String,
+}
+
+/// Endpoint to grade a submission.
+#[post("/small_group/{small_group_id}/grading/{status_update_id}")]
+async fn submit_grade(
+    auth: AuthenticationCookie,
+    Path((small_group_id, status_update_id)): Path<(i64, i64)>,
+    Form(GradeForm {
+        username,
+        grade,
+        grader_comments,
+    }): Form<GradeForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let (grader, _) = grading_context_checked(&auth, small_group_id).await?;
+
+    if !grade.is_finite() || grade < 0.0 {
+        return Err(TelescopeError::BadRequest {
+            header: "Invalid Grade".into(),
+            message: "Grades must be non-negative numbers.".into(),
+            show_status_code: false,
+        });
+    }
+
+    // Graders can only grade students in their small group.
+    let in_small_group: bool = GradingQueue::get(small_group_id, status_update_id)
+        .await?
+        .iter()
+        .flat_map(|group_project| group_project.project.enrollments.iter())
+        .any(|enrollment| enrollment.user.username == username);
+
+    if !in_small_group {
+        return Err(TelescopeError::Forbidden);
+    }
+
+    // Empty comments are stored as null.
+    let grader_comments: Option<String> =
+        Some(grader_comments).filter(|comments| !comments.trim().is_empty());
+
+    let graded: bool =
+        GradeSubmission::execute(status_update_id, username, grade, grader_comments, grader)
+            .await?;
+
+    if !graded {
+        return Err(TelescopeError::BadRequest {
+            header: "No Submission".into(),
+            message: "This student has not submitted this status update.".into(),
+            show_status_code: false,
+        });
+    }
+
+    // Send the grader back to the queue.
+    Ok(HttpResponse::Found()
+        .header(
+            LOCATION,
+            format!(
+                "/small_group/{}/grading/{}",
+                small_group_id, status_update_id
+            ),
+        )
+        .finish())
+}
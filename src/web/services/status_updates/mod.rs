@@ -0,0 +1,15 @@
+//! Weekly status update services.
+
+use actix_web::web::ServiceConfig;
+
+mod grading;
+mod submit;
+
+/// Register status update services.
+pub fn register(config: &mut ServiceConfig) {
+    // Student submission services.
+    submit::register(config);
+
+    // Mentor and coordinator grading services.
+    grading::register(config);
+}
@@ -0,0 +1,154 @@
+//! Status update submission services.
+//!
+//! Students see the status updates open to them at `/status_updates`, and can submit or edit
+//! their answers at `/status_update/{id}` until the status update closes.
+
+use crate::api::rcos::status_updates::get::{
+    status_update::StatusUpdateStatusUpdate, StatusUpdate,
+};
+use crate::api::rcos::status_updates::open::OpenStatusUpdates;
+use crate::api::rcos::status_updates::submit::SubmitStatusUpdate;
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::templates::Template;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+/// The handlebars template for the list of open status updates.
+const LIST_TEMPLATE: &'static str = "status_updates/list";
+
+/// The handlebars template for the status update submission form.
+const SUBMISSION_FORM: &'static str = "status_updates/form";
+
+/// Register status update submission services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(open_status_updates)
+        .service(submission_page)
+        .service(submit);
+}
+
+/// Page listing the status updates open to the authenticated user.
+#[get("/status_updates")]
+async fn open_status_updates(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+) -> Result<Template, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let status_updates = OpenStatusUpdates::get(username).await?;
+
+    return Template::new(LIST_TEMPLATE)
+        .field("status_updates", status_updates)
+        .render_into_page(&req, "Status Updates")
+        .await;
+}
+
+/// Get a status update and check that the user is enrolled in its semester.
+async fn get_status_update(
+    status_update_id: i64,
+    username: String,
+) -> Result<StatusUpdateStatusUpdate, TelescopeError> {
+    let status_update = StatusUpdate::get(status_update_id, username).await?.ok_or(
+        TelescopeError::resource_not_found(
+            "Status Update Not Found",
+            "Could not find a status update for this ID.",
+        ),
+    )?;
+
+    // Only students enrolled in the semester submit status updates.
+    if status_update.semester.enrollments.is_empty() {
+        return Err(TelescopeError::Forbidden);
+    }
+
+    return Ok(status_update);
+}
+
+/// Make the submission form for a status update, filled in with any existing submission.
+fn make_form(status_update: &StatusUpdateStatusUpdate) -> FormTemplate {
+    let title: String = status_update
+        .title
+        .clone()
+        .unwrap_or("Status Update".into());
+
+    let mut form = FormTemplate::new(SUBMISSION_FORM, title);
+    form.template = json!({
+        "status_update": status_update,
+        "is_open": status_update.is_open(),
+        "data": status_update.submission.first()
+    });
+    return form;
+}
+
+/// Status update submission form.
+#[get("/status_update/{status_update_id}")]
+async fn submission_page(
+    auth: AuthenticationCookie,
+    Path(status_update_id): Path<i64>,
+) -> Result<FormTemplate, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let status_update = get_status_update(status_update_id, username).await?;
+    return Ok(make_form(&status_update));
+}
+
+/// Answers submitted to a status update.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct SubmissionForm {
+    /// What the student did this week.
+    this_week: String,
+    /// What the student plans to do next week.
+    next_week: String,
+    /// Anything blocking the student's progress.
+    #[serde(default)]
+    blockers: String,
+}
+
+/// Endpoint to submit a status update.
+#[post("/status_update/{status_update_id}")]
+async fn submit(
+    auth: AuthenticationCookie,
+    Path(status_update_id): Path<i64>,
+    Form(SubmissionForm {
+        this_week,
+        next_week,
+        blockers,
+    }): Form<SubmissionForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let status_update = get_status_update(status_update_id, username.clone()).await?;
+
+    // Put the submitted answers back in the form in case there is an issue.
+    let mut form: FormTemplate = make_form(&status_update);
+    form.template["data"] = json!({
+        "this_week": &this_week,
+        "next_week": &next_week,
+        "blockers": &blockers,
+    });
+
+    // Answers can only be submitted or changed while the status update is open.
+    if !status_update.is_open() {
+        form.template["issues"]["closed"] = json!("This status update is not open.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    let mut valid: bool = true;
+    if this_week.trim().is_empty() {
+        form.template["issues"]["this_week"] = json!("This field is required.");
+        valid = false;
+    }
+    if next_week.trim().is_empty() {
+        form.template["issues"]["next_week"] = json!("This field is required.");
+        valid = false;
+    }
+    if !valid {
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    SubmitStatusUpdate::execute(status_update_id, username, this_week, next_week, blockers).await?;
+
+    // Send the user back to the list of open status updates.
+    Ok(HttpResponse::Found()
+        .header(LOCATION, "/status_updates")
+        .finish())
+}
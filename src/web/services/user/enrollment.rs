@@ -0,0 +1,181 @@
+//! Semester enrollment form.
+//!
+//! Users enroll themselves in the ongoing semester, picking a project (or none yet), a credit
+//! count, and whether they are taking RCOS for pay. Enrollments can be changed until the
+//! semester's add/drop deadline.
+//!
+//! Taking RCOS for pay requires a pay request, which a faculty advisor has to approve before
+//! the enrollment is marked for pay.
+
+use crate::api::rcos::pay_requests::submit::{DeletePayRequest, UpsertPayRequest};
+use crate::api::rcos::users::enrollments::add_drop_deadline;
+use crate::api::rcos::users::enrollments::context::{
+    enrollment_context::ResponseData, EnrollmentContext,
+};
+use crate::api::rcos::users::enrollments::upsert::UpsertEnrollment;
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::web::profile_for;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, ServiceConfig};
+use actix_web::HttpResponse;
+use chrono::{Local, NaiveDate};
+
+/// The path from the templates directory to the enrollment form template.
+const ENROLLMENT_FORM: &'static str = "user/enrollment/form";
+
+/// The most credits a user can enroll in RCOS for.
+const MAX_CREDITS: i64 = 4;
+
+/// Register enrollment services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(enrollment_page).service(submit_enrollment);
+}
+
+/// Get the enrollment context for the authenticated user and make the form for it.
+/// Return the form, the context, and whether the add/drop deadline has passed.
+async fn get_context_and_make_form(
+    username: String,
+) -> Result<(FormTemplate, ResponseData, bool), TelescopeError> {
+    let context: ResponseData = EnrollmentContext::get(username).await?;
+
+    // There has to be an ongoing semester to enroll in.
+    let semester = context
+        .current_semester
+        .first()
+        .ok_or(TelescopeError::BadRequest {
+            header: "No Ongoing Semester".into(),
+            message: "There is no ongoing semester to enroll in.".into(),
+            show_status_code: false,
+        })?;
+
+    let deadline: NaiveDate = add_drop_deadline(semester.start_date);
+    let past_deadline: bool = Local::today().naive_local() > deadline;
+
+    let mut form = FormTemplate::new(ENROLLMENT_FORM, format!("Enroll in {}", semester.title));
+    form.template = json!({
+        "semester": semester,
+        "deadline": deadline,
+        "past_deadline": past_deadline,
+        "projects": &context.projects,
+        // The existing enrollment, if any.
+        "data": context.enrollments.first(),
+        // The existing pay request, if any.
+        "pay_request": context.enrollments.first().and_then(|e| e.pay_request.first())
+    });
+
+    return Ok((form, context, past_deadline));
+}
+
+/// Enrollment form page.
+#[get("/enroll")]
+async fn enrollment_page(auth: AuthenticationCookie) -> Result<FormTemplate, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let (form, _, _) = get_context_and_make_form(username).await?;
+    return Ok(form);
+}
+
+/// Enrollment form submission.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct EnrollmentForm {
+    /// The selected project ID. Empty string for no project yet.
+    project_id: String,
+    /// The number of credits.
+    credits: i64,
+    /// Checkbox for taking RCOS for pay instead of credit.
+    #[serde(default)]
+    is_for_pay: Option<bool>,
+    /// Why the user is asking to take RCOS for pay. Required if they are.
+    #[serde(default)]
+    pay_reason: String,
+}
+
+/// Endpoint to submit enrollment form.
+#[post("/enroll")]
+async fn submit_enrollment(
+    auth: AuthenticationCookie,
+    Form(EnrollmentForm {
+        project_id,
+        credits,
+        is_for_pay,
+        pay_reason,
+    }): Form<EnrollmentForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let (mut form, context, past_deadline) = get_context_and_make_form(username.clone()).await?;
+
+    // Parse the project selection. The empty option means no project yet.
+    let project_id: Option<i64> = project_id.parse().ok();
+    let is_for_pay: bool = is_for_pay.unwrap_or(false);
+    let pay_reason: String = pay_reason.trim().to_string();
+
+    // Add the submitted data back to the form.
+    form.template["data"] = json!({
+        "project_id": project_id,
+        "credits": credits,
+        "is_for_pay": is_for_pay,
+        "pay_reason": &pay_reason,
+    });
+
+    // Enrollments are locked after the add/drop deadline.
+    if past_deadline {
+        form.template["issues"]["deadline"] =
+            json!("The add/drop deadline for this semester has passed.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    if credits < 0 || credits > MAX_CREDITS {
+        form.template["issues"]["credits"] =
+            json!(format!("Credits must be between 0 and {}.", MAX_CREDITS));
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    if is_for_pay && pay_reason.is_empty() {
+        form.template["issues"]["pay_reason"] =
+            json!("Please explain why you are taking RCOS for pay.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    // Get the pay request already on file, if any.
+    let pay_request = context
+        .enrollments
+        .first()
+        .and_then(|enrollment| enrollment.pay_request.first());
+    let pay_approved: bool = pay_request
+        .and_then(|request| request.is_approved)
+        .unwrap_or(false);
+
+    // The enrollment is only for pay once a faculty advisor approves the pay request.
+    // The semester exists since the form was created.
+    let semester_id: String = context.current_semester[0].semester_id.clone();
+    UpsertEnrollment::execute(
+        username.clone(),
+        semester_id.clone(),
+        project_id,
+        credits,
+        is_for_pay && pay_approved,
+    )
+    .await?;
+
+    if is_for_pay {
+        // File a new request, or refile if the reason changed on a request that was not
+        // approved. Approved requests are left alone.
+        let refile: bool = match pay_request {
+            None => true,
+            Some(request) => !pay_approved && request.reason != pay_reason,
+        };
+
+        if refile {
+            UpsertPayRequest::execute(username.clone(), semester_id, pay_reason).await?;
+        }
+    } else if pay_request.is_some() {
+        // Withdraw the pay request if the user is no longer asking to be paid.
+        DeletePayRequest::execute(username.clone(), semester_id).await?;
+    }
+
+    // Send the user back to their profile.
+    Ok(HttpResponse::Found()
+        .header(LOCATION, profile_for(username.as_str()))
+        .finish())
+}
@@ -0,0 +1,148 @@
+//! Student grades page and grade appeal form.
+//!
+//! Students can see their mid-year and final grade for each semester they enrolled in. Once a
+//! final grade is entered, they can appeal it with the grade they expected and a reason, and
+//! update their appeal until a faculty advisor marks it handled.
+
+use crate::api::rcos::grades::student::{
+    student_grades::StudentGradesEnrollments, StudentGrades, UpsertGradeAppeal,
+};
+use crate::error::TelescopeError;
+use crate::templates::forms::FormTemplate;
+use crate::templates::Template;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, Path, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+
+/// The path from the templates directory to the grade appeal form template.
+const APPEAL_FORM: &'static str = "user/grades/appeal";
+
+/// Register grade services.
+pub fn register(config: &mut ServiceConfig) {
+    config
+        .service(grades_page)
+        .service(appeal_page)
+        .service(submit_appeal);
+}
+
+/// Page showing the authenticated user's grades.
+#[get("/grades")]
+async fn grades_page(
+    req: HttpRequest,
+    auth: AuthenticationCookie,
+) -> Result<Template, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let enrollments = StudentGrades::get(username).await?;
+
+    Template::new("user/grades/index")
+        .field("enrollments", enrollments)
+        .render_into_page(&req, "My Grades")
+        .await
+}
+
+/// Get the user's enrollment in a semester and make the appeal form for it. Students can
+/// only appeal a final grade that has been entered, and only until their appeal is handled.
+async fn get_enrollment_and_make_form(
+    username: String,
+    semester_id: &str,
+) -> Result<FormTemplate, TelescopeError> {
+    let enrollment: StudentGradesEnrollments = StudentGrades::get(username)
+        .await?
+        .into_iter()
+        .find(|enrollment| enrollment.semester_id == semester_id)
+        .ok_or(TelescopeError::resource_not_found(
+            "Enrollment Not Found",
+            "You were not enrolled in this semester.",
+        ))?;
+
+    if enrollment.final_grade.is_none() {
+        return Err(TelescopeError::BadRequest {
+            header: "No Final Grade".into(),
+            message: "Your final grade for this semester has not been entered yet.".into(),
+            show_status_code: false,
+        });
+    }
+
+    if enrollment.appeal.iter().any(|appeal| appeal.is_handled) {
+        return Err(TelescopeError::BadRequest {
+            header: "Appeal Already Handled".into(),
+            message: "A faculty advisor has already handled your appeal for this semester.".into(),
+            show_status_code: false,
+        });
+    }
+
+    let mut form = FormTemplate::new(
+        APPEAL_FORM,
+        format!("Appeal Grade for {}", enrollment.semester.title),
+    );
+    form.template = json!({
+        "enrollment": &enrollment,
+        // The existing appeal, if any.
+        "data": enrollment.appeal.first(),
+    });
+
+    return Ok(form);
+}
+
+/// Grade appeal form page.
+#[get("/grades/{semester_id}/appeal")]
+async fn appeal_page(
+    auth: AuthenticationCookie,
+    Path(semester_id): Path<String>,
+) -> Result<FormTemplate, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    return get_enrollment_and_make_form(username, semester_id.as_str()).await;
+}
+
+/// Grade appeal form submission.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct AppealForm {
+    /// The grade the student expected.
+    expected_grade: String,
+    /// Why the student is appealing.
+    reason: String,
+}
+
+/// Endpoint to submit a grade appeal.
+#[post("/grades/{semester_id}/appeal")]
+async fn submit_appeal(
+    auth: AuthenticationCookie,
+    Path(semester_id): Path<String>,
+    Form(AppealForm {
+        expected_grade,
+        reason,
+    }): Form<AppealForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let username: String = auth.get_rcos_username_or_error().await?;
+    let mut form: FormTemplate =
+        get_enrollment_and_make_form(username.clone(), semester_id.as_str()).await?;
+
+    let expected_grade: String = expected_grade.trim().to_string();
+    let reason: String = reason.trim().to_string();
+
+    // Add the submitted data back to the form.
+    form.template["data"] = json!({
+        "expected_grade": &expected_grade,
+        "reason": &reason,
+    });
+
+    let mut valid: bool = true;
+    if expected_grade.is_empty() {
+        form.template["issues"]["expected_grade"] = json!("Enter the grade you expected.");
+        valid = false;
+    }
+
+    if reason.is_empty() {
+        form.template["issues"]["reason"] = json!("Explain why you are appealing your grade.");
+        valid = false;
+    }
+
+    if !valid {
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
+    UpsertGradeAppeal::execute(username, semester_id, expected_grade, reason).await?;
+
+    Ok(HttpResponse::Found().header(LOCATION, "/grades").finish())
+}
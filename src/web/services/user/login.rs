@@ -21,7 +21,7 @@ pub async fn login_page(req: HttpRequest) -> Result<Template, TelescopeError> {
 /// homepage.
 pub async fn logout(identity: Identity) -> HttpResponse {
     // Forget the user's identity
-    identity.forget();
+    identity.forget().await;
     // Redirect the user to the homepage.
     HttpResponse::Found().header(LOCATION, "/").finish()
 }
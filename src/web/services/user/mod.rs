@@ -3,9 +3,12 @@
 use actix_web::web::ServiceConfig;
 
 pub mod developers;
+mod enrollment;
+mod grades;
 mod login;
 pub mod profile;
 mod register;
+mod sessions;
 
 /// Register user related services.
 pub fn register(config: &mut ServiceConfig) {
@@ -15,6 +18,15 @@ pub fn register(config: &mut ServiceConfig) {
     // User profile and settings.
     profile::register(config);
 
+    // Semester enrollment form.
+    enrollment::register(config);
+
+    // Grades and grade appeals.
+    grades::register(config);
+
+    // Session listing and revocation.
+    sessions::register(config);
+
     // Everything else
     config
         // Login related services.
@@ -6,11 +6,13 @@ use crate::api::rcos::users::profile::{
     Profile,
 };
 use crate::api::rcos::users::UserRole;
+use crate::audit::AuditEntry;
 use crate::error::TelescopeError;
 use crate::templates::forms::FormTemplate;
 use crate::templates::Template;
 use crate::web::profile_for;
 use crate::web::services::auth::identity::{AuthenticationCookie, Identity};
+use crate::web::sessions::session_store;
 use actix_web::web::{Form, Query, ServiceConfig};
 use actix_web::{http::header::LOCATION, HttpRequest, HttpResponse};
 use chrono::{Datelike, Local};
@@ -69,6 +71,8 @@ async fn profile(
     // Make a profile template
     return Template::new(TEMPLATE_NAME)
         .field("data", response)
+        // Link to the sessions page if sessions are stored on the server.
+        .field("sessions_enabled", session_store().is_some())
         // Render it inside a page (with the user's name as the title)
         .render_into_page(&req, page_title)
         .await;
@@ -171,6 +175,15 @@ async fn save_changes(
     // Convert the cohort to a number or default to no cohort input. This should be checked client side.
     let cohort: Option<i64> = cohort.parse::<i64>().ok();
 
+    // Get the user's current role and whether they can switch to the submitted role
+    // before the context is overwritten with the submitted info.
+    let previous_role: UserRole = serde_json::from_value(form.template["context"]["role"].clone())
+        .map_err(|_| TelescopeError::ise("Could not get current role from edit context."))?;
+    let role_allowed: bool = json!(role)
+        .as_str()
+        .and_then(|role_key| form.template["roles"][role_key].as_bool())
+        .unwrap_or(false);
+
     // Fill the form with the submitted info.
     form.template["context"]["first_name"] = json!(&first_name);
     form.template["context"]["last_name"] = json!(&last_name);
@@ -188,6 +201,13 @@ async fn save_changes(
         return Err(TelescopeError::invalid_form(&form));
     }
 
+    // Users cannot change their own role past what the role switching rules allow.
+    // Admins can make any change from the admin panel.
+    if !role_allowed {
+        form.template["issues"]["role"] = json!("You cannot switch to this role.");
+        return Err(TelescopeError::invalid_form(&form));
+    }
+
     // Execute GraphQL mutation to save changes.
     let username = SaveProfileEdits::execute(username, first_name, last_name, cohort, role)
         .await?
@@ -195,6 +215,18 @@ async fn save_changes(
             "Could not save changes -- user not found.",
         ))?;
 
+    // Record role changes to the audit trail.
+    if previous_role != role {
+        AuditEntry::new(
+            username.as_str(),
+            "role_change",
+            username.as_str(),
+            format!("Changed own role from {} to {}.", previous_role, role),
+        )
+        .record()
+        .await;
+    }
+
     // On success, redirect to user's profile.
     return Ok(HttpResponse::Found()
         .header(LOCATION, profile_for(username.as_str()))
@@ -9,6 +9,8 @@ use crate::templates::forms::FormTemplate;
 use crate::templates::{auth, page, Template};
 use crate::web::profile_for;
 use crate::web::services::auth::identity::{AuthenticationCookie, RootIdentity};
+use crate::web::services::auth::oauth2_providers::bitbucket::BitBucketUser;
+use crate::web::services::auth::oauth2_providers::gitlab::GitLabUser;
 use crate::web::services::auth::rpi_cas::RpiCasIdentity;
 use actix_web::http::header::LOCATION;
 use actix_web::web::Form;
@@ -73,12 +75,42 @@ async fn empty_registration_form(id: &RootIdentity) -> Result<FormTemplate, Tele
         }
 
         RootIdentity::RpiCas(r) => {
+            // Pre-fill the form with the profile released by the CAS server, if any.
+            let profile = r.profile.clone().unwrap_or_default();
             form.template = json!({
                 "info": {
-                    "username": format!("{}@rpi.edu", r.rcs_id),
-                }
+                    "username": profile.email.unwrap_or_else(|| format!("{}@rpi.edu", r.rcs_id)),
+                },
+                "first_name": { "value": profile.first_name },
+                "last_name": { "value": profile.last_name },
             });
         }
+
+        RootIdentity::GitLab(g) => {
+            form.template = g.get_authenticated_user().await.map(|gitlab_user| {
+                json!({
+                    "icon": UserAccountType::GitLab,
+                    "info": {
+                        "username": gitlab_user.username,
+                        "avatar_url": gitlab_user.avatar_url,
+                        "profile_url": gitlab_user.web_url,
+                    }
+                })
+            })?;
+        }
+
+        RootIdentity::BitBucket(b) => {
+            // There is no feather icon for BitBucket, so none is shown.
+            form.template = b.get_authenticated_user().await.map(|bitbucket_user| {
+                json!({
+                    "info": {
+                        "username": bitbucket_user.nickname,
+                        "avatar_url": bitbucket_user.links.avatar.map(|link| link.href),
+                        "profile_url": bitbucket_user.links.html.map(|link| link.href),
+                    }
+                })
+            })?;
+        }
     }
 
     return Ok(form);
@@ -212,7 +244,7 @@ pub async fn submit_registration(
         }
 
         // On RPI CAS based identity
-        RootIdentity::RpiCas(RpiCasIdentity { rcs_id }) => CreateOneUser::make_variables(
+        RootIdentity::RpiCas(RpiCasIdentity { rcs_id, .. }) => CreateOneUser::make_variables(
             rcs_id.clone(),
             first_name,
             last_name,
@@ -221,6 +253,32 @@ pub async fn submit_registration(
             UserAccountType::Rpi,
             rcs_id.clone(),
         ),
+
+        // On GitLab authenticated identity.
+        RootIdentity::GitLab(g) => {
+            let GitLabUser { id, username, .. } = g.get_authenticated_user().await?;
+            CreateOneUser::make_variables(
+                username,
+                first_name,
+                last_name,
+                UserRole::External,
+                UserAccountType::GitLab,
+                id.to_string(),
+            )
+        }
+
+        // On BitBucket authenticated identity.
+        RootIdentity::BitBucket(b) => {
+            let BitBucketUser { uuid, nickname, .. } = b.get_authenticated_user().await?;
+            CreateOneUser::make_variables(
+                nickname,
+                first_name,
+                last_name,
+                UserRole::External,
+                UserAccountType::BitBucket,
+                uuid,
+            )
+        }
     };
 
     // Extract the platform for use in error reporting if necessary.
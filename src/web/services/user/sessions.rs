@@ -0,0 +1,104 @@
+//! Page listing the user's active sessions, where they can revoke them. This is only available
+//! when sessions are stored on the server.
+
+use crate::error::TelescopeError;
+use crate::templates::Template;
+use crate::web::services::auth::identity::Identity;
+use crate::web::sessions::{session_store, Session, SessionStore};
+use actix_web::http::header::LOCATION;
+use actix_web::web::{Form, ServiceConfig};
+use actix_web::{HttpRequest, HttpResponse};
+use serde_json::Value;
+
+/// Register session services.
+pub fn register(config: &mut ServiceConfig) {
+    config.service(sessions_page).service(revoke);
+}
+
+/// Get the session store, or an error if sessions are stored in the identity cookie.
+fn enabled_store() -> Result<&'static dyn SessionStore, TelescopeError> {
+    session_store().ok_or(TelescopeError::resource_not_found(
+        "Sessions Not Available",
+        "This Telescope instance does not store sessions on the server, so they cannot be \
+        listed or revoked.",
+    ))
+}
+
+/// Get the RCOS username of the authenticated user.
+async fn authenticated_username(identity: &Identity) -> Result<String, TelescopeError> {
+    identity
+        .identity()
+        .await
+        .ok_or(TelescopeError::NotAuthenticated)?
+        .get_rcos_username_or_error()
+        .await
+}
+
+/// Page listing the user's active sessions, most recently used first.
+#[get("/sessions")]
+async fn sessions_page(req: HttpRequest, identity: Identity) -> Result<Template, TelescopeError> {
+    let store: &dyn SessionStore = enabled_store()?;
+    let username: String = authenticated_username(&identity).await?;
+    let current: Option<String> = identity.session_id();
+
+    let mut sessions: Vec<Session> = store.list_for_user(username.as_str()).await?;
+    sessions.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+
+    let sessions: Vec<Value> = sessions
+        .iter()
+        .map(|session| {
+            json!({
+                "public_id": &session.public_id,
+                "provider": session.provider().to_string(),
+                "created": &session.created,
+                "last_seen": &session.last_seen,
+                "ip_addr": &session.ip_addr,
+                "current": current.as_ref() == Some(&session.id),
+            })
+        })
+        .collect();
+
+    Template::new("user/sessions")
+        .field("username", username)
+        .field("sessions", sessions)
+        .render_into_page(&req, "Sessions")
+        .await
+}
+
+/// Form to revoke a session. Sessions are referred to by their public ID, so that session
+/// IDs are never put in the page.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct RevokeForm {
+    session: String,
+}
+
+/// Revoke one of the user's sessions. Revoking the current session signs the user out.
+#[post("/sessions/revoke")]
+async fn revoke(
+    identity: Identity,
+    Form(RevokeForm { session }): Form<RevokeForm>,
+) -> Result<HttpResponse, TelescopeError> {
+    let store: &dyn SessionStore = enabled_store()?;
+    let username: String = authenticated_username(&identity).await?;
+
+    // Users can only revoke their own sessions, so only look through those.
+    let session_id: String = store
+        .list_for_user(username.as_str())
+        .await?
+        .into_iter()
+        .find(|owned| owned.public_id == session)
+        .map(|owned| owned.id)
+        .ok_or(TelescopeError::resource_not_found(
+            "Session Not Found",
+            "Could not find this session. It may have already expired or been revoked.",
+        ))?;
+
+    // Revoking the current session is the same as signing out.
+    if identity.session_id().as_ref() == Some(&session_id) {
+        identity.forget().await;
+        return Ok(HttpResponse::Found().header(LOCATION, "/").finish());
+    }
+
+    store.remove(session_id.as_str()).await?;
+    Ok(HttpResponse::Found().header(LOCATION, "/sessions").finish())
+}
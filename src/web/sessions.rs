@@ -0,0 +1,220 @@
+//! Server-side sessions.
+//!
+//! By default the whole [`AuthenticationCookie`] is stored in the encrypted identity cookie.
+//! When a session store is configured, it is stored on the server instead and the identity
+//! cookie only holds a random session ID. This keeps access and refresh tokens off of the
+//! client, and lets sessions be listed and revoked.
+//!
+//! Stores implement [`SessionStore`]. Only an in-memory store is provided; a persistent store
+//! can be added by implementing the trait and adding a variant to [`SessionStoreKind`].
+
+use crate::api::rcos::users::UserAccountType;
+use crate::env::{global_config, SessionStoreKind};
+use crate::error::TelescopeError;
+use crate::web::services::auth::identity::AuthenticationCookie;
+use actix::{Actor, AsyncContext, Context};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use dashmap::DashMap;
+use rand::distributions::Alphanumeric;
+use rand::rngs::OsRng;
+use rand::Rng;
+use std::time::Duration as StdDuration;
+
+/// How long a session lasts without being used, in days. This matches the max age of the
+/// identity cookie.
+pub const SESSION_LIFETIME_DAYS: i64 = 1;
+
+/// The number of characters in a session ID.
+const SESSION_ID_LENGTH: usize = 48;
+
+/// The number of characters in the public ID of a session.
+const PUBLIC_ID_LENGTH: usize = 16;
+
+/// Generate a random alphanumeric string of a given length.
+fn random_id(length: usize) -> String {
+    OsRng::default()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+/// An authenticated session stored on the server.
+#[derive(Clone, Debug)]
+pub struct Session {
+    /// The random ID of this session, stored in the identity cookie.
+    pub id: String,
+    /// A separate random ID used to refer to this session on the sessions page. Unlike the
+    /// session ID, this does not authenticate anyone, so it is safe to show.
+    pub public_id: String,
+    /// The authenticated identity of this session.
+    pub identity: AuthenticationCookie,
+    /// The RCOS username of the authenticated user. This is `None` until the user has
+    /// registered.
+    pub username: Option<String>,
+    /// When this session was created.
+    pub created: DateTime<Utc>,
+    /// When this session was last used.
+    pub last_seen: DateTime<Utc>,
+    /// The IP address this session was last used from, if known.
+    pub ip_addr: Option<String>,
+}
+
+impl Session {
+    /// Create a new session with a random ID.
+    pub fn new(
+        identity: AuthenticationCookie,
+        username: Option<String>,
+        ip_addr: Option<String>,
+    ) -> Self {
+        let now: DateTime<Utc> = Utc::now();
+        Session {
+            id: random_id(SESSION_ID_LENGTH),
+            public_id: random_id(PUBLIC_ID_LENGTH),
+            identity,
+            username,
+            created: now,
+            last_seen: now,
+            ip_addr,
+        }
+    }
+
+    /// Get the platform this session is authenticated with.
+    pub fn provider(&self) -> UserAccountType {
+        self.identity.root.get_user_account_type()
+    }
+
+    /// Check if this session has gone unused for longer than the session lifetime.
+    pub fn is_expired(&self) -> bool {
+        self.last_seen + Duration::days(SESSION_LIFETIME_DAYS) < Utc::now()
+    }
+}
+
+/// A place to store sessions.
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Get an unexpired session by its ID.
+    async fn get(&self, id: &str) -> Result<Option<Session>, TelescopeError>;
+
+    /// Save a session, replacing any session with the same ID.
+    async fn put(&self, session: Session) -> Result<(), TelescopeError>;
+
+    /// Remove a session. Return `true` if it existed.
+    async fn remove(&self, id: &str) -> Result<bool, TelescopeError>;
+
+    /// List the unexpired sessions of a user.
+    async fn list_for_user(&self, username: &str) -> Result<Vec<Session>, TelescopeError>;
+
+    /// Remove all of the sessions of a user. Return the number of sessions removed.
+    async fn remove_all_for_user(&self, username: &str) -> Result<usize, TelescopeError>;
+
+    /// Remove expired sessions. Return the number of sessions removed.
+    async fn remove_expired(&self) -> Result<usize, TelescopeError>;
+}
+
+/// A session store that keeps sessions in memory. Sessions are lost on restart.
+#[derive(Default)]
+pub struct MemorySessionStore {
+    /// Sessions by ID.
+    sessions: DashMap<String, Session>,
+}
+
+impl MemorySessionStore {
+    /// Remove all of the sessions matching a predicate. Return the number removed.
+    fn remove_where(&self, predicate: impl Fn(&Session) -> bool) -> usize {
+        // Collect the keys first, since removing while iterating would deadlock.
+        let remove_keys: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|record| predicate(record.value()))
+            .map(|record| record.key().clone())
+            .collect();
+
+        return remove_keys
+            .iter()
+            .map(|key| self.sessions.remove(key))
+            .filter(Option::is_some)
+            .count();
+    }
+}
+
+#[async_trait]
+impl SessionStore for MemorySessionStore {
+    async fn get(&self, id: &str) -> Result<Option<Session>, TelescopeError> {
+        Ok(self
+            .sessions
+            .get(id)
+            .map(|record| record.value().clone())
+            .filter(|session| !session.is_expired()))
+    }
+
+    async fn put(&self, session: Session) -> Result<(), TelescopeError> {
+        self.sessions.insert(session.id.clone(), session);
+        Ok(())
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, TelescopeError> {
+        Ok(self.sessions.remove(id).is_some())
+    }
+
+    async fn list_for_user(&self, username: &str) -> Result<Vec<Session>, TelescopeError> {
+        Ok(self
+            .sessions
+            .iter()
+            .map(|record| record.value().clone())
+            .filter(|session| {
+                !session.is_expired() && session.username.as_deref() == Some(username)
+            })
+            .collect())
+    }
+
+    async fn remove_all_for_user(&self, username: &str) -> Result<usize, TelescopeError> {
+        Ok(self.remove_where(|session| session.username.as_deref() == Some(username)))
+    }
+
+    async fn remove_expired(&self) -> Result<usize, TelescopeError> {
+        Ok(self.remove_where(Session::is_expired))
+    }
+}
+
+lazy_static! {
+    /// The global session store, if one is configured.
+    static ref SESSION_STORE: Option<Box<dyn SessionStore>> = match global_config().session_store {
+        SessionStoreKind::Cookie => None,
+        SessionStoreKind::Memory => Some(Box::new(MemorySessionStore::default())),
+    };
+}
+
+/// Get the global session store. This is `None` when identities are stored in the cookie.
+pub fn session_store() -> Option<&'static dyn SessionStore> {
+    SESSION_STORE.as_deref()
+}
+
+/// A zero sized struct to act as an actor and run every hour cleaning up expired sessions.
+pub struct SessionJanitor;
+
+impl Actor for SessionJanitor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        info!("Session Janitor Started");
+
+        // Set the janitor to get called every hour.
+        let interval: StdDuration = StdDuration::new(60 * 60, 0);
+
+        ctx.run_interval(interval, |_, _| {
+            if let Some(store) = session_store() {
+                actix::spawn(async move {
+                    info!("Calling Session Janitor.");
+                    match store.remove_expired().await {
+                        Ok(removed) => {
+                            info!("Session Janitor removed {} expired sessions.", removed)
+                        }
+                        Err(e) => warn!("Session Janitor could not remove expired sessions: {}", e),
+                    }
+                });
+            }
+        });
+    }
+}